@@ -1,6 +1,7 @@
 use crate::ability::{Duration, ElapsedTime, Origin, Projectile, Target};
 use crate::arena::TILE_SIZE;
 use crate::audio::Audio;
+use crate::boss::Decoy;
 use crate::character::{Boss, Character};
 use crate::materials::Materials;
 use crate::selectors::Active;
@@ -9,7 +10,7 @@ use bevy::audio::{AudioPlayer, PlaybackSettings};
 use bevy::pbr::MeshMaterial3d;
 use bevy::prelude::{
     Commands, Component, Entity, GlobalTransform, Local, Mesh, Mesh3d, Query, Res, ResMut, Sphere,
-    Time, Timer, TimerMode, Transform, With,
+    Time, Timer, TimerMode, Transform, With, Without,
 };
 
 #[derive(Component, Debug)]
@@ -72,7 +73,7 @@ pub fn auto_shot_ability(
     time: Res<Time>,
     mut timer: Local<Timer>,
     character_query: Query<(&GlobalTransform, &AutoShot), (With<Character>, With<AutoShot>)>,
-    boss_query: Query<&GlobalTransform, (With<Boss>, With<Active>)>,
+    boss_query: Query<&GlobalTransform, (With<Boss>, Without<Decoy>, With<Active>)>,
 ) {
     // Initialize timer on first run
     if timer.duration().as_secs_f32() == 0.0 {
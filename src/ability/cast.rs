@@ -0,0 +1,49 @@
+use crate::ability::{AbilityCast, AbilityCooldowns};
+use crate::arena::CurrentArenaEntity;
+use crate::character::Character;
+use crate::class_type::ClassType;
+use crate::controls::{Action, ActionState};
+use crate::selectors::Active;
+use crate::timeline::{DraftTimeline, GlobalTimelinePause, TimelineClock, TimelineEvent};
+use bevy::prelude::*;
+
+/// The hero the player is controlling, with what it needs to cast
+type ActiveCasterQuery<'w, 's> = Single<
+    'w,
+    (Entity, &'static ClassType, &'static mut AbilityCooldowns),
+    (With<Character>, With<Active>),
+>;
+
+/// Casts the active hero's ability for each bar slot pressed this frame
+///
+/// A ready ability starts its cooldown, becomes an `AbilityCast` and is recorded into
+/// the draft timeline, so the ghost made from the recording casts it at the same time.
+pub fn cast_slotted_abilities(
+    actions: Res<ActionState>,
+    active_q: ActiveCasterQuery,
+    current: CurrentArenaEntity,
+    clock_q: Query<&TimelineClock>,
+    mut casts: EventWriter<AbilityCast>,
+    mut draft_timeline: ResMut<DraftTimeline>,
+    global_pause: Res<GlobalTimelinePause>,
+) {
+    if global_pause.is_paused {
+        return;
+    }
+
+    let (caster, class, mut cooldowns) = active_q.into_inner();
+    for (&ability, action) in class.abilities().iter().zip(Action::ABILITY_SLOTS) {
+        if !actions.just_pressed(action) || !cooldowns.is_ready(ability) {
+            continue;
+        }
+        cooldowns.start(ability);
+        casts.write(AbilityCast { caster, ability });
+
+        if let Ok(clock) = clock_q.get(current.get()) {
+            let event = TimelineEvent::ability(clock.current(), ability);
+            if let Err(e) = draft_timeline.add_event(event) {
+                bevy::log::warn!("Failed to record ability event: {:?}", e);
+            }
+        }
+    }
+}
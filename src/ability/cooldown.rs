@@ -0,0 +1,143 @@
+use crate::ability::{
+    AbilityType, AlchemistAbility, BardAbility, CardinalAbility, ForagerAbility,
+    GuildMasterAbility, HunterAbility, MerchantAbility, ThiefAbility, WarriorAbility,
+};
+use crate::status_effect::StatusEffects;
+use bevy::prelude::*;
+use std::time::Duration;
+
+impl AbilityType {
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Time before the ability can be used again
+    #[must_use]
+    pub const fn cooldown(self) -> Duration {
+        let seconds = match self {
+            Self::Hunter(ability) => match ability {
+                HunterAbility::AutoShot => 1,
+                HunterAbility::PoisonShot => 6,
+                HunterAbility::Sniper => 15,
+                HunterAbility::Trap => 20,
+            },
+            Self::Cardinal(ability) => match ability {
+                CardinalAbility::HolyNova => 8,
+                CardinalAbility::Heal => 4,
+                CardinalAbility::Barrier => 20,
+                CardinalAbility::Beam => 12,
+                CardinalAbility::Resurrect => 60,
+            },
+            Self::Alchemist(ability) => match ability {
+                AlchemistAbility::AcidFlask => 6,
+                AlchemistAbility::Ironskin => 20,
+                AlchemistAbility::Siphon => 10,
+                AlchemistAbility::Transmute => 30,
+            },
+            Self::Bard(ability) => match ability {
+                BardAbility::Cleanse => 12,
+                BardAbility::Dance => 8,
+                BardAbility::Helix => 15,
+                BardAbility::Mimic => 30,
+            },
+            Self::Forager(ability) => match ability {
+                ForagerAbility::Border => 20,
+                ForagerAbility::Boulder => 10,
+                ForagerAbility::Dig => 8,
+                ForagerAbility::Mushroom => 15,
+            },
+            Self::Merchant(ability) => match ability {
+                MerchantAbility::CoinToss => 4,
+                MerchantAbility::Dice => 10,
+                MerchantAbility::Fortune => 30,
+                MerchantAbility::Vault => 25,
+            },
+            Self::Thief(ability) => match ability {
+                ThiefAbility::Backstab => 6,
+                ThiefAbility::Pickpocket => 15,
+                ThiefAbility::ShadowStep => 12,
+                ThiefAbility::SmokeScreen => 25,
+            },
+            Self::Warrior(ability) => match ability {
+                WarriorAbility::Bash => 6,
+                WarriorAbility::Block => 10,
+                WarriorAbility::Bulwark => 25,
+                WarriorAbility::Taunt => 15,
+            },
+            Self::GuildMaster(ability) => match ability {
+                GuildMasterAbility::Decree => 10,
+                GuildMasterAbility::Rally => 12,
+                GuildMasterAbility::Levy => 5,
+                GuildMasterAbility::Muster => 30,
+            },
+        };
+        Duration::from_secs(seconds)
+    }
+}
+
+/// A running cooldown (value type stored inside AbilityCooldowns)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cooldown {
+    pub ability: AbilityType,
+    pub remaining: Duration,
+}
+
+/// Abilities a character is waiting on - only running cooldowns are stored
+#[derive(Component, Debug, Clone, Default)]
+pub struct AbilityCooldowns(pub Vec<Cooldown>);
+
+impl AbilityCooldowns {
+    /// Starts (or restarts) the ability's full cooldown
+    pub fn start(&mut self, ability: AbilityType) {
+        let remaining = ability.cooldown();
+        match self
+            .0
+            .iter_mut()
+            .find(|cooldown| cooldown.ability == ability)
+        {
+            Some(cooldown) => cooldown.remaining = remaining,
+            None => self.0.push(Cooldown { ability, remaining }),
+        }
+    }
+
+    #[must_use]
+    pub fn remaining(&self, ability: AbilityType) -> Duration {
+        self.0
+            .iter()
+            .find(|cooldown| cooldown.ability == ability)
+            .map_or(Duration::ZERO, |cooldown| cooldown.remaining)
+    }
+
+    #[must_use]
+    pub fn is_ready(&self, ability: AbilityType) -> bool {
+        self.remaining(ability).is_zero()
+    }
+
+    /// Fraction of the cooldown still to run (1.0 just used, 0.0 ready)
+    #[must_use]
+    pub fn fraction_remaining(&self, ability: AbilityType) -> f32 {
+        let total = ability.cooldown();
+        if total.is_zero() {
+            return 0.0;
+        }
+        self.remaining(ability).as_secs_f32() / total.as_secs_f32()
+    }
+
+    /// Advances every cooldown, dropping the ones that finished
+    pub fn tick(&mut self, delta: Duration) {
+        for cooldown in self.0.iter_mut() {
+            cooldown.remaining = cooldown.remaining.saturating_sub(delta);
+        }
+        self.0.retain(|cooldown| !cooldown.remaining.is_zero());
+    }
+}
+
+/// Counts down ability cooldowns, faster for hasted characters
+pub fn tick_ability_cooldowns(
+    time: Res<Time>,
+    mut cooldowns_q: Query<(&mut AbilityCooldowns, Option<&StatusEffects>)>,
+) {
+    for (mut cooldowns, effects) in cooldowns_q.iter_mut() {
+        if !cooldowns.0.is_empty() {
+            let speed = effects.map_or(1.0, StatusEffects::speed_multiplier);
+            cooldowns.tick(time.delta().mul_f32(speed));
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use crate::ability::{
+    AbilityType, AlchemistAbility, BardAbility, CardinalAbility, ForagerAbility,
+    GuildMasterAbility, HunterAbility, MerchantAbility, ThiefAbility, WarriorAbility,
+};
+use crate::arena::{Arena, ArenaOccupancy, ArenaReset, TileTerrain, footprint};
+use crate::boss::Decoy;
+use crate::character::{Boss, Character};
+use crate::combat::{DamageCause, DamageEvent, HealEvent, Health, apply_damage};
+use crate::status_effect::{
+    ApplyStatusEffect, CleanseStatusEffects, StatusEffectKind, StealStatusEffect,
+    apply_status_effects, cleanse_status_effects, steal_status_effects,
+};
+use crate::timeline::{GridPositionComponent, emit_arena_resets};
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use thiserror::Error;
+
+impl AbilityType {
+    /// Every ability, grouped by class
+    pub const ALL: [Self; 37] = [
+        Self::Hunter(HunterAbility::AutoShot),
+        Self::Hunter(HunterAbility::PoisonShot),
+        Self::Hunter(HunterAbility::Sniper),
+        Self::Hunter(HunterAbility::Trap),
+        Self::Cardinal(CardinalAbility::HolyNova),
+        Self::Cardinal(CardinalAbility::Heal),
+        Self::Cardinal(CardinalAbility::Barrier),
+        Self::Cardinal(CardinalAbility::Beam),
+        Self::Cardinal(CardinalAbility::Resurrect),
+        Self::Alchemist(AlchemistAbility::AcidFlask),
+        Self::Alchemist(AlchemistAbility::Ironskin),
+        Self::Alchemist(AlchemistAbility::Siphon),
+        Self::Alchemist(AlchemistAbility::Transmute),
+        Self::Bard(BardAbility::Cleanse),
+        Self::Bard(BardAbility::Dance),
+        Self::Bard(BardAbility::Helix),
+        Self::Bard(BardAbility::Mimic),
+        Self::Forager(ForagerAbility::Border),
+        Self::Forager(ForagerAbility::Boulder),
+        Self::Forager(ForagerAbility::Dig),
+        Self::Forager(ForagerAbility::Mushroom),
+        Self::Merchant(MerchantAbility::CoinToss),
+        Self::Merchant(MerchantAbility::Dice),
+        Self::Merchant(MerchantAbility::Fortune),
+        Self::Merchant(MerchantAbility::Vault),
+        Self::Thief(ThiefAbility::Backstab),
+        Self::Thief(ThiefAbility::Pickpocket),
+        Self::Thief(ThiefAbility::ShadowStep),
+        Self::Thief(ThiefAbility::SmokeScreen),
+        Self::Warrior(WarriorAbility::Bash),
+        Self::Warrior(WarriorAbility::Block),
+        Self::Warrior(WarriorAbility::Bulwark),
+        Self::Warrior(WarriorAbility::Taunt),
+        Self::GuildMaster(GuildMasterAbility::Decree),
+        Self::GuildMaster(GuildMasterAbility::Rally),
+        Self::GuildMaster(GuildMasterAbility::Levy),
+        Self::GuildMaster(GuildMasterAbility::Muster),
+    ];
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// What a cast does when it resolves - utility abilities have no direct effect yet
+    #[must_use]
+    pub const fn effect(self) -> AbilityEffect {
+        use AbilityEffect::{Damage, Heal, Utility};
+        match self {
+            Self::Hunter(ability) => match ability {
+                HunterAbility::AutoShot => Damage(40.0),
+                HunterAbility::PoisonShot => Damage(60.0),
+                HunterAbility::Sniper => Damage(250.0),
+                HunterAbility::Trap => Damage(120.0),
+            },
+            Self::Cardinal(ability) => match ability {
+                CardinalAbility::HolyNova => Damage(150.0),
+                CardinalAbility::Heal => Heal(300.0),
+                CardinalAbility::Beam => Damage(200.0),
+                CardinalAbility::Barrier | CardinalAbility::Resurrect => Utility,
+            },
+            Self::Alchemist(ability) => match ability {
+                AlchemistAbility::AcidFlask => Damage(120.0),
+                AlchemistAbility::Siphon => Damage(80.0),
+                AlchemistAbility::Ironskin | AlchemistAbility::Transmute => Utility,
+            },
+            Self::Bard(ability) => match ability {
+                BardAbility::Dance => Heal(100.0),
+                BardAbility::Helix => Damage(90.0),
+                BardAbility::Cleanse | BardAbility::Mimic => Utility,
+            },
+            Self::Forager(ability) => match ability {
+                ForagerAbility::Boulder => Damage(180.0),
+                ForagerAbility::Border | ForagerAbility::Dig | ForagerAbility::Mushroom => Utility,
+            },
+            Self::Merchant(ability) => match ability {
+                MerchantAbility::CoinToss => Damage(60.0),
+                MerchantAbility::Dice => Damage(120.0),
+                MerchantAbility::Fortune | MerchantAbility::Vault => Utility,
+            },
+            Self::Thief(ability) => match ability {
+                ThiefAbility::Backstab => Damage(220.0),
+                ThiefAbility::Pickpocket => Damage(40.0),
+                ThiefAbility::ShadowStep | ThiefAbility::SmokeScreen => Utility,
+            },
+            Self::Warrior(ability) => match ability {
+                WarriorAbility::Bash => Damage(100.0),
+                WarriorAbility::Block | WarriorAbility::Bulwark | WarriorAbility::Taunt => Utility,
+            },
+            Self::GuildMaster(ability) => match ability {
+                GuildMasterAbility::Decree => Damage(200.0),
+                GuildMasterAbility::Rally => Heal(250.0),
+                GuildMasterAbility::Levy => Damage(70.0),
+                GuildMasterAbility::Muster => Utility,
+            },
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Ground a cast leaves on the open tiles near its caster, if any
+    #[must_use]
+    pub const fn terrain(self) -> Option<TileTerrain> {
+        match self {
+            Self::Hunter(HunterAbility::Trap) => Some(TileTerrain::SlowZone),
+            Self::Forager(ForagerAbility::Border) => Some(TileTerrain::Border),
+            _ => None,
+        }
+    }
+}
+
+/// Written as `Class:Ability`, e.g. `Warrior:Bash`
+impl Display for AbilityType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Hunter(ability) => write!(f, "Hunter:{ability:?}"),
+            Self::Cardinal(ability) => write!(f, "Cardinal:{ability:?}"),
+            Self::Alchemist(ability) => write!(f, "Alchemist:{ability:?}"),
+            Self::Bard(ability) => write!(f, "Bard:{ability:?}"),
+            Self::Forager(ability) => write!(f, "Forager:{ability:?}"),
+            Self::Merchant(ability) => write!(f, "Merchant:{ability:?}"),
+            Self::Thief(ability) => write!(f, "Thief:{ability:?}"),
+            Self::Warrior(ability) => write!(f, "Warrior:{ability:?}"),
+            Self::GuildMaster(ability) => write!(f, "GuildMaster:{ability:?}"),
+        }
+    }
+}
+
+/// Error for ability names that match no `Class:Ability` pair
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Unknown ability: {0}")]
+pub struct UnknownAbility(pub String);
+
+impl FromStr for AbilityType {
+    type Err = UnknownAbility;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|ability| ability.to_string() == s)
+            .ok_or_else(|| UnknownAbility(s.to_string()))
+    }
+}
+
+/// Direct result of an ability resolving (value type)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AbilityEffect {
+    /// Damages the boss in the caster's arena
+    Damage(f32),
+    /// Heals the most wounded hero in the caster's arena
+    Heal(f32),
+    Utility,
+}
+
+/// A hero used an ability - written by `cast_slotted_abilities` for live input and
+/// by `replay_ghost_timelines` for ghosts
+#[derive(Event, Debug, Clone)]
+pub struct AbilityCast {
+    pub caster: Entity,
+    pub ability: AbilityType,
+}
+
+/// Bosses abilities can target - decoys are never struck or robbed
+type TargetBossQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static Health, &'static ChildOf), (With<Boss>, Without<Decoy>)>;
+
+/// Turns ability casts into damage and healing requests within the caster's arena
+pub fn resolve_ability_casts(
+    mut casts: EventReader<AbilityCast>,
+    caster_q: Query<&ChildOf, With<Character>>,
+    boss_q: TargetBossQuery,
+    hero_q: Query<(Entity, &Health, &ChildOf), With<Character>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    for cast in casts.read() {
+        let Ok(caster_parent) = caster_q.get(cast.caster) else {
+            continue;
+        };
+        let arena = caster_parent.parent();
+        match cast.ability.effect() {
+            AbilityEffect::Damage(amount) => {
+                let target = boss_q
+                    .iter()
+                    .find(|(_, health, parent)| parent.parent() == arena && !health.is_dead());
+                if let Some((target, _, _)) = target {
+                    damage_events.write(DamageEvent {
+                        source: cast.caster,
+                        target,
+                        amount,
+                        cause: DamageCause::Ability(cast.ability),
+                    });
+                }
+            }
+            AbilityEffect::Heal(amount) => {
+                let target = hero_q
+                    .iter()
+                    .filter(|(_, health, parent)| parent.parent() == arena && !health.is_dead())
+                    .min_by(|(a, a_health, _), (b, b_health, _)| {
+                        a_health
+                            .fraction()
+                            .total_cmp(&b_health.fraction())
+                            .then(a.cmp(b))
+                    });
+                if let Some((target, _, _)) = target {
+                    heal_events.write(HealEvent {
+                        source: cast.caster,
+                        target,
+                        amount,
+                    });
+                }
+            }
+            AbilityEffect::Utility => {}
+        }
+    }
+}
+
+/// Turns casts with lasting effects into status effect requests - Cleanse clears
+/// and Helix hastes every living hero in the caster's arena, Mushroom regenerates
+/// them, Poison Shot poisons the arena's boss, Pickpocket robs it and Ironskin
+/// Draft hardens the caster
+pub fn resolve_status_casts(
+    mut casts: EventReader<AbilityCast>,
+    caster_q: Query<&ChildOf, With<Character>>,
+    boss_q: TargetBossQuery,
+    hero_q: Query<(Entity, &Health, &ChildOf), With<Character>>,
+    mut cleanse_events: EventWriter<CleanseStatusEffects>,
+    mut steal_events: EventWriter<StealStatusEffect>,
+    mut apply_events: EventWriter<ApplyStatusEffect>,
+) {
+    for cast in casts.read() {
+        let Ok(caster_parent) = caster_q.get(cast.caster) else {
+            continue;
+        };
+        let in_arena = |(_, health, parent): &(Entity, &Health, &ChildOf)| {
+            parent.parent() == caster_parent.parent() && !health.is_dead()
+        };
+        match cast.ability {
+            AbilityType::Bard(BardAbility::Cleanse) => {
+                for (target, ..) in hero_q.iter().filter(in_arena) {
+                    cleanse_events.write(CleanseStatusEffects { target });
+                }
+            }
+            AbilityType::Bard(BardAbility::Helix) => {
+                for (target, ..) in hero_q.iter().filter(in_arena) {
+                    apply_events.write(ApplyStatusEffect {
+                        target,
+                        source: cast.caster,
+                        kind: StatusEffectKind::Haste,
+                    });
+                }
+            }
+            AbilityType::Forager(ForagerAbility::Mushroom) => {
+                for (target, ..) in hero_q.iter().filter(in_arena) {
+                    apply_events.write(ApplyStatusEffect {
+                        target,
+                        source: cast.caster,
+                        kind: StatusEffectKind::Regeneration,
+                    });
+                }
+            }
+            AbilityType::Hunter(HunterAbility::PoisonShot) => {
+                if let Some((target, ..)) = boss_q.iter().find(in_arena) {
+                    apply_events.write(ApplyStatusEffect {
+                        target,
+                        source: cast.caster,
+                        kind: StatusEffectKind::Poison,
+                    });
+                }
+            }
+            AbilityType::Thief(ThiefAbility::Pickpocket) => {
+                if let Some((target, ..)) = boss_q.iter().find(in_arena) {
+                    steal_events.write(StealStatusEffect {
+                        thief: cast.caster,
+                        target,
+                    });
+                }
+            }
+            AbilityType::Alchemist(AlchemistAbility::Ironskin) => {
+                apply_events.write(ApplyStatusEffect {
+                    target: cast.caster,
+                    source: cast.caster,
+                    kind: StatusEffectKind::Ironskin,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Tiles a cast lays its terrain on - Trap covers the caster's surroundings, Border
+/// raises a three-tile barrier two tiles out, across the way towards the boss
+fn cast_terrain_tiles(ability: AbilityType, caster: IVec2, boss: Option<IVec2>) -> Vec<IVec2> {
+    match ability {
+        AbilityType::Forager(ForagerAbility::Border) => {
+            let toward = boss.map_or(IVec2::ZERO, |boss| boss - caster);
+            let (ahead, across) = if toward == IVec2::ZERO {
+                (IVec2::Y, IVec2::X)
+            } else if toward.x.abs() >= toward.y.abs() {
+                (IVec2::new(toward.x.signum(), 0), IVec2::Y)
+            } else {
+                (IVec2::new(0, toward.y.signum()), IVec2::X)
+            };
+            let center = caster + ahead * 2;
+            (-1..=1).map(|offset| center + across * offset).collect()
+        }
+        _ => footprint(caster, 1).collect(),
+    }
+}
+
+/// Bosses standing in an arena, by where they stand
+type BossPositionQuery<'w, 's> =
+    Query<'w, 's, (&'static GridPositionComponent, &'static ChildOf), (With<Boss>, Without<Decoy>)>;
+
+/// Lays the ground left by casts such as Trap and Border on open tiles - barriers skip
+/// tiles a unit stands on
+pub fn lay_cast_terrain(
+    mut casts: EventReader<AbilityCast>,
+    caster_q: Query<(&GridPositionComponent, &ChildOf), With<Character>>,
+    boss_q: BossPositionQuery,
+    mut arena_q: Query<&mut ArenaOccupancy>,
+) {
+    for cast in casts.read() {
+        let Some(terrain) = cast.ability.terrain() else {
+            continue;
+        };
+        let Ok((position, child_of)) = caster_q.get(cast.caster) else {
+            continue;
+        };
+        let Ok(mut occupancy) = arena_q.get_mut(child_of.parent()) else {
+            continue;
+        };
+        let boss = boss_q
+            .iter()
+            .find(|(_, boss_parent)| boss_parent.parent() == child_of.parent())
+            .map(|(boss_position, _)| boss_position.0);
+        for tile in cast_terrain_tiles(cast.ability, position.0, boss) {
+            let under_unit = terrain.blocks_movement() && occupancy.occupant(tile).is_some();
+            if occupancy.terrain(tile) == TileTerrain::Open && !under_unit {
+                occupancy.set_terrain(tile, terrain);
+            }
+        }
+    }
+}
+
+/// Clears cast terrain from arenas whose loop just wrapped so replays start on open ground
+pub fn clear_cast_terrain_on_reset(
+    mut reset_events: EventReader<ArenaReset>,
+    mut arena_q: Query<(&Arena, &mut ArenaOccupancy)>,
+) {
+    for reset in reset_events.read() {
+        for (arena, mut occupancy) in arena_q.iter_mut() {
+            if arena.0 == reset.arena {
+                occupancy.replace_terrain(TileTerrain::SlowZone, TileTerrain::Open);
+                occupancy.replace_terrain(TileTerrain::Border, TileTerrain::Open);
+            }
+        }
+    }
+}
+
+/// Plugin resolving ability casts into combat events
+pub struct AbilityEffectPlugin;
+
+impl Plugin for AbilityEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AbilityCast>()
+            .add_event::<CleanseStatusEffects>()
+            .add_event::<StealStatusEffect>()
+            .add_event::<ApplyStatusEffect>()
+            .add_event::<ArenaReset>()
+            .add_systems(
+                Update,
+                (
+                    resolve_ability_casts.before(apply_damage),
+                    resolve_status_casts
+                        .after(resolve_ability_casts)
+                        .before(apply_status_effects)
+                        .before(cleanse_status_effects)
+                        .before(steal_status_effects),
+                    lay_cast_terrain,
+                    clear_cast_terrain_on_reset.after(emit_arena_resets),
+                ),
+            );
+    }
+}
@@ -4,15 +4,11 @@ use bevy::pbr::MeshMaterial3d;
 use bevy::prelude::*;
 
 // Local crate modules
-use crate::ability::{AbilityType, CardinalAbility, Duration, ElapsedTime, EndRadius, StartRadius};
-use crate::arena::{Arena, ArenaEntities, CurrentArena};
+use crate::ability::{
+    AbilityCast, AbilityType, CardinalAbility, Duration, ElapsedTime, EndRadius, StartRadius,
+};
 use crate::audio::Audio;
-use crate::character::Character;
 use crate::materials::Materials;
-use crate::selectors::Active;
-use crate::timeline::{
-    DraftTimeline, EventType, GlobalTimelinePause, TimelineClock, TimelineEvent,
-};
 
 #[derive(Component, Debug)]
 pub struct HolyNova;
@@ -32,68 +28,24 @@ impl HolyNovaVfx {
     }
 }
 
-/// Spawns a holy nova VFX sphere at the active character when the user presses '1'.
+/// Spawns a holy nova VFX sphere on every hero or ghost casting Holy Nova
 pub fn holy_nova_ability(
     mut commands: Commands,
     mats: Res<Materials>,
     audio: Res<Audio>,
     mut meshes: ResMut<Assets<Mesh>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    character_q: Query<(Entity), (With<Character>, With<Active>, With<HolyNova>)>,
-    mut draft_timeline: ResMut<DraftTimeline>,
-    arena_q: Query<(&Arena, &TimelineClock)>,
-    current_arena: Res<CurrentArena>,
-    arena_entities_res: Res<ArenaEntities>,
-    global_pause: Res<GlobalTimelinePause>,
+    mut casts: EventReader<AbilityCast>,
 ) {
-    // Trigger on '1' key (both main row and numpad)
-    let pressed = keyboard_input.just_pressed(KeyCode::Digit1)
-        || keyboard_input.just_pressed(KeyCode::Numpad1);
-
-    if !pressed {
-        return;
-    }
-
-    if character_q.is_empty() {
-        return;
-    }
-
-    if global_pause.is_paused {
-        return;
-    }
-
-    // Handle recording for characters that are recording
-    for (_character_entity) in character_q.iter() {
-        let current_arena_entity = arena_entities_res.get(current_arena.0);
-        if let Ok((_, clock)) = arena_q.get(current_arena_entity) {
-            let timestamp = clock.current();
-
-            let event = TimelineEvent {
-                timestamp,
-                event_type: EventType::Ability(
-                    AbilityType::Cardinal(CardinalAbility::HolyNova),
-                    None,
-                ),
-            };
-
-            if let Err(e) = draft_timeline.add_event(event) {
-                bevy::log::warn!("Failed to record ability event: {:?}", e);
-            } else {
-                bevy::log::trace!("Recorded HolyNova ability at {}", timestamp);
-            }
-        }
-    }
-
-    // Play the holy nova sound effect with automatic cleanup
-    commands.spawn((
-        AudioPlayer::new(audio.holy_nova.clone()),
-        PlaybackSettings::DESPAWN,
-    ));
+    let holy_nova = AbilityType::Cardinal(CardinalAbility::HolyNova);
+    for cast in casts.read().filter(|cast| cast.ability == holy_nova) {
+        // Play the holy nova sound effect with automatic cleanup
+        commands.spawn((
+            AudioPlayer::new(audio.holy_nova.clone()),
+            PlaybackSettings::DESPAWN,
+        ));
 
-    // Spawn a VFX sphere as a child of each active character
-    for (character_entity) in character_q.iter() {
         let vfx_mesh = meshes.add(Sphere::new(0.0625)); // unit sphere, scale controls radius
-        commands.entity(character_entity).with_child((
+        commands.entity(cast.caster).with_child((
             HolyNovaVfx::new(),
             ElapsedTime(0.0),
             Duration(0.225), // seconds
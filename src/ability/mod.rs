@@ -1,5 +1,8 @@
 // Existing abilities
 mod auto_shot;
+mod cast;
+mod cooldown;
+mod effect;
 mod holy_nova;
 
 // Alchemist abilities
@@ -51,6 +54,9 @@ mod taunt;
 
 // Existing exports
 pub use auto_shot::*;
+pub use cast::*;
+pub use cooldown::*;
+pub use effect::*;
 pub use holy_nova::*;
 
 // Alchemist exports
@@ -205,6 +211,15 @@ pub enum WarriorAbility {
     Taunt,
 }
 
+/// Guild Master abilities
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GuildMasterAbility {
+    Decree,
+    Rally,
+    Levy,
+    Muster,
+}
+
 /// Ability type enum with nested class-specific abilities
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum AbilityType {
@@ -216,6 +231,7 @@ pub enum AbilityType {
     Merchant(MerchantAbility),
     Thief(ThiefAbility),
     Warrior(WarriorAbility),
+    GuildMaster(GuildMasterAbility),
 }
 
 // Note: Sub-enums are already publicly exported via their definitions above
@@ -1,11 +1,16 @@
-use crate::arena::{CameraUpdate, CharacterMoved, LastActiveHero};
-use crate::arena_camera::{ZOOM, ZoomOut, position_camera_for_arena};
+use crate::arena::{ArenaOccupancy, CameraUpdate, CharacterMoved, LastActiveHero};
+use crate::arena_camera::{CameraRig, ZOOM, ZoomOut, arena_shot};
 use crate::character::Character;
+use crate::class_type::ClassType;
+use crate::controls::{Action, ActionState};
+use crate::difficulty::Difficulty;
 use crate::materials::Materials;
 use crate::selectors::Active;
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use thiserror::Error;
 
 /// Arena names enum - THE SINGLE SOURCE OF DOMAIN LOGIC
 /// This enum provides all arena identification and conversion functionality
@@ -71,6 +76,40 @@ impl ArenaName {
         };
         Self::from_index_safe(prev_idx)
     }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Class the arena and its boss are themed around (see _docs/BOSS_FIGHTS.md)
+    #[must_use]
+    pub const fn themed_class(self) -> ClassType {
+        match self {
+            Self::Labyrinth => ClassType::Hunter,
+            Self::GuildHouse => ClassType::GuildMaster,
+            Self::Sanctum => ClassType::Cardinal,
+            Self::Mountain => ClassType::Forager,
+            Self::Bastion => ClassType::Warrior,
+            Self::Pawnshop => ClassType::Thief,
+            Self::Crucible => ClassType::Alchemist,
+            Self::Casino => ClassType::Merchant,
+            Self::Gala => ClassType::Bard,
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Name of the arena's boss (see _docs/BOSS_FIGHTS.md)
+    #[must_use]
+    pub const fn boss_name(self) -> &'static str {
+        match self {
+            Self::Labyrinth => "The Hunter",
+            Self::GuildHouse => "The Guild Master",
+            Self::Sanctum => "The Cardinal",
+            Self::Mountain => "The Forager",
+            Self::Bastion => "The Warrior",
+            Self::Pawnshop => "The Thief",
+            Self::Crucible => "The Alchemist",
+            Self::Casino => "The Merchant",
+            Self::Gala => "The Bard",
+        }
+    }
 }
 
 impl Display for ArenaName {
@@ -89,9 +128,27 @@ impl Display for ArenaName {
     }
 }
 
+/// Error for names that match no arena
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Unknown arena: {0}")]
+pub struct UnknownArena(pub String);
+
+/// Accepts the display name ("Guild House") or the variant name ("GuildHouse")
+impl FromStr for ArenaName {
+    type Err = UnknownArena;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL_ARENAS
+            .into_iter()
+            .find(|arena| arena.to_string() == s || format!("{arena:?}") == s)
+            .ok_or_else(|| UnknownArena(s.to_string()))
+    }
+}
+
 /// Arena component that marks arena entities
 /// Simple wrapper around ArenaName - NO DUPLICATE METHODS
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[require(ArenaOccupancy, Difficulty)]
 pub struct Arena(pub ArenaName);
 
 impl Display for Arena {
@@ -135,6 +192,19 @@ impl ArenaEntities {
     }
 }
 
+/// Spawns all nine arenas, each with the bundle `extra` gives it, and registers
+/// them as `ArenaEntities` - shared setup for tests that need the whole grid
+#[cfg(test)]
+pub fn create_test_arenas<B: Bundle>(
+    world: &mut World,
+    mut extra: impl FnMut(ArenaName) -> B,
+) -> ArenaEntities {
+    let arenas =
+        ArenaName::ALL_ARENAS.map(|name| (name, world.spawn((Arena(name), extra(name))).id()));
+    world.insert_resource(ArenaEntities::new(arenas));
+    ArenaEntities::new(arenas)
+}
+
 impl CurrentArena {
     /// Helper method to get the arena entity directly
     /// Eliminates the repetitive lookup pattern used throughout the codebase
@@ -178,14 +248,20 @@ impl CurrentArenaEntity<'_> {
     pub fn is_current(&self, arena_name: ArenaName) -> bool {
         self.current_arena.0 == arena_name
     }
+
+    /// Check if the current arena was switched since the system last ran
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.current_arena.is_changed()
+    }
 }
 
 pub fn decrement_current_arena(
-    keycode: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     mut current_arena: ResMut<CurrentArena>,
     mut arena_refresh_event: EventWriter<CameraUpdate>,
 ) {
-    if keycode.just_pressed(KeyCode::BracketLeft) {
+    if actions.just_pressed(Action::PreviousArena) {
         current_arena.0 = current_arena.0.decrement();
 
         // Send event
@@ -194,11 +270,11 @@ pub fn decrement_current_arena(
 }
 
 pub fn increment_current_arena(
-    keycode: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     mut current_arena: ResMut<CurrentArena>,
     mut arena_refresh_event: EventWriter<CameraUpdate>,
 ) {
-    if keycode.just_pressed(KeyCode::BracketRight) {
+    if actions.just_pressed(Action::NextArena) {
         current_arena.0 = current_arena.0.increment();
 
         // Send event
@@ -210,7 +286,7 @@ pub fn handle_character_moved(
     mut commands: Commands,
     mut character_moved_events: EventReader<CharacterMoved>,
     current_arena: Res<CurrentArena>,
-    camera: Single<(Entity, &mut Transform, Option<&ZoomOut>), With<Camera3d>>,
+    camera: Single<(Entity, &Transform, &mut CameraRig, Option<&ZoomOut>), With<Camera3d>>,
     arena_entities: Res<ArenaEntities>,
 ) {
     // Only process if there are events to handle
@@ -219,14 +295,14 @@ pub fn handle_character_moved(
     }
 
     let current_arena = &*current_arena;
-    let (camera_entity, mut camera_transform, zoom) = camera.into_inner();
+    let (camera_entity, camera_transform, mut rig, zoom) = camera.into_inner();
 
     // Handle character movement between arenas
     for event in character_moved_events.read() {
         // Only update camera if we're not zoomed out and the target arena is the current arena
         if zoom.is_none() && event.to_arena == current_arena.0 {
             // Update camera position to the new arena
-            position_camera_for_arena(&mut camera_transform, event.to_arena.as_u8(), ZOOM.0);
+            rig.move_to(camera_transform, arena_shot(event.to_arena.as_u8(), ZOOM.0));
             commands.entity(camera_entity).remove::<ZoomOut>();
         }
 
@@ -251,7 +327,7 @@ pub fn arena_update(
     mut commands: Commands,
     mut arena_refresh_events: EventReader<CameraUpdate>,
     current: CurrentArenaEntity,
-    camera: Single<(Entity, &mut Transform, Option<&ZoomOut>), With<Camera3d>>,
+    camera: Single<(Entity, Option<&ZoomOut>), With<Camera3d>>,
     arena_q: Query<(&Arena, &Children, Option<&LastActiveHero>), With<Arena>>,
     characters_q: Query<(Entity, Option<&Active>), With<Character>>,
     mats: Res<Materials>,
@@ -261,8 +337,7 @@ pub fn arena_update(
         return;
     }
     arena_refresh_events.clear();
-    let current_arena_name = current.name();
-    let (camera_entity, mut camera_transform, zoom) = camera.into_inner();
+    let (camera_entity, zoom) = camera.into_inner();
     if zoom.is_some() {
         for (entity, active) in characters_q.iter() {
             if active.is_some() {
@@ -273,7 +348,6 @@ pub fn arena_update(
             }
         }
     } else {
-        position_camera_for_arena(&mut camera_transform, current_arena_name.as_u8(), ZOOM.0);
         commands.entity(camera_entity).remove::<ZoomOut>();
 
         // O(1) lookup for current arena entity
@@ -9,3 +9,9 @@ pub struct CharacterMoved {
     pub from_arena: crate::arena::ArenaName,
     pub to_arena: crate::arena::ArenaName,
 }
+
+/// Event fired when an arena's 2-minute loop wraps back to the start
+#[derive(Event, Debug, Clone)]
+pub struct ArenaReset {
+    pub arena: crate::arena::ArenaName,
+}
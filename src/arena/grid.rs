@@ -0,0 +1,79 @@
+use crate::arena::{ArenaName, GRID_HEIGHT, GRID_WIDTH, TILE_SIZE};
+use crate::timeline::GridPositionComponent;
+use bevy::prelude::*;
+
+/// Arenas per row/column of the 3x3 battleground
+const ARENA_COLUMNS: i32 = 3;
+const ARENA_ROWS: i32 = 3;
+
+/// Width and height of the whole battleground in tiles
+pub const GLOBAL_GRID_WIDTH: i32 = GRID_WIDTH as i32 * ARENA_COLUMNS;
+pub const GLOBAL_GRID_HEIGHT: i32 = GRID_HEIGHT as i32 * ARENA_ROWS;
+
+/// Returns true if a local tile lies inside a single arena
+#[must_use]
+pub fn is_local_tile_in_bounds(tile: IVec2) -> bool {
+    tile.x >= 0 && tile.y >= 0 && tile.x < GRID_WIDTH as i32 && tile.y < GRID_HEIGHT as i32
+}
+
+/// Converts an arena-local tile to battleground-global tile coordinates
+///
+/// Global tiles grow right and up like world space: Labyrinth (row 0, top-left)
+/// covers x 0..66, y 62..93 and Gala (row 2, bottom-right) covers x 132..198, y 0..31.
+#[must_use]
+pub fn local_to_global_tile(arena: ArenaName, tile: IVec2) -> IVec2 {
+    let index = arena.as_u8() as i32;
+    let col = index % ARENA_COLUMNS;
+    let row = index / ARENA_COLUMNS;
+    IVec2::new(
+        col * GRID_WIDTH as i32 + tile.x,
+        (ARENA_ROWS - 1 - row) * GRID_HEIGHT as i32 + tile.y,
+    )
+}
+
+/// Converts a battleground-global tile back to its arena and local tile
+/// Returns None when the tile lies outside the battleground
+#[must_use]
+pub fn global_to_local_tile(global: IVec2) -> Option<(ArenaName, IVec2)> {
+    if global.x < 0
+        || global.y < 0
+        || global.x >= GLOBAL_GRID_WIDTH
+        || global.y >= GLOBAL_GRID_HEIGHT
+    {
+        return None;
+    }
+
+    let col = global.x / GRID_WIDTH as i32;
+    let row = ARENA_ROWS - 1 - global.y / GRID_HEIGHT as i32;
+    let arena = ArenaName::from_index_safe((row * ARENA_COLUMNS + col) as u8);
+    let local = IVec2::new(global.x % GRID_WIDTH as i32, global.y % GRID_HEIGHT as i32);
+    Some((arena, local))
+}
+
+/// Arena index (0-8) containing a battleground-global tile
+#[must_use]
+pub fn arena_index_of_global_tile(global: IVec2) -> Option<u8> {
+    global_to_local_tile(global).map(|(arena, _)| arena.as_u8())
+}
+
+/// Moves a local tile by a step, crossing into neighbouring arenas at the edges
+/// Returns None when the step would leave the battleground
+#[must_use]
+pub fn step_tile(arena: ArenaName, tile: IVec2, step: IVec2) -> Option<(ArenaName, IVec2)> {
+    global_to_local_tile(local_to_global_tile(arena, tile) + step)
+}
+
+/// Local-space translation of a tile's centre at the given height
+#[must_use]
+pub fn tile_to_local_translation(tile: IVec2, z: f32) -> Vec3 {
+    Vec3::new(tile.x as f32 * TILE_SIZE, tile.y as f32 * TILE_SIZE, z)
+}
+
+/// Derives Transform from the authoritative grid position, preserving height
+pub fn sync_grid_transforms(
+    mut grid_q: Query<(&GridPositionComponent, &mut Transform), Changed<GridPositionComponent>>,
+) {
+    for (grid_position, mut transform) in grid_q.iter_mut() {
+        transform.translation = tile_to_local_translation(grid_position.0, transform.translation.z);
+    }
+}
@@ -1,16 +1,18 @@
 mod arena;
 mod constants;
 mod events;
+mod grid;
+mod occupancy;
 
 pub use arena::*;
-use bevy::math::Vec3;
 use bevy::prelude::{Component, Entity};
 pub use constants::*;
 pub use events::*;
+pub use grid::*;
+pub use occupancy::*;
 
 #[derive(Component)]
 pub struct LastActiveHero(pub Option<Entity>);
 
-pub fn get_local_tile_space(x: f32, y: f32, z: f32) -> Vec3 {
-    Vec3::new(x * TILE_SIZE, y * TILE_SIZE, z)
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,439 @@
+use crate::arena::{
+    Arena, ArenaEntities, ArenaName, CharacterMoved, CurrentArena, GRID_HEIGHT, GRID_WIDTH,
+    TILE_SIZE, is_local_tile_in_bounds, local_to_global_tile, step_tile, sync_grid_transforms,
+    tile_to_local_translation,
+};
+use crate::character::{Boss, Character, Ghost};
+use crate::selectors::Active;
+use crate::timeline::GridPositionComponent;
+use bevy::prelude::*;
+use std::cmp::Reverse;
+
+/// Tiles around a boss's centre that its body occupies (1 = 3x3 footprint)
+pub const BOSS_FOOTPRINT_RADIUS: i32 = 1;
+
+/// Tiles covered by a unit standing on `center` with a footprint of `radius` tiles
+pub fn footprint(center: IVec2, radius: i32) -> impl Iterator<Item = IVec2> {
+    (-radius..=radius).flat_map(move |y| (-radius..=radius).map(move |x| center + IVec2::new(x, y)))
+}
+
+/// Static terrain held by a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileTerrain {
+    #[default]
+    Open,
+    /// Permanent arena wall
+    Wall,
+    /// Forager's raised stone barrier
+    Border,
+    /// Labyrinth spider webs
+    Web,
+    /// Crucible acid pools
+    Acid,
+    /// Crucible acid turned into a buffing reactive field
+    Catalyst,
+    /// Slowing ground left by a Hunter's Trap
+    SlowZone,
+    /// Gala hazard left behind the boss's dashes
+    Trail,
+    /// Gala dynamic platform that has dropped away
+    Chasm,
+    /// Sanctum corrupted ground
+    Corrupted,
+    /// Pawnshop shadowed ground that cloaks the boss
+    Shadow,
+    /// Mountain seedling that ripens into fertile ground
+    Seedling,
+    /// Mountain fertile ground the boss regenerates on
+    Fertile,
+    /// Mountain fertile ground overgrown into a hazard
+    Bramble,
+}
+
+impl TileTerrain {
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Cost of stepping onto the tile, or None when it cannot be entered
+    #[must_use]
+    pub const fn traversal_cost(self) -> Option<u32> {
+        match self {
+            Self::Open | Self::Catalyst | Self::Shadow | Self::Seedling | Self::Fertile => Some(1),
+            Self::SlowZone => Some(3),
+            Self::Web => Some(5),
+            Self::Acid | Self::Trail | Self::Corrupted | Self::Bramble => Some(8),
+            Self::Wall | Self::Border | Self::Chasm => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn blocks_movement(self) -> bool {
+        self.traversal_cost().is_none()
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Tint of the overlay drawn on the tile, None for plain ground
+    #[must_use]
+    pub const fn overlay_color(self) -> Option<Color> {
+        match self {
+            Self::Open => None,
+            Self::Wall => Some(Color::srgb(0.2, 0.2, 0.22)),
+            Self::Border => Some(Color::srgb(0.45, 0.35, 0.25)),
+            Self::Web => Some(Color::srgb(0.85, 0.85, 0.8)),
+            Self::Acid => Some(Color::srgb(0.35, 0.85, 0.2)),
+            Self::Catalyst => Some(Color::srgb(0.95, 0.8, 0.2)),
+            Self::SlowZone => Some(Color::srgb(0.3, 0.45, 0.8)),
+            Self::Trail => Some(Color::srgb(0.85, 0.25, 0.6)),
+            Self::Chasm => Some(Color::srgb(0.05, 0.04, 0.08)),
+            Self::Corrupted => Some(Color::srgb(0.4, 0.15, 0.5)),
+            Self::Shadow => Some(Color::srgb(0.12, 0.1, 0.18)),
+            Self::Seedling => Some(Color::srgb(0.6, 0.85, 0.45)),
+            Self::Fertile => Some(Color::srgb(0.2, 0.6, 0.15)),
+            Self::Bramble => Some(Color::srgb(0.45, 0.3, 0.1)),
+        }
+    }
+}
+
+/// Per-arena occupancy map: a persistent terrain layer plus a unit layer
+/// rebuilt from grid positions every tick
+#[derive(Component, Debug, Clone)]
+pub struct ArenaOccupancy {
+    terrain: Vec<TileTerrain>,
+    units: Vec<Option<Entity>>,
+    /// Bumped on every terrain change so overlays redraw only when needed
+    terrain_revision: u32,
+}
+
+impl Default for ArenaOccupancy {
+    fn default() -> Self {
+        let tiles = (GRID_WIDTH * GRID_HEIGHT) as usize;
+        Self {
+            terrain: vec![TileTerrain::Open; tiles],
+            units: vec![None; tiles],
+            terrain_revision: 0,
+        }
+    }
+}
+
+impl ArenaOccupancy {
+    /// Open ground walled off wherever the arena meets the edge of the battleground
+    #[must_use]
+    pub fn walled(arena: ArenaName) -> Self {
+        let mut occupancy = Self::default();
+        for i in 0..occupancy.terrain.len() {
+            let tile = Self::tile(i);
+            let on_edge = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+                .into_iter()
+                .any(|step| step_tile(arena, tile, step).is_none());
+            if on_edge {
+                occupancy.terrain[i] = TileTerrain::Wall;
+            }
+        }
+        occupancy
+    }
+
+    fn index(tile: IVec2) -> Option<usize> {
+        is_local_tile_in_bounds(tile).then(|| (tile.y * GRID_WIDTH as i32 + tile.x) as usize)
+    }
+
+    fn tile(index: usize) -> IVec2 {
+        IVec2::new(
+            index as i32 % GRID_WIDTH as i32,
+            index as i32 / GRID_WIDTH as i32,
+        )
+    }
+
+    /// Terrain at a local tile - out of bounds reads as Wall
+    #[must_use]
+    pub fn terrain(&self, tile: IVec2) -> TileTerrain {
+        Self::index(tile).map_or(TileTerrain::Wall, |i| self.terrain[i])
+    }
+
+    pub fn set_terrain(&mut self, tile: IVec2, terrain: TileTerrain) {
+        if let Some(i) = Self::index(tile)
+            && self.terrain[i] != terrain
+        {
+            self.terrain[i] = terrain;
+            self.terrain_revision = self.terrain_revision.wrapping_add(1);
+        }
+    }
+
+    /// Every tile holding the given terrain, in row-major order
+    pub fn tiles_with(&self, terrain: TileTerrain) -> impl Iterator<Item = IVec2> + '_ {
+        self.terrain
+            .iter()
+            .enumerate()
+            .filter(move |(_, held)| **held == terrain)
+            .map(|(i, _)| Self::tile(i))
+    }
+
+    /// Turns every tile of one terrain into another
+    pub fn replace_terrain(&mut self, from: TileTerrain, to: TileTerrain) {
+        let tiles: Vec<IVec2> = self.tiles_with(from).collect();
+        for tile in tiles {
+            self.set_terrain(tile, to);
+        }
+    }
+
+    /// Unit currently holding a local tile
+    #[must_use]
+    pub fn occupant(&self, tile: IVec2) -> Option<Entity> {
+        Self::index(tile).and_then(|i| self.units[i])
+    }
+
+    /// Whether `mover` may not enter the tile (terrain or another unit holds it)
+    #[must_use]
+    pub fn is_blocked(&self, tile: IVec2, mover: Entity) -> bool {
+        self.terrain(tile).blocks_movement()
+            || self.occupant(tile).is_some_and(|unit| unit != mover)
+    }
+
+    /// Whether any tile of `mover`'s footprint would be blocked with it centred on `tile`
+    #[must_use]
+    pub fn is_footprint_blocked(&self, tile: IVec2, radius: i32, mover: Entity) -> bool {
+        footprint(tile, radius).any(|covered| self.is_blocked(covered, mover))
+    }
+
+    fn clear_units(&mut self) {
+        self.units.fill(None);
+    }
+
+    pub(crate) fn set_occupant(&mut self, tile: IVec2, unit: Option<Entity>) {
+        if let Some(i) = Self::index(tile) {
+            self.units[i] = unit;
+        }
+    }
+
+    /// Releases a tile only if `unit` is the one holding it
+    fn release(&mut self, tile: IVec2, unit: Entity) {
+        if self.occupant(tile) == Some(unit) {
+            self.set_occupant(tile, None);
+        }
+    }
+}
+
+/// Request to move a unit to a local tile, possibly in a neighbouring arena
+/// Resolved together each tick so simultaneous claims are settled deterministically
+#[derive(Event, Debug, Clone)]
+pub struct MoveRequest {
+    pub entity: Entity,
+    pub to_arena: ArenaName,
+    pub to_tile: IVec2,
+}
+
+/// Footprint radius of a unit - bosses cover 3x3, everything else a single tile
+const fn footprint_radius(is_boss: bool) -> i32 {
+    if is_boss { BOSS_FOOTPRINT_RADIUS } else { 0 }
+}
+
+/// Rebuilds the unit layer of every arena's occupancy map from grid positions
+pub fn rebuild_occupancy(
+    mut arena_q: Query<&mut ArenaOccupancy>,
+    unit_q: Query<(Entity, &GridPositionComponent, &ChildOf, Has<Boss>)>,
+) {
+    for mut occupancy in arena_q.iter_mut() {
+        occupancy.clear_units();
+    }
+
+    for (entity, position, child_of, is_boss) in unit_q.iter() {
+        let Ok(mut occupancy) = arena_q.get_mut(child_of.parent()) else {
+            continue;
+        };
+        for tile in footprint(position.0, footprint_radius(is_boss)) {
+            occupancy.set_occupant(tile, Some(entity));
+        }
+    }
+}
+
+/// Units that can move, with what the resolver needs to order and block them
+type MovingUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut GridPositionComponent,
+        &'static ChildOf,
+        Has<Ghost>,
+        Has<Character>,
+        Has<Active>,
+        Has<Boss>,
+    ),
+>;
+
+/// Applies this tick's move requests against the occupancy maps
+///
+/// Ghost moves resolve before live ones so recorded timelines replay identically;
+/// ties are broken by the mover's battleground tile in row-major order (top-left first).
+pub fn resolve_move_requests(
+    mut commands: Commands,
+    mut move_events: EventReader<MoveRequest>,
+    mut current_arena: ResMut<CurrentArena>,
+    arena_entities: Res<ArenaEntities>,
+    mut arena_q: Query<(&Arena, &mut ArenaOccupancy)>,
+    mut unit_q: MovingUnitQuery,
+    mut character_moved_event: EventWriter<CharacterMoved>,
+) {
+    if move_events.is_empty() {
+        return;
+    }
+
+    let mut requests: Vec<(MoveRequest, ArenaName, IVec2, bool)> = move_events
+        .read()
+        .filter_map(|request| {
+            let (position, child_of, is_ghost, ..) = unit_q.get(request.entity).ok()?;
+            let (arena, _) = arena_q.get(child_of.parent()).ok()?;
+            Some((request.clone(), arena.0, position.0, is_ghost))
+        })
+        .collect();
+
+    requests.sort_by_key(|(request, from_arena, from_tile, is_ghost)| {
+        let global = local_to_global_tile(*from_arena, *from_tile);
+        (!is_ghost, Reverse(global.y), global.x, request.entity)
+    });
+
+    for (request, from_arena, from_tile, _) in requests {
+        let Ok((mut position, _, _, is_character, is_active, is_boss)) =
+            unit_q.get_mut(request.entity)
+        else {
+            continue;
+        };
+        // Only the first resolved request per unit per tick applies
+        if position.0 != from_tile {
+            continue;
+        }
+        let radius = footprint_radius(is_boss);
+        let Ok((_, to_occupancy)) = arena_q.get(arena_entities.get(request.to_arena)) else {
+            continue;
+        };
+        if to_occupancy.is_footprint_blocked(request.to_tile, radius, request.entity) {
+            debug!(
+                "Move of {:?} into {} {} blocked",
+                request.entity, request.to_arena, request.to_tile
+            );
+            continue;
+        }
+
+        // Release before claiming - a boss's old and new footprints overlap
+        if let Ok((_, mut from_occupancy)) = arena_q.get_mut(arena_entities.get(from_arena)) {
+            for tile in footprint(from_tile, radius) {
+                from_occupancy.release(tile, request.entity);
+            }
+        }
+        if let Ok((_, mut to_occupancy)) = arena_q.get_mut(arena_entities.get(request.to_arena)) {
+            for tile in footprint(request.to_tile, radius) {
+                to_occupancy.set_occupant(tile, Some(request.entity));
+            }
+        }
+
+        position.0 = request.to_tile;
+
+        if request.to_arena != from_arena {
+            commands
+                .entity(request.entity)
+                .insert(ChildOf(arena_entities.get(request.to_arena)));
+
+            if is_character {
+                if is_active {
+                    current_arena.0 = request.to_arena;
+                }
+                println!("Moved to {}", request.to_arena);
+                character_moved_event.write(CharacterMoved {
+                    character_entity: request.entity,
+                    from_arena,
+                    to_arena: request.to_arena,
+                });
+            }
+        }
+    }
+}
+
+/// Flat marker drawn over a tile whose terrain is not plain ground
+#[derive(Component, Debug)]
+pub struct TerrainOverlay;
+
+/// Shared overlay mesh, one material per terrain kind, and the terrain revision
+/// last drawn for each arena
+#[derive(Default)]
+pub struct OverlayCache {
+    mesh: Option<Handle<Mesh>>,
+    palette: Vec<(TileTerrain, Handle<StandardMaterial>)>,
+    drawn: Vec<(Entity, u32)>,
+}
+
+/// Redraws an arena's terrain overlays whenever its terrain layer changes
+pub fn sync_terrain_overlays(
+    mut commands: Commands,
+    arena_q: Query<(Entity, &ArenaOccupancy, Option<&Children>)>,
+    overlay_q: Query<(), With<TerrainOverlay>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut cache: Local<OverlayCache>,
+) {
+    let OverlayCache {
+        mesh,
+        palette,
+        drawn,
+    } = &mut *cache;
+    let mesh = mesh
+        .get_or_insert_with(|| meshes.add(Rectangle::new(TILE_SIZE, TILE_SIZE)))
+        .clone();
+    for (arena, occupancy, children) in arena_q.iter() {
+        let revision = occupancy.terrain_revision;
+        match drawn
+            .iter_mut()
+            .find(|(drawn_arena, _)| *drawn_arena == arena)
+        {
+            Some((_, drawn_revision)) if *drawn_revision == revision => continue,
+            Some((_, drawn_revision)) => *drawn_revision = revision,
+            None => drawn.push((arena, revision)),
+        }
+
+        for child in children.into_iter().flatten() {
+            if overlay_q.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+        for (i, terrain) in occupancy.terrain.iter().enumerate() {
+            let Some(color) = terrain.overlay_color() else {
+                continue;
+            };
+            let material = match palette.iter().find(|(kind, _)| kind == terrain) {
+                Some((_, material)) => material.clone(),
+                None => {
+                    let material = materials.add(StandardMaterial {
+                        base_color: color,
+                        unlit: true,
+                        ..default()
+                    });
+                    palette.push((*terrain, material.clone()));
+                    material
+                }
+            };
+            let tile = ArenaOccupancy::tile(i);
+            commands.spawn((
+                TerrainOverlay,
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material),
+                Transform::from_translation(tile_to_local_translation(
+                    tile,
+                    TILE_SIZE / 2.0 + 0.002,
+                )),
+                ChildOf(arena),
+            ));
+        }
+    }
+}
+
+/// Plugin for tile occupancy and collision-checked movement
+pub struct OccupancyPlugin;
+
+impl Plugin for OccupancyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<MoveRequest>()
+            .add_event::<CharacterMoved>()
+            .add_systems(PreUpdate, rebuild_occupancy)
+            .add_systems(
+                PostUpdate,
+                (resolve_move_requests, sync_grid_transforms)
+                    .chain()
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
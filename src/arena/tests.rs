@@ -0,0 +1,292 @@
+use super::*;
+use crate::character::{Boss, Character, Ghost};
+use crate::selectors::Active;
+use crate::timeline::GridPositionComponent;
+use bevy::app::App;
+use bevy::prelude::*;
+
+#[test]
+fn test_local_and_global_tiles_round_trip_for_every_arena() {
+    let corners = [
+        IVec2::ZERO,
+        IVec2::new(GRID_WIDTH as i32 - 1, 0),
+        IVec2::new(0, GRID_HEIGHT as i32 - 1),
+        IVec2::new(GRID_WIDTH as i32 - 1, GRID_HEIGHT as i32 - 1),
+    ];
+
+    for arena in ArenaName::ALL_ARENAS {
+        for tile in corners {
+            let global = local_to_global_tile(arena, tile);
+            assert_eq!(global_to_local_tile(global), Some((arena, tile)));
+            assert_eq!(arena_index_of_global_tile(global), Some(arena.as_u8()));
+        }
+    }
+
+    // Labyrinth is the top-left arena, Gala the bottom-right
+    assert_eq!(
+        local_to_global_tile(ArenaName::Labyrinth, IVec2::ZERO),
+        IVec2::new(0, 2 * GRID_HEIGHT as i32)
+    );
+    assert_eq!(
+        local_to_global_tile(ArenaName::Gala, IVec2::ZERO),
+        IVec2::new(2 * GRID_WIDTH as i32, 0)
+    );
+    assert_eq!(global_to_local_tile(IVec2::new(-1, 0)), None);
+    assert_eq!(global_to_local_tile(IVec2::new(GLOBAL_GRID_WIDTH, 0)), None);
+}
+
+#[test]
+fn test_step_tile_crosses_arena_edges_and_stops_at_battleground_edges() {
+    let max_x = GRID_WIDTH as i32 - 1;
+    let max_y = GRID_HEIGHT as i32 - 1;
+
+    // Inside an arena
+    assert_eq!(
+        step_tile(ArenaName::Bastion, IVec2::new(5, 5), IVec2::X),
+        Some((ArenaName::Bastion, IVec2::new(6, 5)))
+    );
+
+    // Right edge of GuildHouse lands on the left edge of Sanctum
+    assert_eq!(
+        step_tile(ArenaName::GuildHouse, IVec2::new(max_x, 7), IVec2::X),
+        Some((ArenaName::Sanctum, IVec2::new(0, 7)))
+    );
+
+    // Bottom edge of GuildHouse lands on the top edge of Bastion
+    assert_eq!(
+        step_tile(ArenaName::GuildHouse, IVec2::new(9, 0), IVec2::NEG_Y),
+        Some((ArenaName::Bastion, IVec2::new(9, max_y)))
+    );
+
+    // Battleground boundaries block movement
+    assert_eq!(
+        step_tile(ArenaName::Labyrinth, IVec2::new(0, 3), IVec2::NEG_X),
+        None
+    );
+    assert_eq!(
+        step_tile(ArenaName::Gala, IVec2::new(4, 0), IVec2::NEG_Y),
+        None
+    );
+}
+
+#[test]
+fn test_walls_ring_only_the_battleground_edge() {
+    let max_x = GRID_WIDTH as i32 - 1;
+    let max_y = GRID_HEIGHT as i32 - 1;
+
+    // Labyrinth is the top-left arena - walled on its left and top
+    let labyrinth = ArenaOccupancy::walled(ArenaName::Labyrinth);
+    assert_eq!(labyrinth.terrain(IVec2::new(0, 5)), TileTerrain::Wall);
+    assert_eq!(labyrinth.terrain(IVec2::new(9, max_y)), TileTerrain::Wall);
+    assert_eq!(labyrinth.terrain(IVec2::new(max_x, 5)), TileTerrain::Open);
+    assert_eq!(labyrinth.terrain(IVec2::new(9, 0)), TileTerrain::Open);
+    assert_eq!(labyrinth.terrain(IVec2::new(1, 1)), TileTerrain::Open);
+
+    // Bastion sits in the middle with neighbours on every side
+    let bastion = ArenaOccupancy::walled(ArenaName::Bastion);
+    assert_eq!(bastion.tiles_with(TileTerrain::Wall).count(), 0);
+}
+
+#[test]
+fn test_many_grid_steps_do_not_drift() {
+    let mut arena = ArenaName::Labyrinth;
+    let mut tile = IVec2::new(3, 3);
+
+    // Walk back and forth across the Labyrinth/GuildHouse seam thousands of times
+    for i in 0..10_000 {
+        let step = if (i / 100) % 2 == 0 {
+            IVec2::X
+        } else {
+            IVec2::NEG_X
+        };
+        (arena, tile) = step_tile(arena, tile, step).expect("Step stays on battleground");
+    }
+
+    assert_eq!((arena, tile), (ArenaName::Labyrinth, IVec2::new(3, 3)));
+    assert_eq!(
+        tile_to_local_translation(tile, 0.5),
+        Vec3::new(0.75, 0.75, 0.5)
+    );
+}
+
+/// Builds an app with two neighbouring arenas and the occupancy plugin
+fn create_occupancy_app() -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.add_plugins(OccupancyPlugin);
+    app.insert_resource(CurrentArena(ArenaName::Labyrinth));
+
+    let world = app.world_mut();
+    let labyrinth = world
+        .spawn((Arena(ArenaName::Labyrinth), Transform::default()))
+        .id();
+    let guild_house = world
+        .spawn((Arena(ArenaName::GuildHouse), Transform::default()))
+        .id();
+    let mut entities = ArenaName::ALL_ARENAS.map(|name| (name, Entity::PLACEHOLDER));
+    entities[ArenaName::Labyrinth as usize].1 = labyrinth;
+    entities[ArenaName::GuildHouse as usize].1 = guild_house;
+    world.insert_resource(ArenaEntities::new(entities));
+
+    (app, labyrinth, guild_house)
+}
+
+fn spawn_unit(app: &mut App, arena_entity: Entity, tile: IVec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Character,
+            GridPositionComponent(tile),
+            Transform::default(),
+            ChildOf(arena_entity),
+        ))
+        .id()
+}
+
+fn request_move(app: &mut App, entity: Entity, to_arena: ArenaName, to_tile: IVec2) {
+    app.world_mut().send_event(MoveRequest {
+        entity,
+        to_arena,
+        to_tile,
+    });
+}
+
+fn tile_of(app: &App, entity: Entity) -> IVec2 {
+    app.world().get::<GridPositionComponent>(entity).unwrap().0
+}
+
+#[test]
+fn test_moves_into_bosses_walls_and_borders_are_blocked() {
+    let (mut app, labyrinth, _) = create_occupancy_app();
+    app.world_mut().spawn((
+        Boss,
+        GridPositionComponent(IVec2::new(10, 10)),
+        ChildOf(labyrinth),
+    ));
+    let hero = spawn_unit(&mut app, labyrinth, IVec2::new(8, 10));
+    {
+        let mut occupancy = app
+            .world_mut()
+            .get_mut::<ArenaOccupancy>(labyrinth)
+            .expect("Arena requires occupancy");
+        occupancy.set_terrain(IVec2::new(8, 11), TileTerrain::Wall);
+        occupancy.set_terrain(IVec2::new(8, 9), TileTerrain::Border);
+    }
+    app.update();
+
+    // The boss's 3x3 footprint starts one tile to the right
+    for to_tile in [IVec2::new(9, 10), IVec2::new(8, 11), IVec2::new(8, 9)] {
+        request_move(&mut app, hero, ArenaName::Labyrinth, to_tile);
+        app.update();
+        assert_eq!(tile_of(&app, hero), IVec2::new(8, 10));
+    }
+
+    request_move(&mut app, hero, ArenaName::Labyrinth, IVec2::new(7, 10));
+    app.update();
+    assert_eq!(tile_of(&app, hero), IVec2::new(7, 10));
+}
+
+#[test]
+fn test_boss_moves_check_its_whole_footprint() {
+    let (mut app, labyrinth, _) = create_occupancy_app();
+    let boss = app
+        .world_mut()
+        .spawn((
+            Boss,
+            GridPositionComponent(IVec2::new(10, 10)),
+            Transform::default(),
+            ChildOf(labyrinth),
+        ))
+        .id();
+    // Beside the boss's right edge, clear of the row its centre tile moves along
+    spawn_unit(&mut app, labyrinth, IVec2::new(12, 11));
+    app.world_mut()
+        .get_mut::<ArenaOccupancy>(labyrinth)
+        .expect("Arena requires occupancy")
+        .set_terrain(IVec2::new(9, 8), TileTerrain::Wall);
+    app.update();
+
+    for to_tile in [IVec2::new(11, 10), IVec2::new(10, 9)] {
+        request_move(&mut app, boss, ArenaName::Labyrinth, to_tile);
+        app.update();
+        assert_eq!(tile_of(&app, boss), IVec2::new(10, 10));
+    }
+
+    request_move(&mut app, boss, ArenaName::Labyrinth, IVec2::new(10, 11));
+    app.update();
+    assert_eq!(tile_of(&app, boss), IVec2::new(10, 11));
+
+    // The footprint follows the boss and the tiles it left are free again
+    let occupancy = app.world().get::<ArenaOccupancy>(labyrinth).unwrap();
+    assert_eq!(occupancy.occupant(IVec2::new(11, 12)), Some(boss));
+    assert_eq!(occupancy.occupant(IVec2::new(11, 9)), None);
+}
+
+#[test]
+fn test_heroes_cannot_stack_on_one_tile() {
+    let (mut app, labyrinth, _) = create_occupancy_app();
+    let first = spawn_unit(&mut app, labyrinth, IVec2::new(5, 5));
+    let second = spawn_unit(&mut app, labyrinth, IVec2::new(6, 5));
+    app.update();
+
+    request_move(&mut app, second, ArenaName::Labyrinth, IVec2::new(5, 5));
+    app.update();
+
+    assert_eq!(tile_of(&app, first), IVec2::new(5, 5));
+    assert_eq!(tile_of(&app, second), IVec2::new(6, 5));
+}
+
+#[test]
+fn test_ghost_wins_a_contested_tile_regardless_of_request_order() {
+    for ghost_first in [true, false] {
+        let (mut app, labyrinth, _) = create_occupancy_app();
+        let live = spawn_unit(&mut app, labyrinth, IVec2::new(4, 5));
+        let ghost = spawn_unit(&mut app, labyrinth, IVec2::new(6, 5));
+        app.world_mut().entity_mut(ghost).insert(Ghost);
+        app.update();
+
+        let target = IVec2::new(5, 5);
+        if ghost_first {
+            request_move(&mut app, ghost, ArenaName::Labyrinth, target);
+            request_move(&mut app, live, ArenaName::Labyrinth, target);
+        } else {
+            request_move(&mut app, live, ArenaName::Labyrinth, target);
+            request_move(&mut app, ghost, ArenaName::Labyrinth, target);
+        }
+        app.update();
+
+        assert_eq!(tile_of(&app, ghost), target);
+        assert_eq!(tile_of(&app, live), IVec2::new(4, 5));
+    }
+}
+
+#[test]
+fn test_move_across_arena_edge_reparents_the_active_hero() {
+    let (mut app, labyrinth, guild_house) = create_occupancy_app();
+    let hero = spawn_unit(&mut app, labyrinth, IVec2::new(GRID_WIDTH as i32 - 1, 3));
+    app.world_mut().entity_mut(hero).insert(Active);
+    app.update();
+
+    let (to_arena, to_tile) = step_tile(
+        ArenaName::Labyrinth,
+        IVec2::new(GRID_WIDTH as i32 - 1, 3),
+        IVec2::X,
+    )
+    .unwrap();
+    request_move(&mut app, hero, to_arena, to_tile);
+    app.update();
+
+    assert_eq!(tile_of(&app, hero), IVec2::new(0, 3));
+    assert_eq!(
+        app.world().get::<ChildOf>(hero).unwrap().parent(),
+        guild_house
+    );
+    assert_eq!(
+        app.world().resource::<CurrentArena>().0,
+        ArenaName::GuildHouse
+    );
+    // Transform is derived from the grid position in the same frame
+    assert_eq!(
+        app.world().get::<Transform>(hero).unwrap().translation.x,
+        0.0
+    );
+}
@@ -1,5 +1,6 @@
 use crate::arena::{ARENA_HEIGHT, ARENA_WIDTH, CameraUpdate, CurrentArena, TILE_SIZE};
-use crate::arena_camera::ZoomOut;
+use crate::arena_camera::{CameraRig, ZoomOut, arena_shot};
+use crate::controls::{Action, ActionState};
 use bevy::prelude::*;
 
 pub const ZOOM: (f32, f32) = (24.0, 72.0);
@@ -17,9 +18,7 @@ pub fn position_camera_for_arena(transform: &mut Transform, arena_index: u8, zoo
 
 /// Setup camera to center on a specific arena
 pub fn setup_camera(mut commands: Commands, current_arena: Res<CurrentArena>) {
-    let arena = &*current_arena;
-    let mut transform = Transform::default();
-    position_camera_for_arena(&mut transform, arena.0.as_u8(), ZOOM.0);
+    let transform = arena_shot(current_arena.0.as_u8(), ZOOM.0);
 
     commands.spawn((
         Camera3d::default(),
@@ -34,32 +33,35 @@ pub fn setup_camera(mut commands: Commands, current_arena: Res<CurrentArena>) {
             far: 150.0, // Increased far plane to accommodate further camera distance
         }),
         transform,
+        CameraRig::default(),
     ));
 }
 
 pub fn toggle_camera_zoom(
     mut commands: Commands,
-    keycode: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     current_arena: Res<CurrentArena>,
-    camera_query: Single<(Entity, &mut Transform, Option<&ZoomOut>), With<Camera>>,
+    camera_query: Single<(Entity, &Transform, &mut CameraRig, Option<&ZoomOut>), With<Camera>>,
     mut arena_refresh_event: EventWriter<CameraUpdate>,
 ) {
-    if keycode.just_pressed(KeyCode::KeyP) {
-        let (camera_entity, mut camera_transform, zoom_out) = camera_query.into_inner();
+    if actions.just_pressed(Action::ToggleOverview) {
+        let (camera_entity, camera_transform, mut rig, zoom_out) = camera_query.into_inner();
         let current_arena = &*current_arena;
 
         if zoom_out.is_some() {
-            // Camera is zoomed out, zoom back in to current arena
-            // Reset camera position based on current arena
-            position_camera_for_arena(&mut camera_transform, current_arena.0.as_u8(), ZOOM.0);
+            // Camera is zoomed out, glide back in to the current arena
+            rig.move_to(
+                camera_transform,
+                arena_shot(current_arena.0.as_u8(), ZOOM.0),
+            );
             commands.entity(camera_entity).remove::<ZoomOut>();
 
             // Send event
             arena_refresh_event.write(CameraUpdate);
         } else {
-            // Center camera to see all 9 arenas (middle of the 3x3 grid)
+            // Pull back to see all 9 arenas (middle of the 3x3 grid)
             commands.entity(camera_entity).insert(ZoomOut);
-            position_camera_for_arena(&mut camera_transform, 4, ZOOM.1);
+            rig.move_to(camera_transform, arena_shot(4, ZOOM.1));
 
             // Send event
             arena_refresh_event.write(CameraUpdate);
@@ -1,11 +1,16 @@
 mod camera;
+mod rig;
 
 use bevy::math::Vec3;
 use bevy::prelude::Component;
 pub use camera::*;
+pub use rig::*;
 
 /// Marker component indicating the camera is zoomed out
 #[derive(Component)]
 pub struct ZoomOut;
 
 pub const CAMERA_CENTER: Vec3 = Vec3::new(8.125, 3.5, 0.0);
+
+#[cfg(test)]
+mod tests;
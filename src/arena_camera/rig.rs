@@ -0,0 +1,141 @@
+use crate::arena::CurrentArena;
+use crate::arena_camera::{ZOOM, ZoomOut, position_camera_for_arena};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Time taken to glide the camera from one shot to the next
+pub const CAMERA_TRANSITION: Duration = Duration::from_millis(600);
+
+/// Camera transform framing an arena at the given zoom distance
+#[must_use]
+pub fn arena_shot(arena_index: u8, zoom: f32) -> Transform {
+    let mut transform = Transform::default();
+    position_camera_for_arena(&mut transform, arena_index, zoom);
+    transform
+}
+
+/// Cubic ease-in-out over 0..=1
+#[must_use]
+pub fn ease_in_out_cubic(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+/// One eased glide from `from` to `to` (value type driven by CameraRig)
+#[derive(Debug, Clone)]
+pub struct CameraTransition {
+    pub from: Transform,
+    pub to: Transform,
+    pub timer: Timer,
+}
+
+impl CameraTransition {
+    #[must_use]
+    pub fn new(from: Transform, to: Transform) -> Self {
+        Self {
+            from,
+            to,
+            timer: Timer::new(CAMERA_TRANSITION, TimerMode::Once),
+        }
+    }
+
+    /// Transform at the transition's current progress
+    #[must_use]
+    pub fn sample(&self) -> Transform {
+        let t = ease_in_out_cubic(self.timer.fraction());
+        Transform {
+            translation: self.from.translation.lerp(self.to.translation, t),
+            rotation: self.from.rotation.slerp(self.to.rotation, t),
+            scale: self.from.scale.lerp(self.to.scale, t),
+        }
+    }
+}
+
+/// Eased camera rig - every camera move goes through here instead of
+/// writing the Transform directly. Moves requested mid-glide are queued
+/// and played in order once the current one lands.
+#[derive(Component, Debug, Clone, Default)]
+pub struct CameraRig {
+    pub transition: Option<CameraTransition>,
+    pub queued: VecDeque<Transform>,
+}
+
+impl CameraRig {
+    /// Where the camera ends up once every queued move has played
+    #[must_use]
+    pub fn destination(&self, current: &Transform) -> Transform {
+        self.queued
+            .back()
+            .copied()
+            .or_else(|| self.transition.as_ref().map(|transition| transition.to))
+            .unwrap_or(*current)
+    }
+
+    /// Requests a glide to `target`, ignoring it if the camera is already headed there
+    pub fn move_to(&mut self, current: &Transform, target: Transform) {
+        if self.destination(current) == target {
+            return;
+        }
+        if self.transition.is_some() {
+            self.queued.push_back(target);
+        } else {
+            self.transition = Some(CameraTransition::new(*current, target));
+        }
+    }
+
+    #[must_use]
+    pub fn is_moving(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Advances the glide, starting the next queued move when one lands
+    pub fn advance(&mut self, transform: &mut Transform, delta: Duration) {
+        let Some(transition) = self.transition.as_mut() else {
+            return;
+        };
+        transition.timer.tick(delta);
+        if transition.timer.finished() {
+            // Land exactly on the shot rather than on a float-rounded sample
+            let landed = transition.to;
+            *transform = landed;
+            self.transition = self
+                .queued
+                .pop_front()
+                .map(|next| CameraTransition::new(landed, next));
+        } else {
+            *transform = transition.sample();
+        }
+    }
+}
+
+/// Drives every camera rig forward by the frame delta
+pub fn animate_camera_rig(
+    time: Res<Time>,
+    mut camera_q: Query<(&mut Transform, &mut CameraRig), With<Camera3d>>,
+) {
+    for (mut transform, mut rig) in camera_q.iter_mut() {
+        if rig.is_moving() {
+            rig.advance(&mut transform, time.delta());
+        }
+    }
+}
+
+/// Glides the camera onto the current arena whenever it changes outside the overview
+pub fn follow_current_arena(
+    current_arena: Res<CurrentArena>,
+    mut camera_q: Query<(&Transform, &mut CameraRig, Has<ZoomOut>), With<Camera3d>>,
+) {
+    if !current_arena.is_changed() {
+        return;
+    }
+    for (transform, mut rig, zoomed_out) in camera_q.iter_mut() {
+        if !zoomed_out {
+            rig.move_to(transform, arena_shot(current_arena.0.as_u8(), ZOOM.0));
+        }
+    }
+}
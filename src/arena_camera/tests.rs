@@ -0,0 +1,122 @@
+use super::*;
+use crate::arena::{ArenaName, CameraUpdate, CurrentArena, increment_current_arena};
+use crate::controls::{Action, ActionState};
+use bevy::app::{App, Update};
+use bevy::prelude::{Camera3d, Entity, IntoScheduleConfigs, Transform};
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+const FRAME: Duration = Duration::from_millis(100);
+
+/// Camera-only app: input, arena switching and the rig, no GPU required
+fn create_test_app(arena: ArenaName) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.init_resource::<ActionState>();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+    app.add_event::<CameraUpdate>();
+    app.insert_resource(CurrentArena(arena));
+    app.add_systems(
+        Update,
+        (
+            (toggle_camera_zoom, increment_current_arena),
+            follow_current_arena,
+            animate_camera_rig,
+        )
+            .chain(),
+    );
+
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera3d::default(),
+            arena_shot(arena.as_u8(), ZOOM.0),
+            CameraRig::default(),
+        ))
+        .id();
+    // First update only primes the clock
+    app.update();
+    (app, camera)
+}
+
+/// Triggers an action for exactly one frame
+fn tap(app: &mut App, action: Action) {
+    app.world_mut()
+        .resource_mut::<ActionState>()
+        .set(action, true);
+    app.update();
+    app.world_mut()
+        .resource_mut::<ActionState>()
+        .set(action, false);
+}
+
+fn camera_transform(app: &App, camera: Entity) -> Transform {
+    *app.world().get::<Transform>(camera).unwrap()
+}
+
+#[test]
+fn test_ease_in_out_cubic_is_symmetric_and_clamped() {
+    assert_eq!(ease_in_out_cubic(0.0), 0.0);
+    assert_eq!(ease_in_out_cubic(0.5), 0.5);
+    assert_eq!(ease_in_out_cubic(1.0), 1.0);
+    assert_eq!(ease_in_out_cubic(2.0), 1.0);
+    assert!((ease_in_out_cubic(0.25) + ease_in_out_cubic(0.75) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_overview_toggle_glides_the_camera_out_over_time() {
+    let (mut app, camera) = create_test_app(ArenaName::GuildHouse);
+    let start = arena_shot(ArenaName::GuildHouse.as_u8(), ZOOM.0);
+    let overview = arena_shot(4, ZOOM.1);
+
+    tap(&mut app, Action::ToggleOverview);
+    let first_frame = camera_transform(&app, camera);
+    assert!(first_frame.translation.z > start.translation.z);
+    assert!(first_frame.translation.z < overview.translation.z);
+
+    // 0.6s glide at 0.1s per frame - halfway after three frames total
+    app.update();
+    app.update();
+    let halfway = camera_transform(&app, camera);
+    let expected = start.translation.lerp(overview.translation, 0.5);
+    assert!(halfway.translation.distance(expected) < 1e-3);
+
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(camera_transform(&app, camera), overview);
+    assert!(!app.world().get::<CameraRig>(camera).unwrap().is_moving());
+    assert!(app.world().get::<ZoomOut>(camera).is_some());
+}
+
+#[test]
+fn test_arena_switches_during_a_glide_are_queued_in_order() {
+    let (mut app, camera) = create_test_app(ArenaName::Labyrinth);
+
+    tap(&mut app, Action::NextArena);
+    tap(&mut app, Action::NextArena);
+    {
+        let rig = app.world().get::<CameraRig>(camera).unwrap();
+        assert!(rig.is_moving());
+        assert_eq!(
+            rig.queued.iter().copied().collect::<Vec<_>>(),
+            vec![arena_shot(ArenaName::Sanctum.as_u8(), ZOOM.0)]
+        );
+    }
+    assert_eq!(app.world().resource::<CurrentArena>().0, ArenaName::Sanctum);
+
+    for _ in 0..4 {
+        app.update();
+    }
+    assert_eq!(
+        camera_transform(&app, camera),
+        arena_shot(ArenaName::GuildHouse.as_u8(), ZOOM.0)
+    );
+    for _ in 0..6 {
+        app.update();
+    }
+    assert_eq!(
+        camera_transform(&app, camera),
+        arena_shot(ArenaName::Sanctum.as_u8(), ZOOM.0)
+    );
+}
@@ -0,0 +1,224 @@
+//! Bastion: The Warrior - the Patchwerk-inspired tank-and-spank fight from _docs/BOSS_FIGHTS.md
+use crate::arena::{Arena, ArenaReset, BOSS_FOOTPRINT_RADIUS};
+use crate::boss::{
+    BossActions, BossPhase, HeroQuery, Rotation, ScriptCursor, living_heroes, within,
+};
+use crate::character::Boss;
+use crate::combat::{DamageCause, Health};
+use crate::minion::{Minion, MinionKind, SpawnMinion};
+use crate::recording::Playback;
+use crate::status_effect::{ApplyStatusEffect, StatusEffectKind};
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::prelude::*;
+
+/// Fixed Summon Constructs spots, one per quarter of the arena
+const CONSTRUCT_SPOTS: [IVec2; 4] = [
+    IVec2::new(12, 6),
+    IVec2::new(52, 6),
+    IVec2::new(12, 24),
+    IVec2::new(52, 24),
+];
+
+const SHIELD_BASH: Rotation = (3.0, 6.0);
+const ARMOR_PLATING: Rotation = (8.0, 20.0);
+const SUMMON_CONSTRUCTS: Rotation = (10.0, 30.0);
+const FORTIFY: Rotation = (5.0, 5.0);
+const GRAVITY_WELL: Rotation = (15.0, 20.0);
+const GRAVITY_PULL: Rotation = (0.5, 0.5);
+const CATACLYSM_SMASH: Rotation = (20.0, 15.0);
+const BOSS_STEP: Rotation = (2.0, 2.0);
+
+/// Telegraph between Shield Bash turning on its target and the hit
+const SHIELD_BASH_TELEGRAPH_SECS: f32 = 1.5;
+const SHIELD_BASH_RANGE: i32 = 4;
+/// Cosine of the cone's half-angle (45 degrees either side of the target)
+const SHIELD_BASH_CONE_COS: f32 = 0.7;
+const SHIELD_BASH_DAMAGE: f32 = 250.0;
+const GRAVITY_WELL_SECS: f32 = 5.0;
+const CATACLYSM_PULSES: u8 = 3;
+const CATACLYSM_GAP_SECS: f32 = 1.0;
+const CATACLYSM_DAMAGE: f32 = 120.0;
+
+/// Bastion script state, reset whenever the arena loop restarts
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct BastionEncounter {
+    /// Loop time the telegraphed Shield Bash lands, and the direction it faces
+    bash: Option<(TimeStamp, Vec2)>,
+    /// Loop time Gravity Well stops pulling constructs in
+    gravity_until: Option<TimeStamp>,
+    /// Loop time of the next Cataclysm Smash pulse
+    next_pulse: Option<TimeStamp>,
+    pulses_left: u8,
+}
+
+/// The Bastion boss with its script state
+type BastionBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut BastionEncounter,
+        &'static mut ScriptCursor,
+        &'static BossPhase,
+        &'static GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    With<Boss>,
+>;
+
+/// Runs the Bastion rotation against the arena clock
+///
+/// The boss walks into melee with the nearest hero, Shield Bashes a cone through them
+/// and periodically hardens behind Armor Plating. Constructs it summons stack Fortified
+/// on it until they die; from phase Three, Gravity Well drags them back to its side, and
+/// in phase Four, Cataclysm Smash pulses damage across the whole arena.
+pub fn run_bastion_encounter(
+    mut reset_events: EventReader<ArenaReset>,
+    arena_q: Query<(Entity, &Arena, &TimelineClock), With<Playback>>,
+    mut boss_q: BastionBossQuery,
+    hero_q: HeroQuery,
+    minion_q: Query<(Entity, &Minion, &GridPositionComponent, &Health, &ChildOf)>,
+    mut actions: BossActions,
+) {
+    let resets: Vec<_> = reset_events.read().map(|event| event.arena).collect();
+
+    for (boss, mut encounter, mut cursor, phase, position, health, child_of) in boss_q.iter_mut() {
+        let Ok((arena_entity, arena, clock)) = arena_q.get(child_of.parent()) else {
+            continue;
+        };
+        if resets.contains(&arena.0) {
+            *encounter = BastionEncounter::default();
+        }
+        let window = cursor.advance(clock);
+        if health.is_dead() {
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+        let constructs: Vec<(Entity, IVec2)> = minion_q
+            .iter()
+            .filter(|(_, minion, _, health, parent)| {
+                minion.0 == MinionKind::Construct
+                    && parent.parent() == arena_entity
+                    && !health.is_dead()
+            })
+            .map(|(construct, _, tile, ..)| (construct, tile.0))
+            .collect();
+
+        if window.crossed(ARMOR_PLATING.0, ARMOR_PLATING.1) {
+            actions.effects.write(ApplyStatusEffect {
+                target: boss,
+                source: boss,
+                kind: StatusEffectKind::ArmorPlating,
+            });
+        }
+
+        if window.crossed(SUMMON_CONSTRUCTS.0, SUMMON_CONSTRUCTS.1) {
+            for tile in CONSTRUCT_SPOTS {
+                actions.summons.write(SpawnMinion {
+                    arena: arena.0,
+                    kind: MinionKind::Construct,
+                    tile,
+                });
+            }
+        }
+        if window.crossed(FORTIFY.0, FORTIFY.1) {
+            for (construct, _) in &constructs {
+                actions.effects.write(ApplyStatusEffect {
+                    target: boss,
+                    source: *construct,
+                    kind: StatusEffectKind::Fortified,
+                });
+            }
+        }
+
+        if *phase >= BossPhase::Three && window.crossed(GRAVITY_WELL.0, GRAVITY_WELL.1) {
+            encounter.gravity_until = Some(TimeStamp::new(window.to.as_secs() + GRAVITY_WELL_SECS));
+        }
+        if let Some(until) = encounter.gravity_until {
+            if window.crossed(GRAVITY_PULL.0, GRAVITY_PULL.1) {
+                for (construct, tile) in &constructs {
+                    actions.step_toward(*construct, arena.0, *tile, position.0);
+                }
+            }
+            if window.contains(until) {
+                encounter.gravity_until = None;
+            }
+        }
+
+        if *phase == BossPhase::Four && window.crossed(CATACLYSM_SMASH.0, CATACLYSM_SMASH.1) {
+            encounter.pulses_left = CATACLYSM_PULSES;
+            encounter.next_pulse = Some(window.to);
+        }
+        if let Some(pulse_at) = encounter.next_pulse
+            && window.contains(pulse_at)
+        {
+            for (hero, tile) in &heroes {
+                actions.strike_area(
+                    boss,
+                    DamageCause::BossAction("Cataclysm Smash"),
+                    &[(*hero, *tile)],
+                    *tile,
+                    0,
+                    CATACLYSM_DAMAGE,
+                );
+            }
+            encounter.pulses_left -= 1;
+            encounter.next_pulse = (encounter.pulses_left > 0)
+                .then(|| TimeStamp::new(window.to.as_secs() + CATACLYSM_GAP_SECS));
+        }
+
+        let nearest = heroes
+            .iter()
+            .map(|(_, tile)| *tile)
+            .min_by_key(|tile| ((*tile - position.0).length_squared(), tile.x, tile.y));
+
+        if window.crossed(SHIELD_BASH.0, SHIELD_BASH.1)
+            && let Some(target) = nearest
+            && target != position.0
+        {
+            encounter.bash = Some((
+                TimeStamp::new(window.to.as_secs() + SHIELD_BASH_TELEGRAPH_SECS),
+                (target - position.0).as_vec2().normalize(),
+            ));
+        }
+        if let Some((bash_at, facing)) = encounter.bash
+            && window.contains(bash_at)
+        {
+            for (hero, tile) in &heroes {
+                if in_cone(position.0, facing, *tile) {
+                    actions.strike_area(
+                        boss,
+                        DamageCause::BossAction("Shield Bash"),
+                        &[(*hero, *tile)],
+                        *tile,
+                        0,
+                        SHIELD_BASH_DAMAGE,
+                    );
+                }
+            }
+            encounter.bash = None;
+        }
+
+        // Holds still to channel Cataclysm Smash, otherwise closes to melee range
+        let channeling = encounter.pulses_left > 0;
+        if !channeling
+            && window.crossed(BOSS_STEP.0, BOSS_STEP.1)
+            && let Some(target) = nearest
+            && !within(target, position.0, BOSS_FOOTPRINT_RADIUS + 1)
+        {
+            actions.step_toward(boss, arena.0, position.0, target);
+        }
+    }
+}
+
+/// Whether a tile falls inside Shield Bash's cone from `origin` toward `facing`
+fn in_cone(origin: IVec2, facing: Vec2, tile: IVec2) -> bool {
+    let offset = tile - origin;
+    if offset == IVec2::ZERO {
+        return true;
+    }
+    offset.abs().max_element() <= SHIELD_BASH_RANGE
+        && offset.as_vec2().normalize().dot(facing) >= SHIELD_BASH_CONE_COS
+}
@@ -0,0 +1,338 @@
+//! Casino: The Merchant - the Thaddius-inspired treasure fight from _docs/BOSS_FIGHTS.md
+use crate::arena::{
+    Arena, ArenaEntities, ArenaOccupancy, ArenaReset, BOSS_FOOTPRINT_RADIUS,
+    tile_to_local_translation,
+};
+use crate::boss::{
+    BossActions, BossPhase, HeroQuery, Rotation, ScriptCursor, ScriptWindow, living_heroes, within,
+};
+use crate::character::{Boss, Character, Interact};
+use crate::combat::{DamageCause, HealEvent, Health};
+use crate::materials::Materials;
+use crate::minion::{Minion, MinionKind, SpawnMinion};
+use crate::recording::Playback;
+use crate::status_effect::{ApplyStatusEffect, StatusEffectKind};
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::prelude::*;
+
+/// Fixed Hoard Call spots
+const HOARD_SPOTS: [IVec2; 6] = [
+    IVec2::new(14, 8),
+    IVec2::new(14, 22),
+    IVec2::new(32, 25),
+    IVec2::new(50, 22),
+    IVec2::new(50, 8),
+    IVec2::new(32, 4),
+];
+
+const HOARD_CALL: Rotation = (5.0, 20.0);
+const TREASURE_SHIELD: Rotation = (1.0, 1.0);
+const SUMMON_GOLDLINGS: Rotation = (12.0, 20.0);
+const GOLDLING_STEP: Rotation = (0.5, 0.5);
+const TREASURE_OVERLOAD: Rotation = (30.0, 20.0);
+const BOSS_STEP: Rotation = (1.5, 1.5);
+
+/// How close the boss must stand to a pile for Treasure Shield to hold
+const TREASURE_SHIELD_RANGE: i32 = 3;
+/// Goldlings spawn this far either side of the boss
+const GOLDLING_OFFSETS: [IVec2; 2] = [IVec2::new(-3, 0), IVec2::new(3, 0)];
+/// Healing the boss receives for every pile a goldling brings home
+const GOLDLING_HEAL: f32 = 250.0;
+/// Delay between Treasure Overload and the unlooted piles exploding
+const OVERLOAD_DELAY_SECS: f32 = 3.0;
+const OVERLOAD_RADIUS: i32 = 2;
+const OVERLOAD_DAMAGE: f32 = 300.0;
+
+/// Casino script marker - the fight's state lives on its treasure piles
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct CasinoEncounter;
+
+/// Seconds between a hero touching a bait pile and it going off
+const BAIT_FUSE_SECS: f32 = 2.0;
+
+/// A pile of treasure heroes can loot by interacting beside it
+#[derive(Component, Debug, Clone, Default)]
+pub struct TreasurePile {
+    /// Loop time the pile blows up - set by Treasure Overload, or by touching bait
+    detonate_at: Option<TimeStamp>,
+    /// False treasure that arms itself instead of being looted
+    bait: bool,
+}
+
+impl TreasurePile {
+    /// A false pile that explodes shortly after someone tries to loot it
+    #[must_use]
+    pub fn bait() -> Self {
+        Self {
+            detonate_at: None,
+            bait: true,
+        }
+    }
+
+    /// Whether the pile goes off during this frame's slice of the loop
+    #[must_use]
+    pub fn detonates_in(&self, window: ScriptWindow) -> bool {
+        self.detonate_at.is_some_and(|at| window.contains(at))
+    }
+}
+
+/// Goldling on its way back to the boss with a pile
+#[derive(Component, Debug)]
+pub struct CarryingTreasure;
+
+/// Heroes and ghosts interacting beside treasure piles loot them - bait piles
+/// arm themselves instead and go off when their fuse runs out
+pub fn collect_treasure(
+    mut commands: Commands,
+    mut interactions: EventReader<Interact>,
+    actor_q: Query<(&GridPositionComponent, &ChildOf, &Health), With<Character>>,
+    clock_q: Query<&TimelineClock>,
+    mut pile_q: Query<(Entity, &mut TreasurePile, &GridPositionComponent, &ChildOf)>,
+) {
+    let mut looted = Vec::new();
+    for event in interactions.read() {
+        let Ok((actor, arena, health)) = actor_q.get(event.actor) else {
+            continue;
+        };
+        if health.is_dead() {
+            continue;
+        }
+        for (pile, mut treasure, tile, pile_arena) in pile_q.iter_mut() {
+            if pile_arena.parent() != arena.parent()
+                || !within(tile.0, actor.0, 1)
+                || looted.contains(&pile)
+            {
+                continue;
+            }
+            if treasure.bait {
+                if treasure.detonate_at.is_none()
+                    && let Ok(clock) = clock_q.get(arena.parent())
+                {
+                    let fuse = clock.current().as_secs() + BAIT_FUSE_SECS;
+                    treasure.detonate_at = Some(TimeStamp::new(fuse));
+                    info!("{:?} set off the bait at {}", event.actor, tile.0);
+                }
+            } else {
+                looted.push(pile);
+                commands.entity(pile).despawn();
+                info!("{:?} looted the treasure pile at {}", event.actor, tile.0);
+            }
+        }
+    }
+}
+
+/// Clears an arena's treasure piles when its 2-minute loop restarts
+pub fn despawn_treasure_on_arena_reset(
+    mut commands: Commands,
+    mut reset_events: EventReader<ArenaReset>,
+    arena_entities: Res<ArenaEntities>,
+    pile_q: Query<(Entity, &ChildOf), With<TreasurePile>>,
+) {
+    for event in reset_events.read() {
+        let arena = arena_entities.get(event.arena);
+        for (pile, child_of) in pile_q.iter() {
+            if child_of.parent() == arena {
+                commands.entity(pile).despawn();
+            }
+        }
+    }
+}
+
+/// The Casino boss with its script state
+type CasinoBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut ScriptCursor,
+        &'static BossPhase,
+        &'static GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    (With<Boss>, With<CasinoEncounter>),
+>;
+
+/// Minions with whether they are carrying a pile back to the boss
+type GoldlingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Minion,
+        &'static GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+        Has<CarryingTreasure>,
+    ),
+>;
+
+/// Runs the Casino rotation against the arena clock
+///
+/// Hoard Call lays treasure at fixed spots and the boss walks from pile to pile,
+/// shielded while it stands beside one. From phase Two, goldlings steal piles back
+/// to heal it; from phase Three, Treasure Overload blows up every pile left unlooted.
+pub fn run_casino_encounter(
+    mut commands: Commands,
+    arena_q: Query<(Entity, &Arena, &TimelineClock, &ArenaOccupancy), With<Playback>>,
+    mut boss_q: CasinoBossQuery,
+    hero_q: HeroQuery,
+    mut pile_q: Query<(Entity, &mut TreasurePile, &GridPositionComponent, &ChildOf)>,
+    goldling_q: GoldlingQuery,
+    mut actions: BossActions,
+) {
+    for (boss, mut cursor, phase, position, health, child_of) in boss_q.iter_mut() {
+        let Ok((arena_entity, arena, clock, occupancy)) = arena_q.get(child_of.parent()) else {
+            continue;
+        };
+        let in_arena = |parent: &ChildOf| parent.parent() == arena_entity;
+        let window = cursor.advance(clock);
+        if health.is_dead() {
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+        // Bait is worthless to goldlings and does not shield the boss
+        let mut piles: Vec<(Entity, IVec2)> = pile_q
+            .iter()
+            .filter(|(_, treasure, _, parent)| in_arena(parent) && !treasure.bait)
+            .map(|(pile, _, tile, _)| (pile, tile.0))
+            .collect();
+        let mut stolen = Vec::new();
+
+        if window.crossed(HOARD_CALL.0, HOARD_CALL.1) {
+            for tile in HOARD_SPOTS {
+                let taken = pile_q
+                    .iter()
+                    .any(|(.., held, parent)| in_arena(parent) && held.0 == tile);
+                if !taken {
+                    commands.spawn((
+                        TreasurePile::default(),
+                        GridPositionComponent(tile),
+                        Transform::from_translation(tile_to_local_translation(tile, 0.0625)),
+                        ChildOf(arena_entity),
+                        Name::new("Treasure Pile"),
+                    ));
+                }
+            }
+        }
+
+        if window.crossed(TREASURE_SHIELD.0, TREASURE_SHIELD.1)
+            && piles
+                .iter()
+                .any(|(_, tile)| within(*tile, position.0, TREASURE_SHIELD_RANGE))
+        {
+            actions.effects.write(ApplyStatusEffect {
+                target: boss,
+                source: boss,
+                kind: StatusEffectKind::TreasureShield,
+            });
+        }
+
+        if *phase >= BossPhase::Two && window.crossed(SUMMON_GOLDLINGS.0, SUMMON_GOLDLINGS.1) {
+            for offset in GOLDLING_OFFSETS {
+                actions.summons.write(SpawnMinion {
+                    arena: arena.0,
+                    kind: MinionKind::Goldling,
+                    tile: position.0 + offset,
+                });
+            }
+        }
+        if window.crossed(GOLDLING_STEP.0, GOLDLING_STEP.1) {
+            let goldlings = goldling_q
+                .iter()
+                .filter(|(_, minion, _, health, parent, _)| {
+                    minion.0 == MinionKind::Goldling && in_arena(parent) && !health.is_dead()
+                });
+            for (goldling, _, tile, _, _, carrying) in goldlings {
+                if carrying {
+                    if within(tile.0, position.0, BOSS_FOOTPRINT_RADIUS + 1) {
+                        actions.heals.write(HealEvent {
+                            source: goldling,
+                            target: boss,
+                            amount: GOLDLING_HEAL,
+                        });
+                        commands.entity(goldling).remove::<CarryingTreasure>();
+                    } else {
+                        actions.path_toward(goldling, arena.0, occupancy, tile.0, position.0);
+                    }
+                    continue;
+                }
+                let Some(index) = nearest(tile.0, &piles) else {
+                    continue;
+                };
+                let (pile, pile_tile) = piles[index];
+                if within(pile_tile, tile.0, 1) {
+                    commands.entity(pile).despawn();
+                    commands.entity(goldling).insert(CarryingTreasure);
+                    stolen.push(pile);
+                    piles.remove(index);
+                } else {
+                    actions.path_toward(goldling, arena.0, occupancy, tile.0, pile_tile);
+                }
+            }
+        }
+
+        if *phase >= BossPhase::Three && window.crossed(TREASURE_OVERLOAD.0, TREASURE_OVERLOAD.1) {
+            let detonate_at = TimeStamp::new(window.to.as_secs() + OVERLOAD_DELAY_SECS);
+            for (_, mut pile, _, parent) in pile_q.iter_mut() {
+                if in_arena(parent) && pile.detonate_at.is_none() {
+                    pile.detonate_at = Some(detonate_at);
+                }
+            }
+        }
+        for (pile_entity, pile, tile, parent) in pile_q.iter() {
+            if !in_arena(parent) || !pile.detonates_in(window) {
+                continue;
+            }
+            // A goldling may have grabbed it this very tick
+            if stolen.contains(&pile_entity) {
+                continue;
+            }
+            actions.strike_area(
+                boss,
+                DamageCause::BossAction("Treasure Overload"),
+                &heroes,
+                tile.0,
+                OVERLOAD_RADIUS,
+                OVERLOAD_DAMAGE,
+            );
+            commands.entity(pile_entity).despawn();
+        }
+
+        // Walks from pile to pile to keep Treasure Shield up
+        if window.crossed(BOSS_STEP.0, BOSS_STEP.1)
+            && let Some(index) = nearest(position.0, &piles)
+            && !within(piles[index].1, position.0, BOSS_FOOTPRINT_RADIUS + 1)
+        {
+            actions.step_toward(boss, arena.0, position.0, piles[index].1);
+        }
+    }
+}
+
+/// Index of the pile closest to `from`, ties broken by tile
+fn nearest(from: IVec2, piles: &[(Entity, IVec2)]) -> Option<usize> {
+    piles
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, (_, tile))| ((*tile - from).length_squared(), tile.x, tile.y))
+        .map(|(index, _)| index)
+}
+
+/// Gives newly laid treasure piles a mesh and material
+pub fn attach_treasure_visuals(
+    mut commands: Commands,
+    pile_q: Query<Entity, Added<TreasurePile>>,
+    mats: Res<Materials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh: Local<Option<Handle<Mesh>>>,
+) {
+    let mesh = mesh
+        .get_or_insert_with(|| meshes.add(Cuboid::new(0.15, 0.15, 0.125)))
+        .clone();
+    for pile in pile_q.iter() {
+        commands
+            .entity(pile)
+            .insert((Mesh3d(mesh.clone()), MeshMaterial3d(mats.yellow.clone())));
+    }
+}
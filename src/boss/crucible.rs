@@ -0,0 +1,217 @@
+//! Crucible: The Alchemist - the Rotface-inspired hazard-pool fight from _docs/BOSS_FIGHTS.md
+use crate::arena::{Arena, ArenaOccupancy, ArenaReset, TileTerrain};
+use crate::boss::{
+    BossActions, BossPhase, HeroQuery, Rotation, ScriptCursor, living_heroes, within,
+};
+use crate::combat::{DamageCause, Health};
+use crate::recording::Playback;
+use crate::status_effect::{ApplyStatusEffect, StatusEffectKind, StatusEffects};
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::prelude::*;
+
+/// Fixed Unstable Pools spots - each pool covers the 2x2 block above-right of its corner
+const POOL_CORNERS: [IVec2; 6] = [
+    IVec2::new(10, 4),
+    IVec2::new(10, 25),
+    IVec2::new(54, 4),
+    IVec2::new(54, 25),
+    IVec2::new(32, 2),
+    IVec2::new(32, 17),
+];
+
+/// Wide arc the boss walks, clear of every pool
+const ARC_WAYPOINTS: [IVec2; 6] = [
+    IVec2::new(22, 12),
+    IVec2::new(22, 20),
+    IVec2::new(32, 24),
+    IVec2::new(42, 20),
+    IVec2::new(42, 12),
+    IVec2::new(32, 10),
+];
+
+const UNSTABLE_POOLS: Rotation = (5.0, 30.0);
+const ACID_BURST: Rotation = (3.0, 8.0);
+const HAZARD_TICK: Rotation = (1.0, 1.0);
+const REACTIVE_FIELDS: Rotation = (15.0, 30.0);
+const CATALYST_CORE: Rotation = (40.0, 40.0);
+const ELEMENTAL_COLLAPSE: Rotation = (25.0, 30.0);
+const BOSS_STEP: Rotation = (1.5, 1.5);
+
+/// Seconds reactive fields stay buffs before fading back to acid
+const REACTIVE_FIELD_SECS: f32 = 10.0;
+/// Delay between Elemental Collapse going off and the pools detonating
+const COLLAPSE_DELAY_SECS: f32 = 1.0;
+
+/// Acid Burst reach from the boss's centre tile (its body plus two tiles)
+const ACID_BURST_RADIUS: i32 = 3;
+const ACID_BURST_DAMAGE: f32 = 150.0;
+/// Volatile Gasses damage to anyone standing in acid, per hazard tick
+const ACID_TICK_DAMAGE: f32 = 40.0;
+const COLLAPSE_DAMAGE: f32 = 300.0;
+
+/// Crucible script state, reset whenever the arena loop restarts
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct CrucibleEncounter {
+    /// Loop time the reactive fields fade back to acid
+    fields_fade_at: Option<TimeStamp>,
+    /// Loop time the pending Elemental Collapse detonates
+    collapse_at: Option<TimeStamp>,
+    /// Index of the arc waypoint the boss is walking to
+    waypoint: usize,
+}
+
+/// The Crucible boss with its script state
+type CrucibleBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut CrucibleEncounter,
+        &'static mut ScriptCursor,
+        &'static BossPhase,
+        &'static GridPositionComponent,
+        &'static StatusEffects,
+        &'static Health,
+        &'static ChildOf,
+    ),
+>;
+
+/// Runs the Crucible rotation against the arena clock
+///
+/// Phase One lays acid pools that burn whoever stands in them; Two turns them into
+/// hasting reactive fields for a while; Three adds the stationary Catalyst Core shield;
+/// Four detonates every hazard tile at once.
+pub fn run_crucible_encounter(
+    mut reset_events: EventReader<ArenaReset>,
+    mut arena_q: Query<(Entity, &Arena, &TimelineClock, &mut ArenaOccupancy), With<Playback>>,
+    mut boss_q: CrucibleBossQuery,
+    hero_q: HeroQuery,
+    mut actions: BossActions,
+) {
+    let resets: Vec<_> = reset_events.read().map(|event| event.arena).collect();
+
+    for (boss, mut encounter, mut cursor, phase, position, effects, health, child_of) in
+        boss_q.iter_mut()
+    {
+        let Ok((arena_entity, arena, clock, mut occupancy)) = arena_q.get_mut(child_of.parent())
+        else {
+            continue;
+        };
+        if resets.contains(&arena.0) {
+            clear_hazards(&mut occupancy);
+            *encounter = CrucibleEncounter::default();
+        }
+        let window = cursor.advance(clock);
+        if health.is_dead() {
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+
+        if window.crossed(UNSTABLE_POOLS.0, UNSTABLE_POOLS.1) {
+            for corner in POOL_CORNERS {
+                for offset in [IVec2::ZERO, IVec2::X, IVec2::Y, IVec2::ONE] {
+                    if occupancy.terrain(corner + offset) == TileTerrain::Open {
+                        occupancy.set_terrain(corner + offset, TileTerrain::Acid);
+                    }
+                }
+            }
+        }
+
+        if *phase >= BossPhase::Two && window.crossed(REACTIVE_FIELDS.0, REACTIVE_FIELDS.1) {
+            occupancy.replace_terrain(TileTerrain::Acid, TileTerrain::Catalyst);
+            encounter.fields_fade_at =
+                Some(TimeStamp::new(window.to.as_secs() + REACTIVE_FIELD_SECS));
+        }
+        if let Some(fade_at) = encounter.fields_fade_at
+            && window.contains(fade_at)
+        {
+            occupancy.replace_terrain(TileTerrain::Catalyst, TileTerrain::Acid);
+            encounter.fields_fade_at = None;
+        }
+
+        if window.crossed(HAZARD_TICK.0, HAZARD_TICK.1) {
+            for (hero, tile) in &heroes {
+                match occupancy.terrain(*tile) {
+                    TileTerrain::Acid => {
+                        actions.strike_area(
+                            boss,
+                            DamageCause::BossAction("Unstable Pools"),
+                            &[(*hero, *tile)],
+                            *tile,
+                            0,
+                            ACID_TICK_DAMAGE,
+                        );
+                    }
+                    TileTerrain::Catalyst => {
+                        actions.effects.write(ApplyStatusEffect {
+                            target: *hero,
+                            source: boss,
+                            kind: StatusEffectKind::Haste,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if window.crossed(ACID_BURST.0, ACID_BURST.1) {
+            actions.strike_area(
+                boss,
+                DamageCause::BossAction("Acid Burst"),
+                &heroes,
+                position.0,
+                ACID_BURST_RADIUS,
+                ACID_BURST_DAMAGE,
+            );
+        }
+
+        if *phase >= BossPhase::Three && window.crossed(CATALYST_CORE.0, CATALYST_CORE.1) {
+            actions.effects.write(ApplyStatusEffect {
+                target: boss,
+                source: boss,
+                kind: StatusEffectKind::Shielded,
+            });
+        }
+
+        if *phase == BossPhase::Four && window.crossed(ELEMENTAL_COLLAPSE.0, ELEMENTAL_COLLAPSE.1) {
+            encounter.collapse_at = Some(TimeStamp::new(window.to.as_secs() + COLLAPSE_DELAY_SECS));
+        }
+        if let Some(collapse_at) = encounter.collapse_at
+            && window.contains(collapse_at)
+        {
+            let hazards: Vec<IVec2> = occupancy
+                .tiles_with(TileTerrain::Acid)
+                .chain(occupancy.tiles_with(TileTerrain::Catalyst))
+                .collect();
+            for (hero, tile) in &heroes {
+                if hazards.iter().any(|hazard| within(*tile, *hazard, 1)) {
+                    actions.strike_area(
+                        boss,
+                        DamageCause::BossAction("Elemental Collapse"),
+                        &[(*hero, *tile)],
+                        *tile,
+                        0,
+                        COLLAPSE_DAMAGE,
+                    );
+                }
+            }
+            clear_hazards(&mut occupancy);
+            encounter.collapse_at = None;
+        }
+
+        // Catalyst Core roots the boss in place for as long as the shield holds
+        let shielded = effects.stacks_of(StatusEffectKind::Shielded) > 0;
+        if !shielded && window.crossed(BOSS_STEP.0, BOSS_STEP.1) {
+            if position.0 == ARC_WAYPOINTS[encounter.waypoint] {
+                encounter.waypoint = (encounter.waypoint + 1) % ARC_WAYPOINTS.len();
+            }
+            actions.step_toward(boss, arena.0, position.0, ARC_WAYPOINTS[encounter.waypoint]);
+        }
+    }
+}
+
+fn clear_hazards(occupancy: &mut ArenaOccupancy) {
+    occupancy.replace_terrain(TileTerrain::Acid, TileTerrain::Open);
+    occupancy.replace_terrain(TileTerrain::Catalyst, TileTerrain::Open);
+}
@@ -0,0 +1,236 @@
+use crate::arena::{Arena, ArenaName, ArenaOccupancy, ArenaReset, MoveRequest};
+use crate::boss::{
+    BastionEncounter, CasinoEncounter, CrucibleEncounter, Decoy, GalaEncounter,
+    GuildHouseEncounter, MountainEncounter, PawnshopEncounter, SanctumEncounter,
+};
+use crate::character::{Boss, Character};
+use crate::combat::{DamageCause, DamageEvent, HealEvent, Health};
+use crate::minion::SpawnMinion;
+use crate::pathfinding::find_path;
+use crate::status_effect::ApplyStatusEffect;
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+/// Slice of arena-loop time a boss script covers this frame (value type)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScriptWindow {
+    pub from: TimeStamp,
+    pub to: TimeStamp,
+}
+
+impl ScriptWindow {
+    /// Whether the moment `at` falls inside this frame's slice
+    #[must_use]
+    pub fn contains(self, at: TimeStamp) -> bool {
+        self.from < at && at <= self.to
+    }
+
+    /// Whether an action first due at `first` seconds and every `every` seconds
+    /// after that comes due this frame
+    #[must_use]
+    pub fn crossed(self, first: f32, every: f32) -> bool {
+        let to = self.to.as_secs();
+        if to < first {
+            return false;
+        }
+        let due = first + ((to - first) / every).floor() * every;
+        due > self.from.as_secs()
+    }
+}
+
+/// A recurring action's timing in loop seconds: (first cast, interval), checked with
+/// `ScriptWindow::crossed`. Every encounter declares its rotation as these constants.
+pub type Rotation = (f32, f32);
+
+/// Loop time a boss's script or a unit's stepping has run up to - rewinds when the
+/// arena loop wraps, so every loop replays the same rotation and recorded layers stay in step
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct ScriptCursor(pub TimeStamp);
+
+impl ScriptCursor {
+    /// Moves the cursor to the clock's time, returning the slice it passed over
+    pub fn advance(&mut self, clock: &TimelineClock) -> ScriptWindow {
+        let to = clock.current();
+        let from = if to < self.0 { TimeStamp::ZERO } else { self.0 };
+        self.0 = to;
+        ScriptWindow { from, to }
+    }
+}
+
+/// Tile a boss starts every loop from
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct BossHome(pub IVec2);
+
+/// Event writers every boss script acts through
+#[derive(SystemParam)]
+pub struct BossActions<'w> {
+    pub damage: EventWriter<'w, DamageEvent>,
+    pub effects: EventWriter<'w, ApplyStatusEffect>,
+    pub moves: EventWriter<'w, MoveRequest>,
+    pub heals: EventWriter<'w, HealEvent>,
+    pub summons: EventWriter<'w, SpawnMinion>,
+}
+
+impl BossActions<'_> {
+    /// Damages every listed hero within `radius` tiles (square) of `center` with the
+    /// given attack
+    pub fn strike_area(
+        &mut self,
+        boss: Entity,
+        cause: DamageCause,
+        heroes: &[(Entity, IVec2)],
+        center: IVec2,
+        radius: i32,
+        amount: f32,
+    ) {
+        for (hero, _) in heroes
+            .iter()
+            .filter(|(_, tile)| within(*tile, center, radius))
+        {
+            self.damage.write(DamageEvent {
+                source: boss,
+                target: *hero,
+                amount,
+                cause,
+            });
+        }
+    }
+
+    /// Requests the first step of the cheapest path from `from` toward `target`,
+    /// for adds that must find their way around the boss and terrain
+    pub fn path_toward(
+        &mut self,
+        unit: Entity,
+        arena: ArenaName,
+        occupancy: &ArenaOccupancy,
+        from: IVec2,
+        target: IVec2,
+    ) {
+        if let Some(next) = find_path(occupancy, unit, from, target)
+            .and_then(|path| path.first().copied())
+            .filter(|next| *next != target)
+        {
+            self.moves.write(MoveRequest {
+                entity: unit,
+                to_arena: arena,
+                to_tile: next,
+            });
+        }
+    }
+
+    /// Requests one orthogonal step toward `target`, horizontal first
+    pub fn step_toward(&mut self, boss: Entity, arena: ArenaName, from: IVec2, target: IVec2) {
+        let delta = target - from;
+        let step = if delta.x != 0 {
+            IVec2::new(delta.x.signum(), 0)
+        } else {
+            IVec2::new(0, delta.y.signum())
+        };
+        if step != IVec2::ZERO {
+            self.moves.write(MoveRequest {
+                entity: boss,
+                to_arena: arena,
+                to_tile: from + step,
+            });
+        }
+    }
+}
+
+/// Heroes a boss script can see and hit
+pub type HeroQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    (With<Character>, Without<Boss>),
+>;
+
+/// Living heroes standing in the given arena, with their tiles, in query order.
+/// Scripts choosing one of them (or a tile near them) break ties on the tile itself,
+/// so the pick is the same on every replay of the loop.
+#[must_use]
+pub fn living_heroes(hero_q: &HeroQuery, arena: Entity) -> Vec<(Entity, IVec2)> {
+    hero_q
+        .iter()
+        .filter(|(_, _, health, child_of)| child_of.parent() == arena && !health.is_dead())
+        .map(|(hero, tile, ..)| (hero, tile.0))
+        .collect()
+}
+
+/// Whether two tiles are at most `radius` apart on both axes
+#[must_use]
+pub fn within(a: IVec2, b: IVec2, radius: i32) -> bool {
+    (a - b).abs().max_element() <= radius
+}
+
+/// Bosses spawned this frame - decoys mirror their original and need no script
+type NewBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static GridPositionComponent, &'static ChildOf),
+    (Added<Boss>, Without<Decoy>),
+>;
+
+/// Gives each new boss its arena's encounter script and name, and remembers where
+/// it started
+pub fn attach_encounters(mut commands: Commands, boss_q: NewBossQuery, arena_q: Query<&Arena>) {
+    for (boss, position, child_of) in boss_q.iter() {
+        let Ok(arena) = arena_q.get(child_of.parent()) else {
+            continue;
+        };
+        let mut entity = commands.entity(boss);
+        entity
+            .insert(BossHome(position.0))
+            .insert_if_new(Name::new(arena.0.boss_name()));
+        match arena.0 {
+            ArenaName::Bastion => {
+                entity.insert(BastionEncounter::default());
+            }
+            ArenaName::Casino => {
+                entity.insert(CasinoEncounter);
+            }
+            ArenaName::Crucible => {
+                entity.insert(CrucibleEncounter::default());
+            }
+            ArenaName::Gala => {
+                entity.insert(GalaEncounter::default());
+            }
+            ArenaName::GuildHouse => {
+                entity.insert(GuildHouseEncounter);
+            }
+            ArenaName::Mountain => {
+                entity.insert(MountainEncounter::default());
+            }
+            ArenaName::Pawnshop => {
+                entity.insert(PawnshopEncounter::default());
+            }
+            ArenaName::Sanctum => {
+                entity.insert(SanctumEncounter);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Puts bosses back on their home tile when their arena's loop restarts
+pub fn return_bosses_home(
+    mut reset_events: EventReader<ArenaReset>,
+    mut boss_q: Query<(&BossHome, &mut GridPositionComponent, &ChildOf)>,
+    arena_q: Query<&Arena>,
+) {
+    for event in reset_events.read() {
+        for (home, mut position, child_of) in boss_q.iter_mut() {
+            if arena_q
+                .get(child_of.parent())
+                .is_ok_and(|arena| arena.0 == event.arena)
+            {
+                position.0 = home.0;
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Gala: The Bard - the Heigan-inspired dash-and-trail fight from _docs/BOSS_FIGHTS.md
+use crate::arena::{
+    Arena, ArenaName, ArenaOccupancy, ArenaReset, BOSS_FOOTPRINT_RADIUS, GRID_HEIGHT, GRID_WIDTH,
+    TileTerrain, footprint,
+};
+use crate::boss::{
+    BossActions, BossPhase, HeroQuery, Rotation, ScriptCursor, living_heroes, within,
+};
+use crate::character::Boss;
+use crate::combat::{DamageCause, DamageEvent, Health};
+use crate::recording::Playback;
+use crate::rng::{RngSeed, SplitMix64};
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::prelude::*;
+
+const CHARGE_STRIKE: Rotation = (4.0, 8.0);
+const HAZARD_TICK: Rotation = (1.0, 1.0);
+const DYNAMIC_PLATFORMS: Rotation = (12.0, 10.0);
+const STAMPEDE: Rotation = (30.0, 20.0);
+
+/// Telegraph between Charge Strike picking its target and the dash
+const CHARGE_TELEGRAPH_SECS: f32 = 1.5;
+/// Stampede chains this many dashes, one every gap
+const STAMPEDE_DASHES: u8 = 4;
+const STAMPEDE_GAP_SECS: f32 = 1.0;
+
+const CHARGE_RANGE: i32 = 12;
+const STAMPEDE_RANGE: i32 = 8;
+const DASH_DAMAGE: f32 = 200.0;
+/// Damage to anyone standing on a trail tile, per hazard tick
+const TRAIL_TICK_DAMAGE: f32 = 30.0;
+/// Damage to anyone standing on a platform as it drops
+const PLATFORM_DROP_DAMAGE: f32 = 100.0;
+/// Platform rolls per Dynamic Platforms cast - rolls landing on blocked tiles are lost
+const PLATFORM_ROLLS: usize = 24;
+
+const DASH_DIRECTIONS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+];
+
+/// Gala script state, reset whenever the arena loop restarts
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct GalaEncounter {
+    /// Boss RNG for this loop, seeded on the loop's first roll
+    rng: Option<SplitMix64>,
+    /// Loop time the telegraphed Charge Strike lands, and the tile it aims at
+    charge: Option<(TimeStamp, IVec2)>,
+    /// Loop time of the next Stampede dash
+    next_stampede_dash: Option<TimeStamp>,
+    stampede_left: u8,
+}
+
+/// Boss RNG for one loop of an arena - the run seed alone decides every roll,
+/// so each loop (and any replay carrying the seed) walks the same path
+#[must_use]
+pub fn loop_rng(seed: RngSeed, arena: ArenaName) -> SplitMix64 {
+    SplitMix64::new(seed.0 ^ (u64::from(arena.as_u8()) << 56))
+}
+
+/// The Gala boss with its script state
+type GalaBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut GalaEncounter,
+        &'static mut ScriptCursor,
+        &'static BossPhase,
+        &'static mut GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    With<Boss>,
+>;
+
+/// Runs the Gala rotation against the arena clock
+///
+/// Charge Strike dashes at the furthest hero and leaves a hazard trail behind; from
+/// phase Two, Dynamic Platforms drops random tiles out of the floor; in phase Four,
+/// Stampede chains dashes in random directions.
+pub fn run_gala_encounter(
+    seed: Res<RngSeed>,
+    mut reset_events: EventReader<ArenaReset>,
+    mut arena_q: Query<(Entity, &Arena, &TimelineClock, &mut ArenaOccupancy), With<Playback>>,
+    mut boss_q: GalaBossQuery,
+    hero_q: HeroQuery,
+    mut actions: BossActions,
+) {
+    let resets: Vec<_> = reset_events.read().map(|event| event.arena).collect();
+
+    for (boss, mut encounter, mut cursor, phase, mut position, health, child_of) in
+        boss_q.iter_mut()
+    {
+        let Ok((arena_entity, arena, clock, mut occupancy)) = arena_q.get_mut(child_of.parent())
+        else {
+            continue;
+        };
+        if resets.contains(&arena.0) {
+            clear_hazards(&mut occupancy);
+            *encounter = GalaEncounter::default();
+        }
+        let window = cursor.advance(clock);
+        if health.is_dead() {
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+        let mut rng = encounter
+            .rng
+            .take()
+            .unwrap_or_else(|| loop_rng(*seed, arena.0));
+
+        if window.crossed(HAZARD_TICK.0, HAZARD_TICK.1) {
+            for (hero, tile) in &heroes {
+                if occupancy.terrain(*tile) == TileTerrain::Trail {
+                    actions.strike_area(
+                        boss,
+                        DamageCause::BossAction("Trail Hazard"),
+                        &[(*hero, *tile)],
+                        *tile,
+                        0,
+                        TRAIL_TICK_DAMAGE,
+                    );
+                }
+            }
+        }
+
+        if *phase >= BossPhase::Two && window.crossed(DYNAMIC_PLATFORMS.0, DYNAMIC_PLATFORMS.1) {
+            occupancy.replace_terrain(TileTerrain::Chasm, TileTerrain::Open);
+            for _ in 0..PLATFORM_ROLLS {
+                let tile = IVec2::new(
+                    rng.below(u64::from(GRID_WIDTH)) as i32,
+                    rng.below(u64::from(GRID_HEIGHT)) as i32,
+                );
+                if occupancy.terrain(tile) != TileTerrain::Open
+                    || within(tile, position.0, BOSS_FOOTPRINT_RADIUS)
+                {
+                    continue;
+                }
+                occupancy.set_terrain(tile, TileTerrain::Chasm);
+                actions.strike_area(
+                    boss,
+                    DamageCause::BossAction("Dynamic Platforms"),
+                    &heroes,
+                    tile,
+                    0,
+                    PLATFORM_DROP_DAMAGE,
+                );
+            }
+        }
+
+        // Stampede takes over the boss's movement until its last dash
+        if *phase == BossPhase::Four && window.crossed(STAMPEDE.0, STAMPEDE.1) {
+            encounter.stampede_left = STAMPEDE_DASHES;
+            encounter.next_stampede_dash = Some(window.to);
+            encounter.charge = None;
+        }
+        if let Some(dash_at) = encounter.next_stampede_dash
+            && window.contains(dash_at)
+        {
+            let direction = DASH_DIRECTIONS[rng.below(DASH_DIRECTIONS.len() as u64) as usize];
+            dash(
+                boss,
+                &mut position.0,
+                direction,
+                STAMPEDE_RANGE,
+                &mut occupancy,
+                &heroes,
+                &mut actions.damage,
+            );
+            encounter.stampede_left -= 1;
+            encounter.next_stampede_dash = (encounter.stampede_left > 0)
+                .then(|| TimeStamp::new(window.to.as_secs() + STAMPEDE_GAP_SECS));
+        }
+
+        if encounter.stampede_left == 0 && window.crossed(CHARGE_STRIKE.0, CHARGE_STRIKE.1) {
+            encounter.charge = furthest_hero(position.0, &heroes).map(|target| {
+                (
+                    TimeStamp::new(window.to.as_secs() + CHARGE_TELEGRAPH_SECS),
+                    target,
+                )
+            });
+        }
+        if let Some((charge_at, target)) = encounter.charge
+            && window.contains(charge_at)
+        {
+            let delta = target - position.0;
+            dash(
+                boss,
+                &mut position.0,
+                delta.signum(),
+                delta.abs().max_element().min(CHARGE_RANGE),
+                &mut occupancy,
+                &heroes,
+                &mut actions.damage,
+            );
+            encounter.charge = None;
+        }
+
+        encounter.rng = Some(rng);
+    }
+}
+
+/// Hero furthest from the boss, ties broken by tile
+pub fn furthest_hero(from: IVec2, heroes: &[(Entity, IVec2)]) -> Option<IVec2> {
+    heroes
+        .iter()
+        .map(|(_, tile)| *tile)
+        .max_by_key(|tile| ((*tile - from).length_squared(), tile.x, tile.y))
+}
+
+/// Dashes the boss up to `range` tiles along `direction`, stopping before any step that
+/// would put part of its body on terrain it cannot cross, and backing off until its body
+/// fits clear of other units; tiles it leaves behind turn into Trail and heroes beside
+/// the path are hit
+pub fn dash(
+    boss: Entity,
+    position: &mut IVec2,
+    direction: IVec2,
+    range: i32,
+    occupancy: &mut ArenaOccupancy,
+    heroes: &[(Entity, IVec2)],
+    damage: &mut EventWriter<DamageEvent>,
+) {
+    let mut path = vec![*position];
+    for _ in 0..range {
+        let next = path[path.len() - 1] + direction;
+        // Off-grid tiles read as Wall, so this also keeps the body inside the arena
+        if footprint(next, BOSS_FOOTPRINT_RADIUS)
+            .any(|tile| occupancy.terrain(tile).blocks_movement())
+        {
+            break;
+        }
+        path.push(next);
+    }
+    while path.len() > 1
+        && occupancy.is_footprint_blocked(path[path.len() - 1], BOSS_FOOTPRINT_RADIUS, boss)
+    {
+        path.pop();
+    }
+
+    for tile in &path[..path.len() - 1] {
+        if occupancy.terrain(*tile) == TileTerrain::Open {
+            occupancy.set_terrain(*tile, TileTerrain::Trail);
+        }
+    }
+    for (hero, tile) in heroes {
+        if path
+            .iter()
+            .any(|step| within(*tile, *step, BOSS_FOOTPRINT_RADIUS))
+        {
+            damage.write(DamageEvent {
+                source: boss,
+                target: *hero,
+                amount: DASH_DAMAGE,
+                cause: DamageCause::BossAction("Charge Strike"),
+            });
+        }
+    }
+    *position = path[path.len() - 1];
+}
+
+fn clear_hazards(occupancy: &mut ArenaOccupancy) {
+    occupancy.replace_terrain(TileTerrain::Trail, TileTerrain::Open);
+    occupancy.replace_terrain(TileTerrain::Chasm, TileTerrain::Open);
+}
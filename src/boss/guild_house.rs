@@ -0,0 +1,445 @@
+//! GuildHouse: The Guild Master - the capstone fight that borrows a signature from
+//! every other arena's boss
+use crate::ability::{AbilityEffect, AbilityType, GuildMasterAbility};
+use crate::arena::{
+    Arena, ArenaName, ArenaOccupancy, BOSS_FOOTPRINT_RADIUS, TileTerrain, tile_to_local_translation,
+};
+use crate::boss::{
+    BossActions, Decoy, HeroQuery, Rotation, ScriptCursor, TreasurePile, dash, furthest_hero,
+    living_heroes, within,
+};
+use crate::character::Boss;
+use crate::combat::{CombatantDied, DamageCause, HealEvent, Health};
+use crate::guild::GuildError;
+use crate::recording::Playback;
+use crate::status_effect::{ApplyStatusEffect, StatusEffectKind};
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Corners Shadow Fade slips away to
+const FADE_SPOTS: [IVec2; 4] = [
+    IVec2::new(10, 6),
+    IVec2::new(56, 6),
+    IVec2::new(10, 24),
+    IVec2::new(56, 24),
+];
+/// Fixed Treasure Bait spots
+const BAIT_SPOTS: [IVec2; 3] = [IVec2::new(20, 15), IVec2::new(46, 15), IVec2::new(33, 24)];
+
+const SIGNATURE: Rotation = (3.0, 5.0);
+const SEALED_SHIELD: Rotation = (1.0, 1.0);
+const HAZARD_TICK: Rotation = (1.0, 1.0);
+const BOSS_STEP: Rotation = (2.0, 2.0);
+
+/// The boss holds its ground once a hero is this close
+const MELEE_RANGE: i32 = BOSS_FOOTPRINT_RADIUS + 1;
+const ACID_BURST_RADIUS: i32 = 3;
+const CHARGE_RANGE: i32 = 12;
+/// Damage to anyone standing on a charge trail, per hazard tick
+const TRAIL_TICK_DAMAGE: f32 = 30.0;
+const BAIT_RADIUS: i32 = 2;
+const BAIT_DAMAGE: f32 = 300.0;
+
+/// Guild Master script marker - the rotation is keyed to the loop clock alone
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct GuildHouseEncounter;
+
+/// Signature abilities the Guild Master borrows, one from each class boss
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signature {
+    AcidBurst,
+    WebSnare,
+    ArmorPlating,
+    ChargeStrike,
+    WitheringTouch,
+    TreasureBait,
+    ShadowFade,
+    RegenerationField,
+}
+
+impl Signature {
+    /// Cast order - the rotation starts over after the last one
+    pub const ROTATION: [Self; 8] = [
+        Self::AcidBurst,
+        Self::WebSnare,
+        Self::ArmorPlating,
+        Self::ChargeStrike,
+        Self::WitheringTouch,
+        Self::TreasureBait,
+        Self::ShadowFade,
+        Self::RegenerationField,
+    ];
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Arena whose boss the signature is borrowed from
+    #[must_use]
+    pub const fn borrowed_from(self) -> ArenaName {
+        match self {
+            Self::AcidBurst => ArenaName::Crucible,
+            Self::WebSnare => ArenaName::Labyrinth,
+            Self::ArmorPlating => ArenaName::Bastion,
+            Self::ChargeStrike => ArenaName::Gala,
+            Self::WitheringTouch => ArenaName::Sanctum,
+            Self::TreasureBait => ArenaName::Casino,
+            Self::ShadowFade => ArenaName::Pawnshop,
+            Self::RegenerationField => ArenaName::Mountain,
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Guild Master ability the signature is cast through - Decree strikes, Rally
+    /// sustains the boss, Levy taxes a hero and Muster reshapes the field
+    #[must_use]
+    pub const fn ability(self) -> GuildMasterAbility {
+        match self {
+            Self::AcidBurst | Self::ChargeStrike => GuildMasterAbility::Decree,
+            Self::ArmorPlating | Self::RegenerationField => GuildMasterAbility::Rally,
+            Self::WitheringTouch | Self::TreasureBait => GuildMasterAbility::Levy,
+            Self::WebSnare | Self::ShadowFade => GuildMasterAbility::Muster,
+        }
+    }
+
+    /// Damage or healing of the ability the signature is cast through
+    fn amount(self) -> f32 {
+        match AbilityType::GuildMaster(self.ability()).effect() {
+            AbilityEffect::Damage(amount) | AbilityEffect::Heal(amount) => amount,
+            AbilityEffect::Utility => 0.0,
+        }
+    }
+
+    /// Signature cast at `seconds` into the loop, on the cast's own tick
+    fn cast_at(seconds: f32) -> Self {
+        let cast = ((seconds - SIGNATURE.0) / SIGNATURE.1).floor() as usize;
+        Self::ROTATION[cast % Self::ROTATION.len()]
+    }
+}
+
+/// Arenas whose boss has fallen, persisted between sessions
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ArenaClears([bool; 9]);
+
+impl ArenaClears {
+    /// Path of the clears save, next to the guild roster
+    pub const SAVE_PATH: &'static str = "saves/arena.clears";
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GuildError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GuildError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, arena: ArenaName) {
+        self.0[arena.as_u8() as usize] = true;
+    }
+
+    #[must_use]
+    pub fn is_cleared(&self, arena: ArenaName) -> bool {
+        self.0[arena.as_u8() as usize]
+    }
+
+    /// Whether every other arena has been cleared, unsealing the Guild Master
+    #[must_use]
+    pub fn unseals_guild_master(&self) -> bool {
+        ArenaName::ALL_ARENAS
+            .into_iter()
+            .filter(|arena| *arena != ArenaName::GuildHouse)
+            .all(|arena| self.is_cleared(arena))
+    }
+}
+
+/// File format: one `cleared <arena>` line per cleared arena, `#` comments
+impl Display for ArenaClears {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for arena in ArenaName::ALL_ARENAS {
+            if self.is_cleared(arena) {
+                writeln!(f, "cleared {arena:?}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ArenaClears {
+    type Err = GuildError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut clears = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = |message: String| GuildError::Malformed {
+                line: index + 1,
+                message,
+            };
+            let Some(arena) = line.strip_prefix("cleared ") else {
+                return Err(malformed(format!(
+                    "expected `cleared <arena>`, got `{line}`"
+                )));
+            };
+            let arena = arena
+                .trim()
+                .parse()
+                .map_err(|error| malformed(format!("{error}")))?;
+            clears.record(arena);
+        }
+        Ok(clears)
+    }
+}
+
+/// Remembers every arena whose boss falls
+pub fn record_arena_clears(
+    mut death_events: EventReader<CombatantDied>,
+    boss_q: Query<&ChildOf, (With<Boss>, Without<Decoy>)>,
+    arena_q: Query<&Arena>,
+    mut clears: ResMut<ArenaClears>,
+) {
+    for event in death_events.read() {
+        let Some(arena) = boss_q
+            .get(event.entity)
+            .ok()
+            .and_then(|child_of| arena_q.get(child_of.parent()).ok())
+        else {
+            continue;
+        };
+        if clears.is_cleared(arena.0) {
+            continue;
+        }
+        clears.record(arena.0);
+        if clears.unseals_guild_master() {
+            info!("Every arena is cleared - the Guild Master awaits in the GuildHouse");
+        }
+    }
+}
+
+/// The Guild Master with its script state
+type GuildHouseBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut ScriptCursor,
+        &'static mut GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    (With<Boss>, With<GuildHouseEncounter>),
+>;
+
+/// Runs the GuildHouse rotation against the arena clock
+///
+/// Until every other arena is cleared the Guild Master stays sealed behind a shield.
+/// Once unsealed it walks into melee and casts one borrowed signature every few
+/// seconds, working through the eight class bosses in a fixed order. Each signature
+/// goes out through one of its own Guild Master abilities, which sets its damage or
+/// healing.
+pub fn run_guild_house_encounter(
+    mut commands: Commands,
+    clears: Res<ArenaClears>,
+    mut arena_q: Query<(Entity, &Arena, &TimelineClock, &mut ArenaOccupancy), With<Playback>>,
+    mut boss_q: GuildHouseBossQuery,
+    hero_q: HeroQuery,
+    pile_q: Query<(Entity, &TreasurePile, &GridPositionComponent, &ChildOf), Without<Boss>>,
+    mut actions: BossActions,
+) {
+    for (boss, mut cursor, mut position, health, child_of) in boss_q.iter_mut() {
+        let Ok((arena_entity, arena, clock, mut occupancy)) = arena_q.get_mut(child_of.parent())
+        else {
+            continue;
+        };
+        let window = cursor.advance(clock);
+        // A fresh loop starts on clean ground
+        if window.from == TimeStamp::ZERO {
+            occupancy.replace_terrain(TileTerrain::Web, TileTerrain::Open);
+            occupancy.replace_terrain(TileTerrain::Trail, TileTerrain::Open);
+        }
+        if health.is_dead() {
+            continue;
+        }
+        if !clears.unseals_guild_master() {
+            if window.crossed(SEALED_SHIELD.0, SEALED_SHIELD.1) {
+                actions.effects.write(ApplyStatusEffect {
+                    target: boss,
+                    source: boss,
+                    kind: StatusEffectKind::Shielded,
+                });
+            }
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+        let piles: Vec<_> = pile_q
+            .iter()
+            .filter(|(.., parent)| parent.parent() == arena_entity)
+            .collect();
+
+        if window.crossed(HAZARD_TICK.0, HAZARD_TICK.1) {
+            for (hero, tile) in &heroes {
+                if occupancy.terrain(*tile) == TileTerrain::Trail {
+                    actions.strike_area(
+                        boss,
+                        DamageCause::BossAction("Trail Hazard"),
+                        &[(*hero, *tile)],
+                        *tile,
+                        0,
+                        TRAIL_TICK_DAMAGE,
+                    );
+                }
+            }
+        }
+        for (pile, treasure, tile, _) in &piles {
+            if treasure.detonates_in(window) {
+                actions.strike_area(
+                    boss,
+                    DamageCause::BossAction("Treasure Bait"),
+                    &heroes,
+                    tile.0,
+                    BAIT_RADIUS,
+                    BAIT_DAMAGE,
+                );
+                commands.entity(*pile).despawn();
+            }
+        }
+
+        if window.crossed(SIGNATURE.0, SIGNATURE.1) {
+            let signature = Signature::cast_at(window.to.as_secs());
+            info!(
+                "The Guild Master casts {signature:?} through {}, borrowed from the {}",
+                AbilityType::GuildMaster(signature.ability()),
+                signature.borrowed_from()
+            );
+            let cause = DamageCause::Ability(AbilityType::GuildMaster(signature.ability()));
+            match signature {
+                Signature::AcidBurst => {
+                    actions.strike_area(
+                        boss,
+                        cause,
+                        &heroes,
+                        position.0,
+                        ACID_BURST_RADIUS,
+                        signature.amount(),
+                    );
+                }
+                Signature::WebSnare => {
+                    if let Some(target) = furthest_hero(position.0, &heroes) {
+                        for y in -1..=1 {
+                            for x in -1..=1 {
+                                let tile = target + IVec2::new(x, y);
+                                if occupancy.terrain(tile) == TileTerrain::Open {
+                                    occupancy.set_terrain(tile, TileTerrain::Web);
+                                }
+                            }
+                        }
+                    }
+                }
+                Signature::ArmorPlating => {
+                    actions.effects.write(ApplyStatusEffect {
+                        target: boss,
+                        source: boss,
+                        kind: StatusEffectKind::ArmorPlating,
+                    });
+                }
+                Signature::ChargeStrike => {
+                    if let Some(target) = furthest_hero(position.0, &heroes) {
+                        let delta = target - position.0;
+                        dash(
+                            boss,
+                            &mut position.0,
+                            delta.signum(),
+                            delta.abs().max_element().min(CHARGE_RANGE),
+                            &mut occupancy,
+                            &heroes,
+                            &mut actions.damage,
+                        );
+                    }
+                }
+                Signature::WitheringTouch => {
+                    if let Some((hero, tile)) = heroes.iter().min_by_key(|(_, tile)| {
+                        ((*tile - position.0).length_squared(), tile.x, tile.y)
+                    }) {
+                        actions.strike_area(
+                            boss,
+                            cause,
+                            &[(*hero, *tile)],
+                            *tile,
+                            0,
+                            signature.amount(),
+                        );
+                        actions.effects.write(ApplyStatusEffect {
+                            target: *hero,
+                            source: boss,
+                            kind: StatusEffectKind::WitheringTouch,
+                        });
+                    }
+                }
+                Signature::TreasureBait => {
+                    for tile in BAIT_SPOTS {
+                        if piles.iter().all(|(.., held, _)| held.0 != tile) {
+                            commands.spawn((
+                                TreasurePile::bait(),
+                                GridPositionComponent(tile),
+                                Transform::from_translation(tile_to_local_translation(
+                                    tile, 0.0625,
+                                )),
+                                ChildOf(arena_entity),
+                                Name::new("Treasure Pile"),
+                            ));
+                        }
+                    }
+                }
+                Signature::ShadowFade => {
+                    if let Some(spot) = furthest_spot(&heroes) {
+                        position.0 = spot;
+                    }
+                }
+                Signature::RegenerationField => {
+                    actions.heals.write(HealEvent {
+                        source: boss,
+                        target: boss,
+                        amount: signature.amount(),
+                    });
+                }
+            }
+        }
+
+        if window.crossed(BOSS_STEP.0, BOSS_STEP.1)
+            && let Some((_, target)) = heroes
+                .iter()
+                .min_by_key(|(_, tile)| ((*tile - position.0).length_squared(), tile.x, tile.y))
+            && !within(*target, position.0, MELEE_RANGE)
+        {
+            actions.step_toward(boss, arena.0, position.0, *target);
+        }
+    }
+}
+
+/// Fade spot whose closest hero is furthest away, skipping any a hero stands on -
+/// ties broken by tile
+fn furthest_spot(heroes: &[(Entity, IVec2)]) -> Option<IVec2> {
+    FADE_SPOTS
+        .into_iter()
+        .filter(|spot| {
+            heroes
+                .iter()
+                .all(|(_, tile)| !within(*tile, *spot, BOSS_FOOTPRINT_RADIUS))
+        })
+        .max_by_key(|spot| {
+            let closest = heroes
+                .iter()
+                .map(|(_, tile)| (*tile - *spot).length_squared())
+                .min()
+                .unwrap_or(0);
+            (closest, -spot.x, -spot.y)
+        })
+}
@@ -0,0 +1,161 @@
+mod bastion;
+mod casino;
+mod crucible;
+mod encounter;
+mod gala;
+mod guild_house;
+mod mountain;
+mod pawnshop;
+mod sanctum;
+
+pub use bastion::*;
+pub use casino::*;
+pub use crucible::*;
+pub use encounter::*;
+pub use gala::*;
+pub use guild_house::*;
+pub use mountain::*;
+pub use pawnshop::*;
+pub use sanctum::*;
+
+use crate::arena::{Arena, ArenaName, ArenaReset, MoveRequest};
+use crate::character::Boss;
+use crate::character::Interact;
+use crate::combat::{CombatantDied, DamageEvent, HealEvent, Health, apply_damage};
+use crate::difficulty::Difficulty;
+use crate::minion::SpawnMinion;
+use crate::rng::RngSeed;
+use crate::status_effect::ApplyStatusEffect;
+use crate::timeline::{emit_arena_resets, replay_ghost_timelines};
+use bevy::prelude::*;
+
+/// Starting health for every arena boss on Normal difficulty
+pub const BOSS_MAX_HEALTH: f32 = 10_000.0;
+
+/// Revision of boss stats and scripts - bump on any change that alters how a
+/// recorded strategy plays out, so stale replays are rejected instead of diverging
+pub const BOSS_DEFINITION_VERSION: u32 = 10;
+
+/// Encounter phase - every boss fight in BOSS_FIGHTS.md runs through four phases
+/// RULE 26 COMPLIANCE: One state component per state machine
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub enum BossPhase {
+    #[default]
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+impl BossPhase {
+    /// Health fractions at which each later phase begins (Two, Three, Four)
+    pub const THRESHOLDS: [f32; 3] = [0.75, 0.5, 0.25];
+
+    /// Phase a boss should be in for the given remaining health fraction
+    #[must_use]
+    pub fn from_health_fraction(fraction: f32) -> Self {
+        match fraction {
+            f if f > Self::THRESHOLDS[0] => Self::One,
+            f if f > Self::THRESHOLDS[1] => Self::Two,
+            f if f > Self::THRESHOLDS[2] => Self::Three,
+            _ => Self::Four,
+        }
+    }
+}
+
+/// Event fired when a boss crosses a phase threshold
+#[derive(Event, Debug, Clone)]
+pub struct BossPhaseChanged {
+    pub boss: Entity,
+    pub arena: ArenaName,
+}
+
+/// Bosses whose health changed since the phase check last ran
+type WoundedBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Health,
+        &'static mut BossPhase,
+        &'static ChildOf,
+    ),
+    (With<Boss>, Changed<Health>),
+>;
+
+/// Moves bosses to the phase matching their health, up to the last phase their
+/// arena's difficulty unlocks, and announces the transition
+pub fn update_boss_phase(
+    mut boss_q: WoundedBossQuery,
+    arena_q: Query<(&Arena, &Difficulty)>,
+    mut phase_events: EventWriter<BossPhaseChanged>,
+) {
+    for (boss_entity, health, mut phase, child_of) in boss_q.iter_mut() {
+        let Ok((arena, difficulty)) = arena_q.get(child_of.parent()) else {
+            continue;
+        };
+        let next = BossPhase::from_health_fraction(health.fraction()).min(difficulty.final_phase());
+        if next == *phase {
+            continue;
+        }
+
+        phase_events.write(BossPhaseChanged {
+            boss: boss_entity,
+            arena: arena.0,
+        });
+        info!("Boss in {} entered phase {:?}", arena.0, next);
+        *phase = next;
+    }
+}
+
+/// Plugin for boss state shared by every encounter
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RngSeed>()
+            .init_resource::<ArenaClears>()
+            .add_event::<BossPhaseChanged>()
+            .add_event::<ArenaReset>()
+            .add_event::<DamageEvent>()
+            .add_event::<CombatantDied>()
+            .add_event::<ApplyStatusEffect>()
+            .add_event::<MoveRequest>()
+            .add_event::<HealEvent>()
+            .add_event::<Interact>()
+            .add_event::<SpawnMinion>()
+            .add_systems(
+                Update,
+                (
+                    update_boss_phase,
+                    attach_encounters,
+                    (
+                        return_bosses_home,
+                        run_bastion_encounter,
+                        despawn_treasure_on_arena_reset,
+                        run_casino_encounter,
+                        run_crucible_encounter,
+                        run_gala_encounter,
+                        run_guild_house_encounter,
+                        run_mountain_encounter,
+                        run_pawnshop_encounter,
+                        mirror_decoys,
+                        run_sanctum_encounter,
+                    )
+                        .chain()
+                        .after(emit_arena_resets),
+                    burst_infested_minions.after(apply_damage),
+                    record_arena_clears.after(apply_damage),
+                    collect_treasure
+                        .after(replay_ghost_timelines)
+                        .before(run_casino_encounter),
+                    harvest_fertile_tiles
+                        .after(replay_ghost_timelines)
+                        .before(run_mountain_encounter),
+                ),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
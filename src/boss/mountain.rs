@@ -0,0 +1,230 @@
+//! Mountain: The Forager - the Malygos-inspired fertile-ground fight from _docs/BOSS_FIGHTS.md
+use crate::arena::{Arena, ArenaOccupancy, ArenaReset, TileTerrain};
+use crate::boss::{BossActions, BossPhase, HeroQuery, Rotation, ScriptCursor, living_heroes};
+use crate::character::{Boss, Character, Interact};
+use crate::combat::{DamageCause, HealEvent, Health};
+use crate::minion::{MinionKind, SpawnMinion};
+use crate::recording::Playback;
+use crate::status_effect::{ApplyStatusEffect, StatusEffectKind};
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::prelude::*;
+
+/// Seedlings plots in planting order, one per cast - each covers the 3x3 block
+/// around its centre, so the ground greens the same way every loop
+const SEED_PLOTS: [IVec2; 8] = [
+    IVec2::new(26, 12),
+    IVec2::new(40, 8),
+    IVec2::new(20, 20),
+    IVec2::new(46, 22),
+    IVec2::new(32, 24),
+    IVec2::new(14, 6),
+    IVec2::new(52, 6),
+    IVec2::new(32, 17),
+];
+
+const SEEDLINGS: Rotation = (4.0, 10.0);
+const REGENERATION_FIELD: Rotation = (1.0, 1.0);
+const HAZARD_TICK: Rotation = (1.0, 1.0);
+const FERTILE_BLOOM: Rotation = (12.0, 20.0);
+const VINE_SHIELD: Rotation = (16.0, 20.0);
+const RAPID_BLOOM: Rotation = (30.0, 30.0);
+const BOSS_STEP: Rotation = (1.5, 1.5);
+
+/// Vine Shield summons a vine this far to either side of the boss
+const VINE_OFFSETS: [IVec2; 2] = [IVec2::new(0, -3), IVec2::new(0, 3)];
+/// Seconds a seedling takes to ripen into fertile ground
+const SEEDLING_GROWTH_SECS: f32 = 10.0;
+/// Share of its maximum health the boss regains per second on fertile ground
+const REGENERATION_FRACTION: f32 = 0.05;
+/// Damage to anyone standing in brambles, per hazard tick
+const BRAMBLE_TICK_DAMAGE: f32 = 40.0;
+
+/// Mountain script state, reset whenever the arena loop restarts
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct MountainEncounter {
+    /// Planted plots and the loop time each ripens
+    seedlings: Vec<(IVec2, TimeStamp)>,
+}
+
+/// Heroes and ghosts interacting beside fertile ground harvest it, denying the
+/// boss its Regeneration Field there
+pub fn harvest_fertile_tiles(
+    mut interactions: EventReader<Interact>,
+    actor_q: Query<(&GridPositionComponent, &ChildOf, &Health), With<Character>>,
+    mut arena_q: Query<&mut ArenaOccupancy>,
+) {
+    for event in interactions.read() {
+        let Ok((actor, arena, health)) = actor_q.get(event.actor) else {
+            continue;
+        };
+        if health.is_dead() {
+            continue;
+        }
+        let Ok(mut occupancy) = arena_q.get_mut(arena.parent()) else {
+            continue;
+        };
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let tile = actor.0 + IVec2::new(x, y);
+                if occupancy.terrain(tile) == TileTerrain::Fertile {
+                    occupancy.set_terrain(tile, TileTerrain::Open);
+                }
+            }
+        }
+    }
+}
+
+/// The Mountain boss with its script state
+type MountainBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut MountainEncounter,
+        &'static mut ScriptCursor,
+        &'static BossPhase,
+        &'static GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    With<Boss>,
+>;
+
+/// Runs the Mountain rotation against the arena clock
+///
+/// Seedlings plants a fixed plot on every cast that ripens into fertile ground ten
+/// loop seconds later, and the boss walks from fertile patch to patch to regenerate
+/// on it. From phase Two, Fertile Bloom ripens every seedling at once and Vine Shield
+/// halves the damage the boss takes while it summons vines beside it; in phase Four,
+/// Rapid Bloom overgrows all fertile ground into brambles.
+pub fn run_mountain_encounter(
+    mut reset_events: EventReader<ArenaReset>,
+    mut arena_q: Query<(Entity, &Arena, &TimelineClock, &mut ArenaOccupancy), With<Playback>>,
+    mut boss_q: MountainBossQuery,
+    hero_q: HeroQuery,
+    mut actions: BossActions,
+) {
+    let resets: Vec<_> = reset_events.read().map(|event| event.arena).collect();
+
+    for (boss, mut encounter, mut cursor, phase, position, health, child_of) in boss_q.iter_mut() {
+        let Ok((arena_entity, arena, clock, mut occupancy)) = arena_q.get_mut(child_of.parent())
+        else {
+            continue;
+        };
+        if resets.contains(&arena.0) {
+            clear_growth(&mut occupancy);
+            *encounter = MountainEncounter::default();
+        }
+        let window = cursor.advance(clock);
+        if health.is_dead() {
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+
+        if window.crossed(SEEDLINGS.0, SEEDLINGS.1)
+            && let Some(plot) = seedlings_planted(window.to.as_secs())
+                .checked_sub(1)
+                .and_then(|cast| SEED_PLOTS.get(cast).copied())
+        {
+            for y in -1..=1 {
+                for x in -1..=1 {
+                    let tile = plot + IVec2::new(x, y);
+                    if occupancy.terrain(tile) == TileTerrain::Open {
+                        occupancy.set_terrain(tile, TileTerrain::Seedling);
+                    }
+                }
+            }
+            let ripens_at = TimeStamp::new(window.to.as_secs() + SEEDLING_GROWTH_SECS);
+            encounter.seedlings.push((plot, ripens_at));
+        }
+
+        let bloom = *phase >= BossPhase::Two && window.crossed(FERTILE_BLOOM.0, FERTILE_BLOOM.1);
+        encounter.seedlings.retain(|(plot, ripens_at)| {
+            let ripe = bloom || window.contains(*ripens_at);
+            if ripe {
+                ripen(&mut occupancy, *plot);
+            }
+            !ripe
+        });
+
+        if *phase >= BossPhase::Two && window.crossed(VINE_SHIELD.0, VINE_SHIELD.1) {
+            actions.effects.write(ApplyStatusEffect {
+                target: boss,
+                source: boss,
+                kind: StatusEffectKind::VineShield,
+            });
+            for offset in VINE_OFFSETS {
+                actions.summons.write(SpawnMinion {
+                    arena: arena.0,
+                    kind: MinionKind::Vine,
+                    tile: position.0 + offset,
+                });
+            }
+        }
+
+        if *phase == BossPhase::Four && window.crossed(RAPID_BLOOM.0, RAPID_BLOOM.1) {
+            occupancy.replace_terrain(TileTerrain::Fertile, TileTerrain::Bramble);
+        }
+
+        if window.crossed(HAZARD_TICK.0, HAZARD_TICK.1) {
+            for (hero, tile) in &heroes {
+                if occupancy.terrain(*tile) == TileTerrain::Bramble {
+                    actions.strike_area(
+                        boss,
+                        DamageCause::BossAction("Rapid Bloom"),
+                        &[(*hero, *tile)],
+                        *tile,
+                        0,
+                        BRAMBLE_TICK_DAMAGE,
+                    );
+                }
+            }
+        }
+
+        let on_fertile = occupancy.terrain(position.0) == TileTerrain::Fertile;
+        if on_fertile && window.crossed(REGENERATION_FIELD.0, REGENERATION_FIELD.1) {
+            actions.heals.write(HealEvent {
+                source: boss,
+                target: boss,
+                amount: health.max * REGENERATION_FRACTION,
+            });
+        }
+
+        // Walks to the closest fertile ground to keep Regeneration Field going
+        if !on_fertile
+            && window.crossed(BOSS_STEP.0, BOSS_STEP.1)
+            && let Some(target) = occupancy
+                .tiles_with(TileTerrain::Fertile)
+                .min_by_key(|tile| ((*tile - position.0).length_squared(), tile.x, tile.y))
+        {
+            actions.step_toward(boss, arena.0, position.0, target);
+        }
+    }
+}
+
+/// Number of Seedlings casts by `seconds` into the loop
+fn seedlings_planted(seconds: f32) -> usize {
+    if seconds < SEEDLINGS.0 {
+        return 0;
+    }
+    ((seconds - SEEDLINGS.0) / SEEDLINGS.1).floor() as usize + 1
+}
+
+/// Turns the seedlings still standing on a plot into fertile ground
+fn ripen(occupancy: &mut ArenaOccupancy, plot: IVec2) {
+    for y in -1..=1 {
+        for x in -1..=1 {
+            let tile = plot + IVec2::new(x, y);
+            if occupancy.terrain(tile) == TileTerrain::Seedling {
+                occupancy.set_terrain(tile, TileTerrain::Fertile);
+            }
+        }
+    }
+}
+
+fn clear_growth(occupancy: &mut ArenaOccupancy) {
+    occupancy.replace_terrain(TileTerrain::Seedling, TileTerrain::Open);
+    occupancy.replace_terrain(TileTerrain::Fertile, TileTerrain::Open);
+    occupancy.replace_terrain(TileTerrain::Bramble, TileTerrain::Open);
+}
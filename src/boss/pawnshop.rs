@@ -0,0 +1,309 @@
+//! Pawnshop: The Thief - the Shade of Aran-inspired shadow fight from _docs/BOSS_FIGHTS.md
+use crate::arena::{
+    Arena, ArenaOccupancy, BOSS_FOOTPRINT_RADIUS, GRID_HEIGHT, GRID_WIDTH, TileTerrain,
+    tile_to_local_translation,
+};
+use crate::boss::{
+    BossActions, BossPhase, HeroQuery, Rotation, ScriptCursor, TreasurePile, living_heroes,
+    loop_rng, within,
+};
+use crate::character::Boss;
+use crate::combat::{DamageCause, Health};
+use crate::minion::{Minion, MinionBehavior, MinionKind};
+use crate::recording::Playback;
+use crate::rng::{RngSeed, SplitMix64};
+use crate::status_effect::{ApplyStatusEffect, StatusEffectKind};
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+use bevy::prelude::*;
+
+/// Centres of the shadowed patches the boss fades between
+const SHADOW_SPOTS: [IVec2; 4] = [
+    IVec2::new(12, 8),
+    IVec2::new(52, 8),
+    IVec2::new(12, 22),
+    IVec2::new(52, 22),
+];
+/// Shadowed patches reach this far (square) around their centre
+const SHADOW_RADIUS: i32 = 2;
+
+/// Treasure spots Dark Refuge teleports to and Treasure Bait is laid on
+const TREASURE_SPOTS: [IVec2; 4] = [
+    IVec2::new(32, 4),
+    IVec2::new(6, 15),
+    IVec2::new(58, 15),
+    IVec2::new(32, 26),
+];
+
+const SHADOW_CLOAK: Rotation = (1.0, 1.0);
+const SHADOW_FADE: Rotation = (6.0, 12.0);
+const TREASURE_BAIT: Rotation = (10.0, 20.0);
+const DECOY_STRIKE: Rotation = (8.0, 15.0);
+const DARK_REFUGE: Rotation = (15.0, 30.0);
+const BOSS_STEP: Rotation = (1.5, 1.5);
+
+/// Seconds a decoy mimics the boss before vanishing
+const DECOY_SECS: f32 = 5.0;
+/// Tiles between the boss and its decoy, toward the middle of the arena
+const DECOY_SPACING: i32 = 8;
+const DECOY_STRIKE_RADIUS: i32 = 2;
+const DECOY_STRIKE_DAMAGE: f32 = 150.0;
+const BAIT_RADIUS: i32 = 2;
+const BAIT_DAMAGE: f32 = 300.0;
+
+/// Pawnshop script state
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct PawnshopEncounter {
+    /// Dark Refuge's treasure-spot picks, reseeded from the run seed every loop
+    rng: Option<SplitMix64>,
+}
+
+/// Fake boss left by Decoy Strike
+///
+/// Decoys carry the `Boss` marker so they pass for the real thing; systems that
+/// must only touch real bosses (targeting, rewards, phases) filter `Without<Decoy>`.
+#[derive(Component, Debug, Clone)]
+pub struct Decoy {
+    /// Boss whose movement the decoy copies
+    pub mimics: Entity,
+    /// Where the decoy stands relative to that boss
+    pub offset: IVec2,
+    /// Loop time the decoy appeared
+    pub from: TimeStamp,
+    /// Loop time the decoy vanishes
+    pub until: TimeStamp,
+}
+
+/// The Pawnshop boss with its script state
+type PawnshopBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut PawnshopEncounter,
+        &'static mut ScriptCursor,
+        &'static BossPhase,
+        &'static mut GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    With<Boss>,
+>;
+
+/// Runs the Pawnshop rotation against the arena clock
+///
+/// The boss drifts between shadowed patches, cloaked while it stands in one, and
+/// Shadow Fade moves it to the patch furthest from the heroes. Treasure Bait lays
+/// false piles on the treasure spots. From phase Two, Dark Refuge teleports it to a
+/// random treasure spot behind a shield and Decoy Strike splits off a mimic.
+pub fn run_pawnshop_encounter(
+    mut commands: Commands,
+    seed: Res<RngSeed>,
+    mut arena_q: Query<(Entity, &Arena, &TimelineClock, &mut ArenaOccupancy), With<Playback>>,
+    mut boss_q: PawnshopBossQuery,
+    hero_q: HeroQuery,
+    pile_q: Query<(Entity, &TreasurePile, &GridPositionComponent, &ChildOf), Without<Boss>>,
+    mut actions: BossActions,
+) {
+    for (boss, mut encounter, mut cursor, phase, mut position, health, child_of) in
+        boss_q.iter_mut()
+    {
+        let Ok((arena_entity, arena, clock, mut occupancy)) = arena_q.get_mut(child_of.parent())
+        else {
+            continue;
+        };
+        let window = cursor.advance(clock);
+        // A fresh loop always rolls from the run seed
+        if window.from == TimeStamp::ZERO {
+            encounter.rng = None;
+        }
+        if occupancy.terrain(SHADOW_SPOTS[0]) != TileTerrain::Shadow {
+            lay_shadows(&mut occupancy);
+        }
+        if health.is_dead() {
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+        let mut rng = encounter
+            .rng
+            .take()
+            .unwrap_or_else(|| loop_rng(*seed, arena.0));
+        let piles: Vec<_> = pile_q
+            .iter()
+            .filter(|(.., parent)| parent.parent() == arena_entity)
+            .collect();
+
+        if window.crossed(SHADOW_CLOAK.0, SHADOW_CLOAK.1)
+            && occupancy.terrain(position.0) == TileTerrain::Shadow
+        {
+            actions.effects.write(ApplyStatusEffect {
+                target: boss,
+                source: boss,
+                kind: StatusEffectKind::ShadowCloak,
+            });
+        }
+
+        for (pile, treasure, tile, _) in &piles {
+            if treasure.detonates_in(window) {
+                actions.strike_area(
+                    boss,
+                    DamageCause::BossAction("Treasure Bait"),
+                    &heroes,
+                    tile.0,
+                    BAIT_RADIUS,
+                    BAIT_DAMAGE,
+                );
+                commands.entity(*pile).despawn();
+            }
+        }
+        if window.crossed(TREASURE_BAIT.0, TREASURE_BAIT.1) {
+            for tile in TREASURE_SPOTS {
+                if piles.iter().all(|(.., held, _)| held.0 != tile) {
+                    commands.spawn((
+                        TreasurePile::bait(),
+                        GridPositionComponent(tile),
+                        Transform::from_translation(tile_to_local_translation(tile, 0.0625)),
+                        ChildOf(arena_entity),
+                        Name::new("Treasure Pile"),
+                    ));
+                }
+            }
+        }
+
+        if window.crossed(SHADOW_FADE.0, SHADOW_FADE.1)
+            && let Some(spot) = safest_shadow(position.0, &heroes)
+        {
+            position.0 = spot;
+        }
+
+        if *phase >= BossPhase::Two && window.crossed(DARK_REFUGE.0, DARK_REFUGE.1) {
+            let spot = TREASURE_SPOTS[rng.below(TREASURE_SPOTS.len() as u64) as usize];
+            if heroes
+                .iter()
+                .all(|(_, tile)| !within(*tile, spot, BOSS_FOOTPRINT_RADIUS))
+            {
+                position.0 = spot;
+            }
+            actions.effects.write(ApplyStatusEffect {
+                target: boss,
+                source: boss,
+                kind: StatusEffectKind::Shielded,
+            });
+        }
+
+        if *phase >= BossPhase::Two && window.crossed(DECOY_STRIKE.0, DECOY_STRIKE.1) {
+            let toward_middle = if position.0.x < GRID_WIDTH as i32 / 2 {
+                1
+            } else {
+                -1
+            };
+            let offset = IVec2::new(toward_middle * DECOY_SPACING, 0);
+            let tile = position.0 + offset;
+            let stats = MinionKind::Decoy.stats();
+            commands.spawn((
+                Boss,
+                Decoy {
+                    mimics: boss,
+                    offset,
+                    from: window.to,
+                    until: TimeStamp::new(window.to.as_secs() + DECOY_SECS),
+                },
+                Minion(MinionKind::Decoy),
+                MinionBehavior::Hold,
+                Health::new(stats.max_health),
+                GridPositionComponent(tile),
+                Transform::from_translation(tile_to_local_translation(tile, stats.radius)),
+                ChildOf(arena_entity),
+                Name::new("Decoy"),
+            ));
+            for center in [position.0, tile] {
+                actions.strike_area(
+                    boss,
+                    DamageCause::BossAction("Decoy Strike"),
+                    &heroes,
+                    center,
+                    DECOY_STRIKE_RADIUS,
+                    DECOY_STRIKE_DAMAGE,
+                );
+            }
+        }
+
+        // Slips back into the nearest shadow between teleports
+        if window.crossed(BOSS_STEP.0, BOSS_STEP.1)
+            && occupancy.terrain(position.0) != TileTerrain::Shadow
+            && let Some(spot) = SHADOW_SPOTS
+                .iter()
+                .min_by_key(|spot| ((**spot - position.0).length_squared(), spot.x, spot.y))
+        {
+            actions.step_toward(boss, arena.0, position.0, *spot);
+        }
+
+        encounter.rng = Some(rng);
+    }
+}
+
+/// Keeps every decoy at its offset from the boss it mimics and clears it once
+/// its time is up (or the loop has wrapped past it)
+pub fn mirror_decoys(
+    mut commands: Commands,
+    clock_q: Query<&TimelineClock>,
+    mut decoy_q: Query<(Entity, &Decoy, &mut GridPositionComponent, &ChildOf)>,
+    boss_q: Query<&GridPositionComponent, Without<Decoy>>,
+) {
+    for (entity, decoy, mut position, child_of) in decoy_q.iter_mut() {
+        let Ok(clock) = clock_q.get(child_of.parent()) else {
+            continue;
+        };
+        let now = clock.current();
+        let Ok(boss) = boss_q.get(decoy.mimics) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if now < decoy.from || now >= decoy.until {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let tile = (boss.0 + decoy.offset).clamp(
+            IVec2::splat(BOSS_FOOTPRINT_RADIUS),
+            IVec2::new(GRID_WIDTH as i32, GRID_HEIGHT as i32) - 1 - BOSS_FOOTPRINT_RADIUS,
+        );
+        if position.0 != tile {
+            position.0 = tile;
+        }
+    }
+}
+
+/// Covers each shadow spot's patch of open ground in shadow
+fn lay_shadows(occupancy: &mut ArenaOccupancy) {
+    for spot in SHADOW_SPOTS {
+        for y in -SHADOW_RADIUS..=SHADOW_RADIUS {
+            for x in -SHADOW_RADIUS..=SHADOW_RADIUS {
+                let tile = spot + IVec2::new(x, y);
+                if occupancy.terrain(tile) == TileTerrain::Open {
+                    occupancy.set_terrain(tile, TileTerrain::Shadow);
+                }
+            }
+        }
+    }
+}
+
+/// Shadow spot whose closest hero is furthest away, other than the one the boss
+/// is standing on - ties broken by tile
+fn safest_shadow(from: IVec2, heroes: &[(Entity, IVec2)]) -> Option<IVec2> {
+    SHADOW_SPOTS
+        .into_iter()
+        .filter(|spot| *spot != from)
+        .filter(|spot| {
+            heroes
+                .iter()
+                .all(|(_, tile)| !within(*tile, *spot, BOSS_FOOTPRINT_RADIUS))
+        })
+        .max_by_key(|spot| {
+            let closest = heroes
+                .iter()
+                .map(|(_, tile)| (*tile - *spot).length_squared())
+                .min()
+                .unwrap_or(0);
+            (closest, -spot.x, -spot.y)
+        })
+}
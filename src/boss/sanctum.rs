@@ -0,0 +1,230 @@
+//! Sanctum: The Cardinal - the Cenarius-inspired corruption fight from _docs/BOSS_FIGHTS.md
+use crate::arena::{Arena, ArenaOccupancy, ArenaReset, TileTerrain};
+use crate::boss::{
+    BossActions, BossPhase, HeroQuery, Rotation, ScriptCursor, living_heroes, within,
+};
+use crate::character::Boss;
+use crate::combat::{CombatantDied, DamageCause, Health};
+use crate::minion::{Minion, MinionKind, SpawnMinion};
+use crate::recording::Playback;
+use crate::status_effect::{ApplyStatusEffect, StatusEffectKind};
+use crate::timeline::{GridPositionComponent, TimelineClock};
+use bevy::prelude::*;
+
+/// Rotting Roots seeds corruption at these tiles in order, one per cast, so the
+/// ground rots the same way every loop
+const ROOT_SEEDS: [IVec2; 12] = [
+    IVec2::new(8, 6),
+    IVec2::new(57, 24),
+    IVec2::new(8, 24),
+    IVec2::new(57, 6),
+    IVec2::new(20, 15),
+    IVec2::new(45, 15),
+    IVec2::new(33, 4),
+    IVec2::new(33, 26),
+    IVec2::new(26, 22),
+    IVec2::new(40, 8),
+    IVec2::new(14, 10),
+    IVec2::new(51, 20),
+];
+
+/// Fixed Infested Minions spots
+const INFESTED_SPOTS: [IVec2; 2] = [IVec2::new(20, 6), IVec2::new(46, 24)];
+
+const ROTTING_ROOTS: Rotation = (6.0, 10.0);
+const ROOT_SPREAD: Rotation = (8.0, 8.0);
+/// Decay Zones doubles the spread rate from phase Two
+const DECAY_SPREAD: Rotation = (8.0, 4.0);
+const HAZARD_TICK: Rotation = (1.0, 1.0);
+const WITHERING_TOUCH: Rotation = (4.0, 5.0);
+const INFESTED_MINIONS: Rotation = (12.0, 20.0);
+const CORRUPTED_TIDE: Rotation = (25.0, 20.0);
+const BOSS_STEP: Rotation = (2.0, 2.0);
+
+/// Damage to anyone standing on corrupted ground, per hazard tick
+const CORRUPTION_TICK_DAMAGE: f32 = 25.0;
+/// Reach of the debuffs an Infested minion bursts into when it dies
+const INFESTATION_RADIUS: i32 = 2;
+
+/// Sanctum script marker - the fight's only state is the corruption on the arena floor
+#[derive(Component, Debug, Clone, Default)]
+#[require(ScriptCursor)]
+pub struct SanctumEncounter;
+
+/// The Sanctum boss with its script state
+type SanctumBossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut ScriptCursor,
+        &'static BossPhase,
+        &'static GridPositionComponent,
+        &'static Health,
+        &'static ChildOf,
+    ),
+    (With<Boss>, With<SanctumEncounter>),
+>;
+
+/// Runs the Sanctum rotation against the arena clock
+///
+/// Rotting Roots plants corruption at fixed spots and it creeps one tile outward on
+/// every spread tick (twice as often once Decay Zones starts in phase Two). Withering
+/// Touch stacks healing reduction on the boss's closest hero from phase Two, Infested
+/// Minions join throughout, and in phase Four Corrupted Tide wards the boss once per
+/// living minion.
+pub fn run_sanctum_encounter(
+    mut reset_events: EventReader<ArenaReset>,
+    mut arena_q: Query<(Entity, &Arena, &TimelineClock, &mut ArenaOccupancy), With<Playback>>,
+    mut boss_q: SanctumBossQuery,
+    hero_q: HeroQuery,
+    minion_q: Query<(Entity, &Health, &ChildOf), With<Minion>>,
+    mut actions: BossActions,
+) {
+    let resets: Vec<_> = reset_events.read().map(|event| event.arena).collect();
+
+    for (boss, mut cursor, phase, position, health, child_of) in boss_q.iter_mut() {
+        let Ok((arena_entity, arena, clock, mut occupancy)) = arena_q.get_mut(child_of.parent())
+        else {
+            continue;
+        };
+        if resets.contains(&arena.0) {
+            occupancy.replace_terrain(TileTerrain::Corrupted, TileTerrain::Open);
+        }
+        let window = cursor.advance(clock);
+        if health.is_dead() {
+            continue;
+        }
+        let heroes = living_heroes(&hero_q, arena_entity);
+
+        if window.crossed(HAZARD_TICK.0, HAZARD_TICK.1) {
+            for (hero, tile) in &heroes {
+                if occupancy.terrain(*tile) == TileTerrain::Corrupted {
+                    actions.strike_area(
+                        boss,
+                        DamageCause::BossAction("Rotting Roots"),
+                        &[(*hero, *tile)],
+                        *tile,
+                        0,
+                        CORRUPTION_TICK_DAMAGE,
+                    );
+                }
+            }
+        }
+
+        let spread = if *phase >= BossPhase::Two {
+            DECAY_SPREAD
+        } else {
+            ROOT_SPREAD
+        };
+        if window.crossed(spread.0, spread.1) {
+            spread_corruption(&mut occupancy);
+        }
+        let seeded = roots_seeded(window.to.as_secs());
+        if window.crossed(ROTTING_ROOTS.0, ROTTING_ROOTS.1)
+            && let Some(root) = seeded.checked_sub(1).map(|cast| ROOT_SEEDS[cast])
+        {
+            for y in -1..=1 {
+                for x in -1..=1 {
+                    let tile = root + IVec2::new(x, y);
+                    if occupancy.terrain(tile) == TileTerrain::Open {
+                        occupancy.set_terrain(tile, TileTerrain::Corrupted);
+                    }
+                }
+            }
+        }
+
+        if *phase >= BossPhase::Two
+            && window.crossed(WITHERING_TOUCH.0, WITHERING_TOUCH.1)
+            && let Some((hero, _)) = heroes
+                .iter()
+                .min_by_key(|(_, tile)| ((*tile - position.0).length_squared(), tile.x, tile.y))
+        {
+            actions.effects.write(ApplyStatusEffect {
+                target: *hero,
+                source: boss,
+                kind: StatusEffectKind::WitheringTouch,
+            });
+        }
+
+        if window.crossed(INFESTED_MINIONS.0, INFESTED_MINIONS.1) {
+            for tile in INFESTED_SPOTS {
+                actions.summons.write(SpawnMinion {
+                    arena: arena.0,
+                    kind: MinionKind::Infested,
+                    tile,
+                });
+            }
+        }
+
+        if *phase == BossPhase::Four && window.crossed(CORRUPTED_TIDE.0, CORRUPTED_TIDE.1) {
+            let minions = minion_q
+                .iter()
+                .filter(|(_, health, parent)| parent.parent() == arena_entity && !health.is_dead());
+            for (minion, ..) in minions {
+                actions.effects.write(ApplyStatusEffect {
+                    target: boss,
+                    source: minion,
+                    kind: StatusEffectKind::TideWard,
+                });
+            }
+        }
+
+        // Wanders to the newest root to tend it
+        if window.crossed(BOSS_STEP.0, BOSS_STEP.1)
+            && let Some(root) = seeded.checked_sub(1).map(|cast| ROOT_SEEDS[cast])
+        {
+            actions.step_toward(boss, arena.0, position.0, root);
+        }
+    }
+}
+
+/// Number of Rotting Roots casts made by loop time `seconds`
+fn roots_seeded(seconds: f32) -> usize {
+    if seconds < ROTTING_ROOTS.0 {
+        return 0;
+    }
+    let casts = ((seconds - ROTTING_ROOTS.0) / ROTTING_ROOTS.1).floor() as usize + 1;
+    casts.min(ROOT_SEEDS.len())
+}
+
+/// Grows corruption one tile outward (orthogonally) into open ground
+fn spread_corruption(occupancy: &mut ArenaOccupancy) {
+    let frontier: Vec<IVec2> = occupancy
+        .tiles_with(TileTerrain::Corrupted)
+        .flat_map(|tile| [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|step| tile + step))
+        .filter(|tile| occupancy.terrain(*tile) == TileTerrain::Open)
+        .collect();
+    for tile in frontier {
+        occupancy.set_terrain(tile, TileTerrain::Corrupted);
+    }
+}
+
+/// Infested minions burst into Poison and Withering Touch on nearby heroes as they die
+pub fn burst_infested_minions(
+    mut death_events: EventReader<CombatantDied>,
+    minion_q: Query<(&Minion, &GridPositionComponent, &ChildOf)>,
+    hero_q: HeroQuery,
+    mut effects: EventWriter<ApplyStatusEffect>,
+) {
+    for event in death_events.read() {
+        let Ok((minion, position, child_of)) = minion_q.get(event.entity) else {
+            continue;
+        };
+        if minion.0 != MinionKind::Infested {
+            continue;
+        }
+        for (hero, tile) in living_heroes(&hero_q, child_of.parent()) {
+            if !within(tile, position.0, INFESTATION_RADIUS) {
+                continue;
+            }
+            for kind in [StatusEffectKind::Poison, StatusEffectKind::WitheringTouch] {
+                effects.write(ApplyStatusEffect {
+                    target: hero,
+                    source: event.entity,
+                    kind,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,932 @@
+use super::*;
+use crate::ability::GuildMasterAbility;
+use crate::arena::{
+    ArenaEntities, ArenaOccupancy, CurrentArena, OccupancyPlugin, TileTerrain, create_test_arenas,
+};
+use crate::character::{Character, Interact};
+use crate::combat::{CombatPlugin, DamageCause, DamageEvent, HealEvent};
+use crate::minion::{Minion, MinionKind, spawn_minions};
+use crate::recording::Playback;
+use crate::rng::RngSeed;
+use crate::status_effect::{StatusEffectKind, StatusEffectPlugin, StatusEffects};
+use crate::timeline::{GridPositionComponent, SavedTimeline, TimelineClock, TimelinePlugin};
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+const STEP: Duration = Duration::from_millis(100);
+
+/// One arena's fight at the given tier and boss health fraction, with its clock running
+fn encounter_app(
+    arena: ArenaName,
+    seed: RngSeed,
+    difficulty: Difficulty,
+    health_fraction: f32,
+) -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+        .insert_resource(seed)
+        .add_plugins((
+            TimelinePlugin,
+            OccupancyPlugin,
+            CombatPlugin,
+            StatusEffectPlugin,
+            BossPlugin,
+        ))
+        .add_systems(Update, spawn_minions);
+
+    let world = app.world_mut();
+    let fight = create_test_arenas(world, |_| TimelineClock::default()).get(arena);
+    world.entity_mut(fight).insert((Playback, difficulty));
+    world.insert_resource(CurrentArena(arena));
+
+    let boss = world
+        .spawn((
+            Boss,
+            difficulty.boss_bundle(),
+            GridPositionComponent(IVec2::new(32, 10)),
+            ChildOf(fight),
+        ))
+        .id();
+    let mut health = world.get_mut::<Health>(boss).unwrap();
+    health.current = health.max * health_fraction;
+
+    // First update only primes the clock
+    app.update();
+    (app, fight, boss)
+}
+
+fn spawn_hero(app: &mut App, arena: Entity, tile: IVec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Character,
+            Health::new(1_000.0),
+            GridPositionComponent(tile),
+            ChildOf(arena),
+        ))
+        .id()
+}
+
+fn run_until(app: &mut App, arena: Entity, seconds: f32) {
+    while app
+        .world()
+        .get::<TimelineClock>(arena)
+        .unwrap()
+        .current()
+        .as_secs()
+        < seconds
+    {
+        app.update();
+    }
+}
+
+/// Runs through the loop wrap and on to `seconds` into the next loop
+fn run_into_next_loop(app: &mut App, arena: Entity, seconds: f32) {
+    run_until(app, arena, 119.0);
+    while app
+        .world()
+        .get::<TimelineClock>(arena)
+        .unwrap()
+        .current()
+        .as_secs()
+        >= 119.0
+    {
+        app.update();
+    }
+    run_until(app, arena, seconds);
+}
+
+fn terrain(app: &App, arena: Entity, tile: IVec2) -> TileTerrain {
+    app.world()
+        .get::<ArenaOccupancy>(arena)
+        .unwrap()
+        .terrain(tile)
+}
+
+#[test]
+fn test_unstable_pools_land_on_fixed_tiles_and_burn() {
+    let (mut app, crucible, _) = encounter_app(
+        ArenaName::Crucible,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    let burned = spawn_hero(&mut app, crucible, IVec2::new(11, 5));
+    let safe = spawn_hero(&mut app, crucible, IVec2::new(5, 28));
+
+    run_until(&mut app, crucible, 4.5);
+    assert_eq!(
+        terrain(&app, crucible, IVec2::new(11, 5)),
+        TileTerrain::Open
+    );
+
+    run_until(&mut app, crucible, 6.5);
+    for tile in [IVec2::new(10, 4), IVec2::new(55, 26), IVec2::new(33, 18)] {
+        assert_eq!(terrain(&app, crucible, tile), TileTerrain::Acid);
+    }
+    let health = |hero| app.world().get::<Health>(hero).unwrap().current;
+    assert!(health(burned) < 1_000.0, "standing in acid hurts");
+    assert_eq!(health(safe), 1_000.0);
+}
+
+#[test]
+fn test_reactive_fields_haste_heroes_then_fade_back_to_acid() {
+    // Heroic holds the boss in phase Two at 60% health
+    let (mut app, crucible, _) = encounter_app(
+        ArenaName::Crucible,
+        RngSeed::default(),
+        Difficulty::Heroic,
+        0.6,
+    );
+    let hero = spawn_hero(&mut app, crucible, IVec2::new(54, 4));
+
+    run_until(&mut app, crucible, 15.5);
+    assert_eq!(
+        terrain(&app, crucible, IVec2::new(54, 4)),
+        TileTerrain::Catalyst
+    );
+
+    run_until(&mut app, crucible, 16.5);
+    let effects = app.world().get::<StatusEffects>(hero).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Haste), 1);
+
+    run_until(&mut app, crucible, 25.5);
+    assert_eq!(
+        terrain(&app, crucible, IVec2::new(54, 4)),
+        TileTerrain::Acid
+    );
+}
+
+#[test]
+fn test_catalyst_core_shields_the_boss_in_place() {
+    let (mut app, crucible, boss) = encounter_app(
+        ArenaName::Crucible,
+        RngSeed::default(),
+        Difficulty::Mythic,
+        0.4,
+    );
+    let position = |app: &App| app.world().get::<GridPositionComponent>(boss).unwrap().0;
+
+    run_until(&mut app, crucible, 20.0);
+    assert_ne!(position(&app), IVec2::new(32, 10), "boss walks its arc");
+
+    run_until(&mut app, crucible, 40.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Shielded), 1);
+    assert_eq!(effects.damage_taken_multiplier(), 0.0);
+    let anchored = position(&app);
+
+    run_until(&mut app, crucible, 48.0);
+    assert_eq!(position(&app), anchored);
+}
+
+#[test]
+fn test_elemental_collapse_detonates_every_hazard() {
+    let (mut app, crucible, _) = encounter_app(
+        ArenaName::Crucible,
+        RngSeed::default(),
+        Difficulty::Mythic,
+        0.1,
+    );
+    let beside_pool = spawn_hero(&mut app, crucible, IVec2::new(9, 3));
+    let clear = spawn_hero(&mut app, crucible, IVec2::new(5, 15));
+
+    run_until(&mut app, crucible, 25.5);
+    assert_eq!(
+        terrain(&app, crucible, IVec2::new(10, 4)),
+        TileTerrain::Acid
+    );
+    assert_eq!(
+        app.world().get::<Health>(beside_pool).unwrap().current,
+        1_000.0
+    );
+
+    run_until(&mut app, crucible, 26.5);
+    let occupancy = app.world().get::<ArenaOccupancy>(crucible).unwrap();
+    assert_eq!(occupancy.tiles_with(TileTerrain::Acid).count(), 0);
+    assert_eq!(occupancy.tiles_with(TileTerrain::Catalyst).count(), 0);
+    assert!(app.world().get::<Health>(beside_pool).unwrap().current < 1_000.0);
+    assert_eq!(app.world().get::<Health>(clear).unwrap().current, 1_000.0);
+}
+
+fn chasms(app: &App, arena: Entity) -> Vec<IVec2> {
+    app.world()
+        .get::<ArenaOccupancy>(arena)
+        .unwrap()
+        .tiles_with(TileTerrain::Chasm)
+        .collect()
+}
+
+#[test]
+fn test_charge_strike_dashes_at_the_furthest_hero_and_leaves_a_trail() {
+    let (mut app, gala, boss) =
+        encounter_app(ArenaName::Gala, RngSeed::default(), Difficulty::Normal, 1.0);
+    let near = spawn_hero(&mut app, gala, IVec2::new(28, 10));
+    let far = spawn_hero(&mut app, gala, IVec2::new(50, 10));
+
+    run_until(&mut app, gala, 5.0);
+    let position = |app: &App| app.world().get::<GridPositionComponent>(boss).unwrap().0;
+    assert_eq!(
+        position(&app),
+        IVec2::new(32, 10),
+        "charge is still telegraphing"
+    );
+
+    run_until(&mut app, gala, 6.0);
+    assert_eq!(position(&app), IVec2::new(44, 10));
+    for x in 32..44 {
+        assert_eq!(terrain(&app, gala, IVec2::new(x, 10)), TileTerrain::Trail);
+    }
+    let health = |hero| app.world().get::<Health>(hero).unwrap().current;
+    assert_eq!(health(near), 1_000.0, "the dash went the other way");
+    assert_eq!(health(far), 1_000.0, "the dash stopped short of the target");
+}
+
+#[test]
+fn test_charge_strike_stops_before_walls_its_body_would_clip() {
+    let (mut app, gala, boss) =
+        encounter_app(ArenaName::Gala, RngSeed::default(), Difficulty::Normal, 1.0);
+    spawn_hero(&mut app, gala, IVec2::new(50, 10));
+    // Off the dash's centre line, but under the boss's 3x3 body from x = 39 on
+    app.world_mut()
+        .get_mut::<ArenaOccupancy>(gala)
+        .unwrap()
+        .set_terrain(IVec2::new(40, 11), TileTerrain::Wall);
+
+    run_until(&mut app, gala, 6.0);
+    assert_eq!(
+        app.world().get::<GridPositionComponent>(boss).unwrap().0,
+        IVec2::new(38, 10)
+    );
+}
+
+#[test]
+fn test_dynamic_platforms_follow_the_seed_every_loop() {
+    // Heroic holds the boss in phase Two at 60% health
+    let (mut app, gala, _) =
+        encounter_app(ArenaName::Gala, RngSeed::default(), Difficulty::Heroic, 0.6);
+    run_until(&mut app, gala, 12.5);
+    let first_loop = chasms(&app, gala);
+    assert!(!first_loop.is_empty());
+
+    run_into_next_loop(&mut app, gala, 5.0);
+    assert!(
+        chasms(&app, gala).is_empty(),
+        "loop restart restores the floor"
+    );
+    run_until(&mut app, gala, 12.5);
+    assert_eq!(chasms(&app, gala), first_loop);
+
+    let (mut replayed, gala, _) =
+        encounter_app(ArenaName::Gala, RngSeed::default(), Difficulty::Heroic, 0.6);
+    run_until(&mut replayed, gala, 12.5);
+    assert_eq!(chasms(&replayed, gala), first_loop);
+
+    let (mut reseeded, gala, _) =
+        encounter_app(ArenaName::Gala, RngSeed(42), Difficulty::Heroic, 0.6);
+    run_until(&mut reseeded, gala, 12.5);
+    assert_ne!(chasms(&reseeded, gala), first_loop);
+}
+
+#[test]
+fn test_stampede_chains_dashes() {
+    let (mut app, gala, boss) =
+        encounter_app(ArenaName::Gala, RngSeed::default(), Difficulty::Mythic, 0.1);
+    run_until(&mut app, gala, 29.5);
+    let position = |app: &App| app.world().get::<GridPositionComponent>(boss).unwrap().0;
+    let mut stops = vec![position(&app)];
+    for second in [30.5, 31.5, 32.5, 33.5] {
+        run_until(&mut app, gala, second);
+        stops.push(position(&app));
+    }
+    assert!(
+        stops.windows(2).filter(|pair| pair[0] != pair[1]).count() >= 3,
+        "boss dashed between stops: {stops:?}"
+    );
+}
+
+fn constructs(app: &mut App) -> Vec<(Entity, IVec2)> {
+    let mut minion_q = app
+        .world_mut()
+        .query::<(Entity, &Minion, &GridPositionComponent)>();
+    minion_q
+        .iter(app.world())
+        .filter(|(_, minion, _)| minion.0 == MinionKind::Construct)
+        .map(|(entity, _, tile)| (entity, tile.0))
+        .collect()
+}
+
+#[test]
+fn test_shield_bash_cones_the_closest_hero_and_armor_plating_hardens_the_boss() {
+    let (mut app, bastion, boss) = encounter_app(
+        ArenaName::Bastion,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    let tank = spawn_hero(&mut app, bastion, IVec2::new(34, 10));
+    let behind_tank = spawn_hero(&mut app, bastion, IVec2::new(35, 11));
+    let healer = spawn_hero(&mut app, bastion, IVec2::new(26, 10));
+
+    run_until(&mut app, bastion, 5.0);
+    let health = |app: &App, hero| app.world().get::<Health>(hero).unwrap().current;
+    assert!(health(&app, tank) < 1_000.0);
+    assert!(
+        health(&app, behind_tank) < 1_000.0,
+        "the cone reaches past the tank"
+    );
+    assert_eq!(
+        health(&app, healer),
+        1_000.0,
+        "the healer stands behind the boss"
+    );
+
+    run_until(&mut app, bastion, 8.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::ArmorPlating), 1);
+    assert!((effects.damage_taken_multiplier() - 0.6).abs() < 1e-6);
+}
+
+#[test]
+fn test_living_constructs_fortify_the_boss() {
+    let (mut app, bastion, boss) = encounter_app(
+        ArenaName::Bastion,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    run_until(&mut app, bastion, 10.5);
+    let summoned = constructs(&mut app);
+    assert_eq!(summoned.len(), 4);
+
+    run_until(&mut app, bastion, 15.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Fortified), 4);
+
+    for (construct, _) in summoned {
+        app.world_mut()
+            .get_mut::<Health>(construct)
+            .unwrap()
+            .current = 0.0;
+    }
+    run_until(&mut app, bastion, 21.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Fortified), 0);
+}
+
+#[test]
+fn test_gravity_well_pulls_constructs_and_cataclysm_hits_everyone() {
+    let (mut app, bastion, boss) = encounter_app(
+        ArenaName::Bastion,
+        RngSeed::default(),
+        Difficulty::Mythic,
+        0.1,
+    );
+    let far = spawn_hero(&mut app, bastion, IVec2::new(2, 28));
+
+    run_until(&mut app, bastion, 10.5);
+    let boss_tile = app.world().get::<GridPositionComponent>(boss).unwrap().0;
+    let distance = |tiles: &[(Entity, IVec2)]| -> i32 {
+        tiles
+            .iter()
+            .map(|(_, tile)| (*tile - boss_tile).abs().max_element())
+            .sum()
+    };
+    let before = distance(&constructs(&mut app));
+
+    run_until(&mut app, bastion, 19.5);
+    assert!(distance(&constructs(&mut app)) < before);
+    assert_eq!(app.world().get::<Health>(far).unwrap().current, 1_000.0);
+
+    run_until(&mut app, bastion, 22.5);
+    let health = app.world().get::<Health>(far).unwrap();
+    assert!(
+        health.current < 1_000.0,
+        "Cataclysm Smash reaches the far corner"
+    );
+}
+
+fn corruption(app: &App, arena: Entity) -> Vec<IVec2> {
+    app.world()
+        .get::<ArenaOccupancy>(arena)
+        .unwrap()
+        .tiles_with(TileTerrain::Corrupted)
+        .collect()
+}
+
+#[test]
+fn test_corruption_spreads_identically_every_loop() {
+    let (mut app, sanctum, _) = encounter_app(
+        ArenaName::Sanctum,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    run_until(&mut app, sanctum, 6.5);
+    assert_eq!(
+        corruption(&app, sanctum).len(),
+        9,
+        "first root is a 3x3 patch"
+    );
+
+    run_until(&mut app, sanctum, 8.5);
+    assert_eq!(
+        terrain(&app, sanctum, IVec2::new(10, 6)),
+        TileTerrain::Corrupted,
+        "roots creep outward"
+    );
+
+    run_until(&mut app, sanctum, 60.0);
+    let first_loop = corruption(&app, sanctum);
+    run_into_next_loop(&mut app, sanctum, 5.0);
+    assert!(corruption(&app, sanctum).is_empty());
+    run_until(&mut app, sanctum, 60.0);
+    assert_eq!(corruption(&app, sanctum), first_loop);
+}
+
+#[test]
+fn test_withering_touch_stacks_healing_reduction_on_the_closest_hero() {
+    let (mut app, sanctum, _) = encounter_app(
+        ArenaName::Sanctum,
+        RngSeed::default(),
+        Difficulty::Heroic,
+        0.6,
+    );
+    let tank = spawn_hero(&mut app, sanctum, IVec2::new(34, 12));
+    let healer = spawn_hero(&mut app, sanctum, IVec2::new(44, 12));
+
+    run_until(&mut app, sanctum, 9.5);
+    let effects = |app: &App, hero| app.world().get::<StatusEffects>(hero).unwrap().clone();
+    assert_eq!(
+        effects(&app, tank).stacks_of(StatusEffectKind::WitheringTouch),
+        2
+    );
+    assert_eq!(
+        effects(&app, healer).stacks_of(StatusEffectKind::WitheringTouch),
+        0
+    );
+
+    app.world_mut().get_mut::<Health>(tank).unwrap().current = 500.0;
+    app.world_mut().send_event(HealEvent {
+        source: healer,
+        target: tank,
+        amount: 100.0,
+    });
+    app.update();
+    assert_eq!(app.world().get::<Health>(tank).unwrap().current, 570.0);
+}
+
+#[test]
+fn test_infested_minions_burst_on_death_and_feed_corrupted_tide() {
+    let (mut app, sanctum, boss) = encounter_app(
+        ArenaName::Sanctum,
+        RngSeed::default(),
+        Difficulty::Mythic,
+        0.1,
+    );
+    let beside = spawn_hero(&mut app, sanctum, IVec2::new(21, 7));
+    run_until(&mut app, sanctum, 12.5);
+
+    let mut minion_q = app
+        .world_mut()
+        .query::<(Entity, &Minion, &GridPositionComponent)>();
+    let infested: Vec<Entity> = minion_q
+        .iter(app.world())
+        .filter(|(_, minion, _)| minion.0 == MinionKind::Infested)
+        .map(|(entity, ..)| entity)
+        .collect();
+    assert_eq!(infested.len(), 2);
+
+    run_until(&mut app, sanctum, 25.5);
+    let ward = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(ward.stacks_of(StatusEffectKind::TideWard), 2);
+
+    let near_hero = *infested
+        .iter()
+        .find(|minion| {
+            app.world()
+                .get::<GridPositionComponent>(**minion)
+                .unwrap()
+                .0
+                == IVec2::new(20, 6)
+        })
+        .unwrap();
+    app.world_mut().send_event(DamageEvent {
+        source: beside,
+        target: near_hero,
+        amount: 10_000.0,
+        cause: DamageCause::BossAction("Shield Bash"),
+    });
+    app.update();
+    app.update();
+    app.update();
+    let effects = app.world().get::<StatusEffects>(beside).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Poison), 1);
+    assert!(effects.stacks_of(StatusEffectKind::WitheringTouch) >= 1);
+}
+
+fn piles(app: &mut App) -> Vec<IVec2> {
+    let mut pile_q = app
+        .world_mut()
+        .query_filtered::<&GridPositionComponent, With<TreasurePile>>();
+    pile_q.iter(app.world()).map(|tile| tile.0).collect()
+}
+
+#[test]
+fn test_heroes_and_replayed_ghosts_loot_treasure_piles() {
+    let (mut app, casino, boss) = encounter_app(
+        ArenaName::Casino,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    let hero = spawn_hero(&mut app, casino, IVec2::new(49, 21));
+    let saved: SavedTimeline =
+        "name Looter\nclass Merchant\narena Casino\nstart 15 9\n6 interact\n"
+            .parse()
+            .unwrap();
+    assert!(saved.to_string().contains("6 interact"));
+    app.world_mut()
+        .spawn((saved.ghost_bundle(ArenaName::Casino), ChildOf(casino)));
+
+    run_until(&mut app, casino, 5.5);
+    assert_eq!(piles(&mut app).len(), 6);
+    app.world_mut().send_event(Interact { actor: hero });
+    run_until(&mut app, casino, 6.5);
+    let left = piles(&mut app);
+    assert_eq!(left.len(), 4);
+    assert!(!left.contains(&IVec2::new(50, 22)));
+    assert!(
+        !left.contains(&IVec2::new(14, 8)),
+        "the ghost looted on cue"
+    );
+
+    // The boss walks to the nearest pile and shelters behind it
+    run_until(&mut app, casino, 14.0);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::TreasureShield), 1);
+}
+
+#[test]
+fn test_goldlings_carry_treasure_home_to_heal_the_boss() {
+    let (mut app, casino, boss) = encounter_app(
+        ArenaName::Casino,
+        RngSeed::default(),
+        Difficulty::Heroic,
+        0.6,
+    );
+    run_until(&mut app, casino, 11.5);
+    let before = app.world().get::<Health>(boss).unwrap().current;
+    let laid = piles(&mut app).len();
+
+    run_until(&mut app, casino, 24.0);
+    assert!(piles(&mut app).len() < laid, "goldlings stole piles");
+    assert!(app.world().get::<Health>(boss).unwrap().current > before);
+}
+
+#[test]
+fn test_bait_neither_shields_nor_draws_the_boss() {
+    let (mut app, casino, boss) = encounter_app(
+        ArenaName::Casino,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    app.world_mut().spawn((
+        TreasurePile::bait(),
+        GridPositionComponent(IVec2::new(32, 13)),
+        ChildOf(casino),
+    ));
+
+    run_until(&mut app, casino, 4.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::TreasureShield), 0);
+    assert_eq!(boss_tile(&app, boss), IVec2::new(32, 10));
+}
+
+#[test]
+fn test_treasure_overload_explodes_unlooted_piles() {
+    let (mut app, casino, _) = encounter_app(
+        ArenaName::Casino,
+        RngSeed::default(),
+        Difficulty::Mythic,
+        0.4,
+    );
+    let hero = spawn_hero(&mut app, casino, IVec2::new(50, 20));
+    run_until(&mut app, casino, 32.5);
+    assert!(!piles(&mut app).is_empty());
+    assert_eq!(app.world().get::<Health>(hero).unwrap().current, 1_000.0);
+
+    run_until(&mut app, casino, 33.5);
+    assert!(piles(&mut app).is_empty());
+    assert!(app.world().get::<Health>(hero).unwrap().current < 1_000.0);
+}
+
+fn boss_tile(app: &App, boss: Entity) -> IVec2 {
+    app.world().get::<GridPositionComponent>(boss).unwrap().0
+}
+
+#[test]
+fn test_shadow_fade_escapes_into_cloaking_shadow() {
+    let (mut app, pawnshop, boss) = encounter_app(
+        ArenaName::Pawnshop,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    spawn_hero(&mut app, pawnshop, IVec2::new(14, 9));
+    run_until(&mut app, pawnshop, 5.5);
+    assert_eq!(
+        terrain(&app, pawnshop, IVec2::new(12, 8)),
+        TileTerrain::Shadow
+    );
+    assert_ne!(
+        terrain(&app, pawnshop, boss_tile(&app, boss)),
+        TileTerrain::Shadow
+    );
+
+    // Fades to the shadow furthest from the only hero
+    run_until(&mut app, pawnshop, 6.5);
+    assert_eq!(boss_tile(&app, boss), IVec2::new(52, 22));
+    run_until(&mut app, pawnshop, 7.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::ShadowCloak), 1);
+    assert_eq!(effects.damage_taken_multiplier(), 0.5);
+}
+
+#[test]
+fn test_treasure_bait_explodes_after_being_touched() {
+    let (mut app, pawnshop, _) = encounter_app(
+        ArenaName::Pawnshop,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    let hero = spawn_hero(&mut app, pawnshop, IVec2::new(33, 5));
+    run_until(&mut app, pawnshop, 10.5);
+    assert_eq!(piles(&mut app).len(), 4);
+
+    app.world_mut().send_event(Interact { actor: hero });
+    run_until(&mut app, pawnshop, 12.0);
+    assert_eq!(piles(&mut app).len(), 4, "bait waits out its fuse");
+    assert_eq!(app.world().get::<Health>(hero).unwrap().current, 1_000.0);
+
+    run_until(&mut app, pawnshop, 13.0);
+    assert!(!piles(&mut app).contains(&IVec2::new(32, 4)));
+    assert_eq!(app.world().get::<Health>(hero).unwrap().current, 700.0);
+}
+
+#[test]
+fn test_decoys_mimic_the_boss_and_dark_refuge_follows_the_seed() {
+    let (mut app, pawnshop, boss) = encounter_app(
+        ArenaName::Pawnshop,
+        RngSeed::default(),
+        Difficulty::Heroic,
+        0.6,
+    );
+    run_until(&mut app, pawnshop, 8.5);
+    let mut decoy_q = app
+        .world_mut()
+        .query_filtered::<(Entity, &Decoy), With<Boss>>();
+    let (decoy, mimic) = decoy_q.single(app.world()).unwrap();
+    assert_eq!(mimic.mimics, boss);
+    let offset = mimic.offset;
+    let mut real_q = app
+        .world_mut()
+        .query_filtered::<Entity, (With<Boss>, Without<Decoy>)>();
+    assert_eq!(real_q.single(app.world()).unwrap(), boss);
+
+    // The decoy copies every move the boss makes
+    app.world_mut()
+        .get_mut::<GridPositionComponent>(boss)
+        .unwrap()
+        .0 += IVec2::new(0, 3);
+    app.update();
+    assert_eq!(boss_tile(&app, decoy), boss_tile(&app, boss) + offset);
+    run_until(&mut app, pawnshop, 13.5);
+    assert!(app.world().get_entity(decoy).is_err(), "the decoy faded");
+
+    run_until(&mut app, pawnshop, 15.5);
+    let refuge = boss_tile(&app, boss);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Shielded), 1);
+    run_into_next_loop(&mut app, pawnshop, 15.5);
+    assert_eq!(boss_tile(&app, boss), refuge);
+}
+
+#[test]
+fn test_seedlings_ripen_on_the_arena_clock_and_regenerate_the_boss() {
+    let (mut app, mountain, boss) = encounter_app(
+        ArenaName::Mountain,
+        RngSeed::default(),
+        Difficulty::Normal,
+        0.9,
+    );
+    let wounded = app.world().get::<Health>(boss).unwrap().current;
+    run_until(&mut app, mountain, 4.5);
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(26, 12)),
+        TileTerrain::Seedling
+    );
+    run_until(&mut app, mountain, 13.9);
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(26, 12)),
+        TileTerrain::Seedling
+    );
+    run_until(&mut app, mountain, 14.5);
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(26, 12)),
+        TileTerrain::Fertile
+    );
+
+    // The boss walks onto the new fertile ground and regenerates there
+    run_until(&mut app, mountain, 23.5);
+    assert_eq!(
+        terrain(&app, mountain, boss_tile(&app, boss)),
+        TileTerrain::Fertile
+    );
+    assert!(app.world().get::<Health>(boss).unwrap().current > wounded);
+}
+
+#[test]
+fn test_harvesting_fertile_ground_denies_regeneration() {
+    let (mut app, mountain, boss) = encounter_app(
+        ArenaName::Mountain,
+        RngSeed::default(),
+        Difficulty::Normal,
+        0.9,
+    );
+    let wounded = app.world().get::<Health>(boss).unwrap().current;
+    let hero = spawn_hero(&mut app, mountain, IVec2::new(26, 12));
+    run_until(&mut app, mountain, 14.5);
+    app.world_mut().send_event(Interact { actor: hero });
+    app.update();
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(25, 11)),
+        TileTerrain::Open
+    );
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(27, 13)),
+        TileTerrain::Open
+    );
+
+    run_until(&mut app, mountain, 23.5);
+    assert_eq!(app.world().get::<Health>(boss).unwrap().current, wounded);
+
+    // Growth restarts with the loop, harvest and all
+    run_into_next_loop(&mut app, mountain, 13.9);
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(26, 12)),
+        TileTerrain::Seedling
+    );
+    run_until(&mut app, mountain, 14.5);
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(26, 12)),
+        TileTerrain::Fertile
+    );
+}
+
+#[test]
+fn test_fertile_bloom_ripens_early_and_rapid_bloom_overgrows_it() {
+    let (mut app, mountain, boss) = encounter_app(
+        ArenaName::Mountain,
+        RngSeed::default(),
+        Difficulty::Mythic,
+        0.2,
+    );
+    let hero = spawn_hero(&mut app, mountain, IVec2::new(26, 12));
+    run_until(&mut app, mountain, 12.5);
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(26, 12)),
+        TileTerrain::Fertile
+    );
+
+    run_until(&mut app, mountain, 29.5);
+    assert_eq!(app.world().get::<Health>(hero).unwrap().current, 1_000.0);
+    // Regeneration Field has healed the boss out of phase Four - knock it back down
+    let mut health = app.world_mut().get_mut::<Health>(boss).unwrap();
+    health.current = health.max * 0.1;
+    run_until(&mut app, mountain, 30.5);
+    assert_eq!(
+        terrain(&app, mountain, IVec2::new(26, 12)),
+        TileTerrain::Bramble
+    );
+    run_until(&mut app, mountain, 31.5);
+    assert!(app.world().get::<Health>(hero).unwrap().current < 1_000.0);
+}
+
+#[test]
+fn test_vine_shield_halves_damage_while_vines_are_summoned() {
+    let (mut app, mountain, boss) = encounter_app(
+        ArenaName::Mountain,
+        RngSeed::default(),
+        Difficulty::Mythic,
+        0.6,
+    );
+    run_until(&mut app, mountain, 15.9);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::VineShield), 0);
+
+    run_until(&mut app, mountain, 16.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert!((effects.damage_taken_multiplier() - 0.5).abs() < 1e-6);
+    let boss_at = boss_tile(&app, boss);
+    let mut minion_q = app.world_mut().query::<(&Minion, &GridPositionComponent)>();
+    let vines: Vec<_> = minion_q
+        .iter(app.world())
+        .filter(|(minion, _)| minion.0 == MinionKind::Vine)
+        .map(|(_, tile)| (tile.0 - boss_at).abs().max_element())
+        .collect();
+    assert_eq!(vines.len(), 2);
+    assert!(vines.iter().all(|distance| *distance <= 4));
+
+    run_until(&mut app, mountain, 21.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::VineShield), 0);
+}
+
+#[test]
+fn test_signatures_cover_every_other_arena_once() {
+    let mut borrowed: Vec<_> = Signature::ROTATION
+        .map(|signature| signature.borrowed_from().as_u8())
+        .to_vec();
+    borrowed.sort_unstable();
+    borrowed.dedup();
+    assert_eq!(borrowed.len(), Signature::ROTATION.len());
+    assert!(!borrowed.contains(&ArenaName::GuildHouse.as_u8()));
+}
+
+#[test]
+fn test_guild_master_stays_sealed_until_every_other_arena_is_cleared() {
+    let (mut app, guild_house, boss) = encounter_app(
+        ArenaName::GuildHouse,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    let hero = spawn_hero(&mut app, guild_house, IVec2::new(33, 12));
+    run_until(&mut app, guild_house, 3.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Shielded), 1);
+    assert_eq!(app.world().get::<Health>(hero).unwrap().current, 1_000.0);
+
+    // Bosses falling in the other eight arenas unseal it
+    let others: Vec<_> = ArenaName::ALL_ARENAS
+        .into_iter()
+        .filter(|arena| *arena != ArenaName::GuildHouse)
+        .map(|arena| app.world().resource::<ArenaEntities>().get(arena))
+        .collect();
+    for arena in others {
+        let fallen = app
+            .world_mut()
+            .spawn((Boss, Health::new(1.0), ChildOf(arena)))
+            .id();
+        app.world_mut().send_event(CombatantDied { entity: fallen });
+    }
+    app.update();
+    assert!(app.world().resource::<ArenaClears>().unseals_guild_master());
+
+    run_into_next_loop(&mut app, guild_house, 3.5);
+    assert!(app.world().get::<Health>(hero).unwrap().current < 1_000.0);
+}
+
+#[test]
+fn test_guild_master_rotates_through_borrowed_signatures() {
+    let (mut app, guild_house, boss) = encounter_app(
+        ArenaName::GuildHouse,
+        RngSeed::default(),
+        Difficulty::Normal,
+        1.0,
+    );
+    let mut clears = ArenaClears::default();
+    for arena in ArenaName::ALL_ARENAS {
+        clears.record(arena);
+    }
+    app.insert_resource(clears);
+    let close = spawn_hero(&mut app, guild_house, IVec2::new(33, 12));
+    spawn_hero(&mut app, guild_house, IVec2::new(50, 20));
+
+    // Acid Burst, borrowed from the Crucible and cast as a Decree
+    run_until(&mut app, guild_house, 3.5);
+    assert_eq!(Signature::AcidBurst.ability(), GuildMasterAbility::Decree);
+    assert_eq!(app.world().get::<Health>(close).unwrap().current, 800.0);
+
+    // Web Snare lands under the furthest hero
+    run_until(&mut app, guild_house, 8.5);
+    assert_eq!(
+        terrain(&app, guild_house, IVec2::new(50, 20)),
+        TileTerrain::Web
+    );
+
+    // Armor Plating hardens the boss
+    run_until(&mut app, guild_house, 13.5);
+    let effects = app.world().get::<StatusEffects>(boss).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::ArmorPlating), 1);
+
+    // A fresh loop sweeps the web away
+    run_into_next_loop(&mut app, guild_house, 1.0);
+    assert_eq!(
+        terrain(&app, guild_house, IVec2::new(50, 20)),
+        TileTerrain::Open
+    );
+}
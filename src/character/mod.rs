@@ -1,40 +1,75 @@
 // Standard library and external crates
-use bevy::input::ButtonInput;
-use bevy::math::Vec3;
+use bevy::math::IVec2;
 use bevy::pbr::MeshMaterial3d;
 use bevy::prelude::{
-    ChildOf, Children, Commands, Component, Entity, EventWriter, KeyCode, Query, Res, ResMut,
-    Single, Transform, With,
+    Children, Commands, Component, Entity, Event, EventWriter, Query, Res, ResMut, Single, With,
 };
 
 // Local crate modules
-use crate::arena::{
-    Arena, ArenaEntities, ArenaName, CharacterMoved, CurrentArena, CurrentArenaEntity, GRID_HEIGHT,
-    GRID_WIDTH, LastActiveHero, TILE_SIZE,
-};
+use crate::ability::AbilityCooldowns;
+use crate::arena::{Arena, CurrentArenaEntity, LastActiveHero, MoveRequest, step_tile};
+use crate::controls::{Action, ActionState};
 use crate::materials::Materials;
+use crate::pathfinding::PathFollower;
 use crate::selectors::Active;
-use crate::timeline::{DraftTimeline, GlobalTimelinePause, TimelineClock};
+use crate::status_effect::StatusEffects;
+use crate::timeline::{
+    DraftTimeline, GlobalTimelinePause, GridPositionComponent, TimelineClock, TimelineEvent,
+};
+
+/// Starting health for every hero
+pub const CHARACTER_MAX_HEALTH: f32 = 1_000.0;
 
 /// Marker component for character entities.
 #[derive(Component, Debug)]
+#[require(StatusEffects, AbilityCooldowns)]
 pub struct Character;
 
 #[derive(Component, Debug)]
+#[require(StatusEffects)]
 pub struct Boss;
 
 #[derive(Component, Debug)]
 pub struct Ghost;
 
+/// Moves the Active marker (and its highlight) from one hero to another in an arena
+/// Shared by Tab cycling and clicking a party frame
+pub fn set_active_character(
+    commands: &mut Commands,
+    mats: &Materials,
+    arena_entity: Entity,
+    previous: Option<Entity>,
+    next: Entity,
+) {
+    // Remove Active from the current character
+    if let Some(previous) = previous {
+        commands
+            .entity(previous)
+            .insert(MeshMaterial3d(mats.gray.clone()))
+            .remove::<Active>();
+    }
+
+    // Add Active to the next character
+    commands
+        .entity(next)
+        .insert(MeshMaterial3d(mats.blue.clone()))
+        .insert(Active);
+
+    // Update LastActiveHero
+    commands
+        .entity(arena_entity)
+        .insert(LastActiveHero(Some(next)));
+}
+
 pub fn toggle_active_character(
     mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     current: CurrentArenaEntity,
     arena_q: Query<(&Arena, &Children), With<Arena>>,
     characters_q: Query<(Entity, Option<&Active>), With<Character>>,
     mats: Res<Materials>,
 ) {
-    if !keyboard_input.just_pressed(KeyCode::Tab) {
+    if !actions.just_pressed(Action::CycleHero) {
         return;
     }
 
@@ -62,24 +97,15 @@ pub fn toggle_active_character(
             // Calculate next index (cyclical)
             let next_index = (current_index + 1) % characters_data.len();
 
-            // Remove Active from the current character
             let current_entity = characters_data[current_index].0;
-            commands
-                .entity(current_entity)
-                .insert(MeshMaterial3d(mats.gray.clone()))
-                .remove::<Active>();
-
-            // Add Active to the next character
             let next_entity = characters_data[next_index].0;
-            commands
-                .entity(next_entity)
-                .insert(MeshMaterial3d(mats.blue.clone()))
-                .insert(Active);
-
-            // Update LastActiveHero
-            commands
-                .entity(current_arena_entity)
-                .insert(LastActiveHero(Some(next_entity)));
+            set_active_character(
+                &mut commands,
+                &mats,
+                current_arena_entity,
+                Some(current_entity),
+                next_entity,
+            );
 
             println!(
                 "Cycled from character {:?} to {:?} in {}",
@@ -91,11 +117,10 @@ pub fn toggle_active_character(
 
 pub fn move_active_character(
     mut commands: Commands,
-    keycode: Res<ButtonInput<KeyCode>>,
-    mut current_arena: ResMut<CurrentArena>,
-    active_character_q: Single<(Entity, &mut Transform), (With<Character>, With<Active>)>,
-    arena_entities: Res<ArenaEntities>,
-    mut character_moved_event: EventWriter<CharacterMoved>,
+    actions: Res<ActionState>,
+    current: CurrentArenaEntity,
+    active_character_q: Single<(Entity, &GridPositionComponent), (With<Character>, With<Active>)>,
+    mut move_requests: EventWriter<MoveRequest>,
     mut draft_timeline: ResMut<DraftTimeline>,
     arena_q: Query<(&Arena, &TimelineClock)>,
     global_pause: Res<GlobalTimelinePause>,
@@ -104,169 +129,76 @@ pub fn move_active_character(
         return;
     }
 
-    // Calculate grid direction directly from key presses
-    let grid_direction = if keycode.just_pressed(KeyCode::KeyW) {
-        Vec3::new(0.0, 1.0, 0.0) // Up
-    } else if keycode.just_pressed(KeyCode::KeyS) {
-        Vec3::new(0.0, -1.0, 0.0) // Down
-    } else if keycode.just_pressed(KeyCode::KeyA) {
-        Vec3::new(-1.0, 0.0, 0.0) // Left
-    } else if keycode.just_pressed(KeyCode::KeyD) {
-        Vec3::new(1.0, 0.0, 0.0) // Right
+    // Calculate grid direction directly from movement actions
+    let grid_direction = if actions.just_pressed(Action::MoveUp) {
+        IVec2::new(0, 1) // Up
+    } else if actions.just_pressed(Action::MoveDown) {
+        IVec2::new(0, -1) // Down
+    } else if actions.just_pressed(Action::MoveLeft) {
+        IVec2::new(-1, 0) // Left
+    } else if actions.just_pressed(Action::MoveRight) {
+        IVec2::new(1, 0) // Right
     } else {
         return;
     };
 
-    let (character_entity, mut character_transform) = active_character_q.into_inner();
-
-    // Calculate a new position (scale grid direction by TILE_SIZE)
-    let new_position = character_transform.translation + grid_direction * TILE_SIZE;
-
-    // Arena boundaries (in local space)
-    let min_x = 0.0;
-    let max_x = (GRID_WIDTH - 1) as f32 * TILE_SIZE;
-    let min_y = 0.0;
-    let max_y = (GRID_HEIGHT - 1) as f32 * TILE_SIZE;
-
-    // Arena grid layout (3x3):
-    // 0 1 2
-    // 3 4 5
-    // 6 7 8
-
-    let current_arena_index = current_arena.0.as_u8();
-    let col = current_arena_index % 3;
-    let row = current_arena_index / 3;
-
-    // Check boundaries and handle transitions
-    if new_position.x < min_x {
-        // Moving left out of bounds
-        if col > 0 {
-            // Can move to an arena on the left
-            let from_arena = current_arena.0;
-            let new_arena_index = current_arena_index - 1;
-            let new_arena_name = ArenaName::from_index_safe(new_arena_index);
-
-            // Teleport character to the right side of a new arena
-            character_transform.translation.x = max_x;
+    let (character_entity, grid_position) = active_character_q.into_inner();
 
-            // Update CurrentArena after character movement
-            current_arena.0 = new_arena_name;
-
-            // Reparent character to new arena - O(1) lookup
-            let new_arena_entity = arena_entities.get(new_arena_name);
-            commands
-                .entity(character_entity)
-                .insert(ChildOf(new_arena_entity));
-            println!("Moved to {} (left)", new_arena_name);
-            // Send character moved event
-            character_moved_event.write(CharacterMoved {
-                character_entity,
-                from_arena,
-                to_arena: new_arena_name,
-            });
-        } else {
-            println!("Cannot move left - at battleground boundary");
-            return; // Prevent movement
-        }
-    } else if new_position.x > max_x {
-        // Moving right out of bounds
-        if col < 2 {
-            // Can move to an arena on the right
-            let from_arena = current_arena.0;
-            let new_arena_index = current_arena_index + 1;
-            let new_arena_name = ArenaName::from_index_safe(new_arena_index);
+    // Steps off an arena edge land on the facing edge of the neighbouring arena
+    let Some((to_arena, to_tile)) = step_tile(current.name(), grid_position.0, grid_direction)
+    else {
+        println!("Cannot move - at battleground boundary");
+        return; // Prevent movement
+    };
 
-            // Teleport character to the left side of a new arena
-            character_transform.translation.x = min_x;
+    // Keyboard input overrides any click-to-move path in progress
+    commands.entity(character_entity).remove::<PathFollower>();
 
-            // Update CurrentArena after character movement
-            current_arena.0 = new_arena_name;
+    // Occupancy resolves the request; reparenting and CharacterMoved follow from it
+    move_requests.write(MoveRequest {
+        entity: character_entity,
+        to_arena,
+        to_tile,
+    });
 
-            // Reparent character to new arena - O(1) lookup
-            let new_arena_entity = arena_entities.get(new_arena_name);
-            commands
-                .entity(character_entity)
-                .insert(ChildOf(new_arena_entity));
-            println!("Moved to {} (right)", new_arena_name);
-            // Send character moved event
-            character_moved_event.write(CharacterMoved {
-                character_entity,
-                from_arena,
-                to_arena: new_arena_name,
-            });
-        } else {
-            println!("Cannot move right - at battleground boundary");
-            return; // Prevent movement
+    if let Ok((_, clock)) = arena_q.get(current.get()) {
+        let event = TimelineEvent::movement(clock.current(), grid_direction);
+        if let Err(e) = draft_timeline.add_event(event) {
+            bevy::log::warn!("Failed to record movement event: {:?}", e);
         }
-    } else if new_position.y < min_y {
-        // Moving down out of bounds
-        if row < 2 {
-            // Can move to an arena below
-            let from_arena = current_arena.0;
-            let new_arena_index = current_arena_index + 3;
-            let new_arena_name = ArenaName::from_index_safe(new_arena_index);
-
-            // Teleport character to the top side of the new arena
-            character_transform.translation.y = max_y;
-
-            // Update CurrentArena after character movement
-            current_arena.0 = new_arena_name;
+    }
+}
 
-            // Reparent character to new arena - O(1) lookup
-            let new_arena_entity = arena_entities.get(new_arena_name);
-            commands
-                .entity(character_entity)
-                .insert(ChildOf(new_arena_entity));
-            println!("Moved to {} (down)", new_arena_name);
-            // Send character moved event
-            character_moved_event.write(CharacterMoved {
-                character_entity,
-                from_arena,
-                to_arena: new_arena_name,
-            });
-        } else {
-            println!("Cannot move down - at battleground boundary");
-            return; // Prevent movement
-        }
-    } else if new_position.y > max_y {
-        // Moving up out of bounds
-        if row > 0 {
-            // Can move to the arena above
-            let from_arena = current_arena.0;
-            let new_arena_index = current_arena_index - 3;
-            let new_arena_name = ArenaName::from_index_safe(new_arena_index);
+/// A hero or ghost interacting with whatever lies on or beside its tile
+/// Recorded into timelines so ghosts repeat the interaction on replay
+#[derive(Event, Debug, Clone)]
+pub struct Interact {
+    pub actor: Entity,
+}
 
-            // Teleport character to the bottom side of the new arena
-            character_transform.translation.y = min_y;
+pub fn interact_with_active_character(
+    actions: Res<ActionState>,
+    current: CurrentArenaEntity,
+    active_character_q: Single<Entity, (With<Character>, With<Active>)>,
+    mut interactions: EventWriter<Interact>,
+    mut draft_timeline: ResMut<DraftTimeline>,
+    arena_q: Query<(&Arena, &TimelineClock)>,
+    global_pause: Res<GlobalTimelinePause>,
+) {
+    if global_pause.is_paused || !actions.just_pressed(Action::Interact) {
+        return;
+    }
 
-            // Update CurrentArena after character movement
-            current_arena.0 = new_arena_name;
+    interactions.write(Interact {
+        actor: active_character_q.into_inner(),
+    });
 
-            // Reparent character to new arena - O(1) lookup
-            let new_arena_entity = arena_entities.get(new_arena_name);
-            commands
-                .entity(character_entity)
-                .insert(ChildOf(new_arena_entity));
-            println!("Moved to {} (up)", new_arena_name);
-            // Send character moved event
-            character_moved_event.write(CharacterMoved {
-                character_entity,
-                from_arena,
-                to_arena: new_arena_name,
-            });
-        } else {
-            println!("Cannot move up - at battleground boundary");
-            return; // Prevent movement
+    if let Ok((_, clock)) = arena_q.get(current.get()) {
+        let event = TimelineEvent::interaction(clock.current());
+        if let Err(e) = draft_timeline.add_event(event) {
+            bevy::log::warn!("Failed to record interaction event: {:?}", e);
         }
-    } else {
-        // Normal movement within arena bounds
-        character_transform.translation = new_position;
     }
-
-    println!(
-        "Character at: {:?} in {}",
-        character_transform.translation, current_arena.0
-    );
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use super::*;
+use crate::arena::ArenaEntities;
 use crate::arena::{Arena, ArenaName, CharacterMoved, CurrentArena, LastActiveHero};
 use crate::materials::Materials;
 use crate::selectors::Active;
@@ -6,6 +7,7 @@ use bevy::app::{App, Update};
 use bevy::asset::{AssetApp, Assets};
 use bevy::ecs::system::RunSystemOnce;
 use bevy::pbr::StandardMaterial;
+use bevy::prelude::ChildOf;
 use bevy::prelude::{Commands, EventReader, Query, ResMut, Single, Transform, Vec3, With};
 
 /// Helper function to create a test app with minimal required plugins and systems
@@ -15,8 +17,8 @@ fn create_test_app() -> App {
     // Add only the minimal required plugins for ECS functionality
     app.add_plugins(bevy::prelude::MinimalPlugins);
 
-    // Add input plugin for ButtonInput resource
-    app.add_plugins(bevy::input::InputPlugin);
+    // Add action state read by the input-driven systems
+    app.init_resource::<crate::controls::ActionState>();
 
     // Add asset plugin for StandardMaterial assets
     app.add_plugins(bevy::asset::AssetPlugin::default());
@@ -44,7 +46,7 @@ fn create_test_app() -> App {
     app.add_systems(Update, (move_active_character, toggle_active_character));
 
     // Add events
-    app.add_event::<CharacterMoved>();
+    app.add_event::<CharacterMoved>().add_event::<MoveRequest>();
 
     // Add timeline resources required by move_active_character
     app.init_resource::<crate::timeline::GlobalTimelinePause>()
@@ -1,6 +1,12 @@
+use crate::ability::{
+    AbilityType, AlchemistAbility, BardAbility, CardinalAbility, ForagerAbility,
+    GuildMasterAbility, HunterAbility, MerchantAbility, ThiefAbility, WarriorAbility,
+};
 use bevy::prelude::Component;
+use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ClassType {
     Hunter = 0,
     Cardinal = 1,
@@ -13,6 +19,23 @@ pub enum ClassType {
     GuildMaster = 8,
 }
 
+/// Error for names that match no class
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Unknown class: {0}")]
+pub struct UnknownClass(pub String);
+
+/// Parses the names produced by `ClassType::name`
+impl FromStr for ClassType {
+    type Err = UnknownClass;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        (0..9)
+            .map(Self::index_of)
+            .find(|class| class.name() == s)
+            .ok_or_else(|| UnknownClass(s.to_string()))
+    }
+}
+
 impl ClassType {
     pub fn name(self) -> String {
         match self {
@@ -41,4 +64,90 @@ impl ClassType {
             _ => panic!("Invalid index"),
         }
     }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// The four abilities on the class's ability bar, in key order
+    #[must_use]
+    pub const fn abilities(self) -> &'static [AbilityType] {
+        match self {
+            ClassType::Hunter => &[
+                AbilityType::Hunter(HunterAbility::AutoShot),
+                AbilityType::Hunter(HunterAbility::PoisonShot),
+                AbilityType::Hunter(HunterAbility::Sniper),
+                AbilityType::Hunter(HunterAbility::Trap),
+            ],
+            ClassType::Cardinal => &[
+                AbilityType::Cardinal(CardinalAbility::HolyNova),
+                AbilityType::Cardinal(CardinalAbility::Heal),
+                AbilityType::Cardinal(CardinalAbility::Barrier),
+                AbilityType::Cardinal(CardinalAbility::Beam),
+            ],
+            ClassType::Forager => &[
+                AbilityType::Forager(ForagerAbility::Border),
+                AbilityType::Forager(ForagerAbility::Boulder),
+                AbilityType::Forager(ForagerAbility::Dig),
+                AbilityType::Forager(ForagerAbility::Mushroom),
+            ],
+            ClassType::Warrior => &[
+                AbilityType::Warrior(WarriorAbility::Bash),
+                AbilityType::Warrior(WarriorAbility::Block),
+                AbilityType::Warrior(WarriorAbility::Bulwark),
+                AbilityType::Warrior(WarriorAbility::Taunt),
+            ],
+            ClassType::Thief => &[
+                AbilityType::Thief(ThiefAbility::Backstab),
+                AbilityType::Thief(ThiefAbility::Pickpocket),
+                AbilityType::Thief(ThiefAbility::ShadowStep),
+                AbilityType::Thief(ThiefAbility::SmokeScreen),
+            ],
+            ClassType::Alchemist => &[
+                AbilityType::Alchemist(AlchemistAbility::AcidFlask),
+                AbilityType::Alchemist(AlchemistAbility::Ironskin),
+                AbilityType::Alchemist(AlchemistAbility::Siphon),
+                AbilityType::Alchemist(AlchemistAbility::Transmute),
+            ],
+            ClassType::Merchant => &[
+                AbilityType::Merchant(MerchantAbility::CoinToss),
+                AbilityType::Merchant(MerchantAbility::Dice),
+                AbilityType::Merchant(MerchantAbility::Fortune),
+                AbilityType::Merchant(MerchantAbility::Vault),
+            ],
+            ClassType::Bard => &[
+                AbilityType::Bard(BardAbility::Cleanse),
+                AbilityType::Bard(BardAbility::Dance),
+                AbilityType::Bard(BardAbility::Helix),
+                AbilityType::Bard(BardAbility::Mimic),
+            ],
+            ClassType::GuildMaster => &[
+                AbilityType::GuildMaster(GuildMasterAbility::Decree),
+                AbilityType::GuildMaster(GuildMasterAbility::Rally),
+                AbilityType::GuildMaster(GuildMasterAbility::Levy),
+                AbilityType::GuildMaster(GuildMasterAbility::Muster),
+            ],
+        }
+    }
+
+    /// Asset path of the class icon, or its highlighted variant for the active hero
+    #[must_use]
+    pub const fn icon_path(self, selected: bool) -> &'static str {
+        match (self, selected) {
+            (ClassType::Hunter, false) => "characters/hunter_icon.png",
+            (ClassType::Hunter, true) => "characters/hunter_icon_selected.png",
+            (ClassType::Cardinal, false) => "characters/cardinal_icon.png",
+            (ClassType::Cardinal, true) => "characters/cardinal_icon_selected.png",
+            (ClassType::Forager, false) => "characters/forager_icon.png",
+            (ClassType::Forager, true) => "characters/forager_icon_selected.png",
+            (ClassType::Warrior, false) => "characters/warrior_icon.png",
+            (ClassType::Warrior, true) => "characters/warrior_icon_selected.png",
+            (ClassType::Thief, false) => "characters/thief_icon.png",
+            (ClassType::Thief, true) => "characters/thief_icon_selected.png",
+            (ClassType::Alchemist, false) => "characters/alchemist_icon.png",
+            (ClassType::Alchemist, true) => "characters/alchemist_icon_selected.png",
+            (ClassType::Merchant, false) => "characters/merchant_icon.png",
+            (ClassType::Merchant, true) => "characters/merchant_icon_selected.png",
+            (ClassType::Bard, false) => "characters/bard_icon.png",
+            (ClassType::Bard, true) => "characters/bard_icon_selected.png",
+            (ClassType::GuildMaster, _) => "characters/guild_master.png",
+        }
+    }
 }
@@ -0,0 +1,180 @@
+use crate::ability::AbilityType;
+use crate::status_effect::{StatusEffectKind, StatusEffects};
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Hit points for any combatant - characters, bosses and minions
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    /// Creates a new Health pool at full capacity
+    #[must_use]
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// Remaining health as a fraction of maximum (0.0 to 1.0)
+    #[must_use]
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+
+    #[must_use]
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Removes health, never dropping below zero. Returns the amount actually removed.
+    pub fn take_damage(&mut self, amount: f32) -> f32 {
+        let applied = amount.max(0.0).min(self.current);
+        self.current -= applied;
+        applied
+    }
+
+    /// Restores health, never exceeding maximum. Returns the amount actually restored.
+    pub fn heal(&mut self, amount: f32) -> f32 {
+        let applied = amount.max(0.0).min(self.max - self.current);
+        self.current += applied;
+        applied
+    }
+}
+
+/// The ability, status effect or boss attack a hit came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageCause {
+    Ability(AbilityType),
+    Status(StatusEffectKind),
+    /// A scripted boss attack, named as in _docs/BOSS_FIGHTS.md
+    BossAction(&'static str),
+}
+
+impl Display for DamageCause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Ability(ability) => write!(f, "{ability}"),
+            Self::Status(kind) => write!(f, "{kind:?}"),
+            Self::BossAction(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Request to damage a combatant - mitigation is applied by the receiver
+#[derive(Event, Debug, Clone)]
+pub struct DamageEvent {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: f32,
+    pub cause: DamageCause,
+}
+
+/// Request to heal a combatant
+#[derive(Event, Debug, Clone)]
+pub struct HealEvent {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: f32,
+}
+
+/// Damage that actually landed, after mitigation and overkill
+#[derive(Event, Debug, Clone)]
+pub struct DamageDealt {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: f32,
+    pub cause: DamageCause,
+}
+
+/// Healing that actually landed, after modifiers and overheal
+#[derive(Event, Debug, Clone)]
+pub struct HealingDone {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: f32,
+}
+
+/// Fired once when a combatant's health reaches zero
+#[derive(Event, Debug, Clone)]
+pub struct CombatantDied {
+    pub entity: Entity,
+}
+
+/// Scales all damage an entity deals - bosses get theirs from the arena's difficulty
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct DamageMultiplier(pub f32);
+
+/// Applies damage requests, scaled by the source's multiplier and reduced by the
+/// target's active status effects
+pub fn apply_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    source_q: Query<&DamageMultiplier>,
+    mut target_q: Query<(&mut Health, Option<&StatusEffects>)>,
+    mut dealt_events: EventWriter<DamageDealt>,
+    mut death_events: EventWriter<CombatantDied>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut health, effects)) = target_q.get_mut(event.target) else {
+            continue;
+        };
+        if health.is_dead() {
+            continue;
+        }
+        let dealt = source_q.get(event.source).map_or(1.0, |scale| scale.0);
+        let taken = effects.map_or(1.0, StatusEffects::damage_taken_multiplier);
+        let applied = health.take_damage(event.amount * dealt * taken);
+        if applied > 0.0 {
+            dealt_events.write(DamageDealt {
+                source: event.source,
+                target: event.target,
+                amount: applied,
+                cause: event.cause,
+            });
+        }
+        if health.is_dead() {
+            death_events.write(CombatantDied {
+                entity: event.target,
+            });
+        }
+    }
+}
+
+/// Applies healing requests
+pub fn apply_healing(
+    mut heal_events: EventReader<HealEvent>,
+    mut target_q: Query<(&mut Health, Option<&StatusEffects>)>,
+    mut done_events: EventWriter<HealingDone>,
+) {
+    for event in heal_events.read() {
+        if let Ok((mut health, effects)) = target_q.get_mut(event.target) {
+            let received = effects.map_or(1.0, StatusEffects::healing_received_multiplier);
+            let applied = health.heal(event.amount * received);
+            if applied > 0.0 {
+                done_events.write(HealingDone {
+                    source: event.source,
+                    target: event.target,
+                    amount: applied,
+                });
+            }
+        }
+    }
+}
+
+/// Plugin for health changes driven by damage and healing events
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .add_event::<HealEvent>()
+            .add_event::<DamageDealt>()
+            .add_event::<HealingDone>()
+            .add_event::<CombatantDied>()
+            .add_systems(Update, (apply_damage, apply_healing).chain());
+    }
+}
@@ -0,0 +1,489 @@
+use crate::ability::{AbilityCast, AbilityType};
+use crate::arena::{Arena, ArenaName, ArenaReset, CurrentArenaEntity};
+use crate::combat::{CombatantDied, DamageCause, DamageDealt, HealingDone};
+use crate::controls::{Action, ActionState};
+use crate::guild::HeroId;
+use crate::status_effect::{
+    StatusEffectApplied, StatusEffectExpired, StatusEffectKind, StatusEffectRefreshed,
+};
+use crate::timeline::{TimeStamp, TimelineClock};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Folder combat logs are exported to, relative to the working directory
+const COMBAT_LOG_DIR: &str = "combat_logs";
+
+/// Column names shared by the CSV header and the JSON keys
+const COLUMNS: [&str; 7] = [
+    "loop", "time", "event", "actor", "target", "detail", "amount",
+];
+
+/// Error types for exporting combat logs
+#[derive(Error, Debug)]
+pub enum CombatLogError {
+    #[error("Could not write combat log: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Nothing has happened in {arena} yet")]
+    Empty { arena: ArenaName },
+}
+
+/// What a combat log line records
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CombatLogKind {
+    Cast(AbilityType),
+    Damage { amount: f32, cause: DamageCause },
+    Heal(f32),
+    StatusApplied(StatusEffectKind),
+    StatusRefreshed { kind: StatusEffectKind, stacks: u8 },
+    StatusExpired(StatusEffectKind),
+    Death,
+}
+
+impl CombatLogKind {
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Value of the `event` column
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Cast(_) => "cast",
+            Self::Damage { .. } => "damage",
+            Self::Heal(_) => "heal",
+            Self::StatusApplied(_) => "status_applied",
+            Self::StatusRefreshed { .. } => "status_refreshed",
+            Self::StatusExpired(_) => "status_expired",
+            Self::Death => "death",
+        }
+    }
+
+    /// Value of the `detail` column - the ability, status effect or boss attack involved
+    #[must_use]
+    pub fn detail(self) -> String {
+        match self {
+            Self::Cast(ability) => ability.to_string(),
+            Self::Damage { cause, .. } => cause.to_string(),
+            Self::StatusApplied(kind) | Self::StatusExpired(kind) => format!("{kind:?}"),
+            Self::StatusRefreshed { kind, stacks } => format!("{kind:?} x{stacks}"),
+            Self::Heal(_) | Self::Death => String::new(),
+        }
+    }
+
+    /// Value of the `amount` column - health moved by damage and healing
+    #[must_use]
+    pub fn amount(self) -> Option<f32> {
+        match self {
+            Self::Damage { amount, .. } | Self::Heal(amount) => Some(amount),
+            _ => None,
+        }
+    }
+}
+
+/// One thing that happened in an arena, stamped with the arena's loop time (value type)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatLogEntry {
+    /// Arena loop the entry happened in, counting from 0 at startup
+    pub loop_index: u32,
+    pub at: TimeStamp,
+    /// Caster, damage or healing source, effect source - or whoever died or lost the
+    /// effect - labelled when the entry was logged, so it outlives the entity
+    pub actor: String,
+    pub target: Option<String>,
+    pub kind: CombatLogKind,
+}
+
+impl CombatLogEntry {
+    /// Column values in `COLUMNS` order
+    fn fields(&self) -> [String; 7] {
+        [
+            self.loop_index.to_string(),
+            format!("{:.2}", self.at.as_secs()),
+            self.kind.name().to_string(),
+            self.actor.clone(),
+            self.target.clone().unwrap_or_default(),
+            self.kind.detail(),
+            self.kind
+                .amount()
+                .map(|amount| format!("{amount:.1}"))
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+/// Readable line for the in-game view, e.g.
+/// `#0 12.5s The Bard damage -> Lyra (hero 3) Charge Strike 150.0`
+impl Display for CombatLogEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "#{} {} {} {}",
+            self.loop_index,
+            self.at,
+            self.actor,
+            self.kind.name()
+        )?;
+        if let Some(target) = &self.target {
+            write!(f, " -> {target}")?;
+        }
+        let detail = self.kind.detail();
+        if !detail.is_empty() {
+            write!(f, " {detail}")?;
+        }
+        if let Some(amount) = self.kind.amount() {
+            write!(f, " {amount:.1}")?;
+        }
+        Ok(())
+    }
+}
+
+/// File formats a combat log can be exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatLogFormat {
+    Csv,
+    Json,
+}
+
+impl CombatLogFormat {
+    pub const ALL: [Self; 2] = [Self::Csv, Self::Json];
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Append-only record of everything that happened in each arena this session
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CombatLog {
+    entries: [Vec<CombatLogEntry>; 9],
+    /// Loops each arena has started since the session began
+    loops: [u32; 9],
+}
+
+impl CombatLog {
+    pub fn push(
+        &mut self,
+        arena: ArenaName,
+        at: TimeStamp,
+        actor: String,
+        target: Option<String>,
+        kind: CombatLogKind,
+    ) {
+        let index = arena.as_u8() as usize;
+        self.entries[index].push(CombatLogEntry {
+            loop_index: self.loops[index],
+            at,
+            actor,
+            target,
+            kind,
+        });
+    }
+
+    /// Starts numbering an arena's entries from its next loop
+    pub fn start_loop(&mut self, arena: ArenaName) {
+        self.loops[arena.as_u8() as usize] += 1;
+    }
+
+    #[must_use]
+    pub fn entries(&self, arena: ArenaName) -> &[CombatLogEntry] {
+        &self.entries[arena.as_u8() as usize]
+    }
+
+    /// The arena's log as CSV with a header row
+    #[must_use]
+    pub fn to_csv(&self, arena: ArenaName) -> String {
+        let mut csv = COLUMNS.join(",");
+        csv.push('\n');
+        for entry in self.entries(arena) {
+            let fields = entry.fields().map(|field| csv_field(&field));
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// The arena's log as a JSON array of objects - every value is a string except
+    /// `loop`, `time` and `amount`, which are numbers, and
+    /// `target` and `amount`, which are null when unused
+    #[must_use]
+    pub fn to_json(&self, arena: ArenaName) -> String {
+        let rows: Vec<String> = self
+            .entries(arena)
+            .iter()
+            .map(|entry| {
+                let fields = entry.fields();
+                let values = [
+                    fields[0].clone(),
+                    fields[1].clone(),
+                    json_string(&fields[2]),
+                    json_string(&fields[3]),
+                    entry
+                        .target
+                        .as_ref()
+                        .map_or_else(|| "null".to_string(), |_| json_string(&fields[4])),
+                    json_string(&fields[5]),
+                    entry
+                        .kind
+                        .amount()
+                        .map_or_else(|| "null".to_string(), |_| fields[6].clone()),
+                ];
+                let pairs: Vec<String> = COLUMNS
+                    .iter()
+                    .zip(values)
+                    .map(|(column, value)| format!("\"{column}\": {value}"))
+                    .collect();
+                format!("  {{{}}}", pairs.join(", "))
+            })
+            .collect();
+        if rows.is_empty() {
+            return "[]\n".to_string();
+        }
+        format!("[\n{}\n]\n", rows.join(",\n"))
+    }
+
+    /// Default location of an arena's exported log
+    #[must_use]
+    pub fn path_for(arena: ArenaName, format: CombatLogFormat) -> PathBuf {
+        Path::new(COMBAT_LOG_DIR).join(format!("{arena:?}.{}", format.extension()))
+    }
+
+    pub fn export(
+        &self,
+        arena: ArenaName,
+        format: CombatLogFormat,
+        path: impl AsRef<Path>,
+    ) -> Result<(), CombatLogError> {
+        if self.entries(arena).is_empty() {
+            return Err(CombatLogError::Empty { arena });
+        }
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = match format {
+            CombatLogFormat::Csv => self.to_csv(arena),
+            CombatLogFormat::Json => self.to_json(arena),
+        };
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Quotes a CSV field that holds a comma, quote or line break, doubling its quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A JSON string literal, with quotes, backslashes and control characters escaped
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Appends entries to the log of whichever arena an entity is fighting in
+#[derive(SystemParam)]
+pub struct CombatLogWriter<'w, 's> {
+    log: ResMut<'w, CombatLog>,
+    parent_q: Query<'w, 's, &'static ChildOf>,
+    arena_q: Query<'w, 's, (&'static Arena, &'static TimelineClock)>,
+    label_q: Query<'w, 's, (Option<&'static HeroId>, Option<&'static Name>)>,
+}
+
+impl CombatLogWriter<'_, '_> {
+    /// How an entity appears in the log - roster heroes by name and id, bosses,
+    /// ghosts and minions by name, anything unnamed by its entity id
+    fn label(&self, entity: Entity) -> String {
+        match self.label_q.get(entity) {
+            Ok((Some(id), Some(name))) => format!("{name} (hero {})", id.0),
+            Ok((Some(id), None)) => format!("hero {}", id.0),
+            Ok((None, Some(name))) => name.to_string(),
+            _ => entity.to_string(),
+        }
+    }
+
+    /// Logs the entry in `located`'s arena at that arena's current loop time -
+    /// entities outside an arena are not logged
+    pub fn record(
+        &mut self,
+        located: Entity,
+        actor: Entity,
+        target: Option<Entity>,
+        kind: CombatLogKind,
+    ) {
+        let Some((arena, clock)) = self
+            .parent_q
+            .get(located)
+            .ok()
+            .and_then(|child_of| self.arena_q.get(child_of.parent()).ok())
+        else {
+            return;
+        };
+        let (arena, at) = (arena.0, clock.current());
+        let actor = self.label(actor);
+        let target = target.map(|target| self.label(target));
+        self.log.push(arena, at, actor, target, kind);
+    }
+}
+
+/// Logs every ability cast, live or replayed
+pub fn log_ability_casts(mut casts: EventReader<AbilityCast>, mut writer: CombatLogWriter) {
+    for cast in casts.read() {
+        writer.record(
+            cast.caster,
+            cast.caster,
+            None,
+            CombatLogKind::Cast(cast.ability),
+        );
+    }
+}
+
+/// Logs damage and healing that landed, and deaths
+pub fn log_health_changes(
+    mut dealt_events: EventReader<DamageDealt>,
+    mut healing_events: EventReader<HealingDone>,
+    mut death_events: EventReader<CombatantDied>,
+    mut writer: CombatLogWriter,
+) {
+    for event in dealt_events.read() {
+        writer.record(
+            event.target,
+            event.source,
+            Some(event.target),
+            CombatLogKind::Damage {
+                amount: event.amount,
+                cause: event.cause,
+            },
+        );
+    }
+    for event in healing_events.read() {
+        writer.record(
+            event.target,
+            event.source,
+            Some(event.target),
+            CombatLogKind::Heal(event.amount),
+        );
+    }
+    for event in death_events.read() {
+        writer.record(event.entity, event.entity, None, CombatLogKind::Death);
+    }
+}
+
+/// Logs status effects landing, refreshing and leaving
+pub fn log_status_changes(
+    mut applied_events: EventReader<StatusEffectApplied>,
+    mut refreshed_events: EventReader<StatusEffectRefreshed>,
+    mut expired_events: EventReader<StatusEffectExpired>,
+    mut writer: CombatLogWriter,
+) {
+    for event in applied_events.read() {
+        writer.record(
+            event.target,
+            event.source,
+            Some(event.target),
+            CombatLogKind::StatusApplied(event.kind),
+        );
+    }
+    for event in refreshed_events.read() {
+        writer.record(
+            event.target,
+            event.source,
+            Some(event.target),
+            CombatLogKind::StatusRefreshed {
+                kind: event.kind,
+                stacks: event.stacks,
+            },
+        );
+    }
+    for event in expired_events.read() {
+        writer.record(
+            event.target,
+            event.target,
+            None,
+            CombatLogKind::StatusExpired(event.kind),
+        );
+    }
+}
+
+/// Numbers each arena's entries by the loop they happened in
+pub fn count_combat_log_loops(
+    mut reset_events: EventReader<ArenaReset>,
+    mut log: ResMut<CombatLog>,
+) {
+    for event in reset_events.read() {
+        log.start_loop(event.arena);
+    }
+}
+
+/// Writes the current arena's combat log to disk in every format
+pub fn export_current_arena_combat_log(
+    actions: Res<ActionState>,
+    current: CurrentArenaEntity,
+    log: Res<CombatLog>,
+) {
+    if !actions.just_pressed(Action::ExportCombatLog) {
+        return;
+    }
+    let arena = current.name();
+    for format in CombatLogFormat::ALL {
+        let path = CombatLog::path_for(arena, format);
+        match log.export(arena, format, &path) {
+            Ok(()) => info!("Exported {arena} combat log to {}", path.display()),
+            Err(error) => warn!("Combat log export failed: {error}"),
+        }
+    }
+}
+
+/// Plugin recording an append-only combat log for every arena
+pub struct CombatLogPlugin;
+
+impl Plugin for CombatLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CombatLog>()
+            .add_event::<AbilityCast>()
+            .add_event::<DamageDealt>()
+            .add_event::<HealingDone>()
+            .add_event::<CombatantDied>()
+            .add_event::<StatusEffectApplied>()
+            .add_event::<StatusEffectRefreshed>()
+            .add_event::<StatusEffectExpired>()
+            .add_event::<ArenaReset>()
+            // Everything that fires during Update has settled by PostUpdate; a loop
+            // that wrapped this frame is counted before its first entries land
+            .add_systems(
+                PostUpdate,
+                (
+                    count_combat_log_loops,
+                    log_ability_casts,
+                    log_health_changes,
+                    log_status_changes,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, export_current_arena_combat_log);
+    }
+}
+
+#[cfg(test)]
+mod tests;
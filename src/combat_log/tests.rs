@@ -0,0 +1,171 @@
+use super::*;
+use crate::ability::HunterAbility;
+use crate::arena::{CurrentArena, create_test_arenas};
+use crate::combat::{CombatPlugin, DamageEvent, HealEvent, Health};
+use std::time::Duration;
+
+/// Arena clocks stopped at 12.5s, with the log and health plugins but nothing driving time
+fn combat_log_app() -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<ActionState>()
+        .add_plugins((CombatPlugin, CombatLogPlugin));
+
+    let world = app.world_mut();
+    let arenas = create_test_arenas(world, |_| {
+        let mut clock = TimelineClock::default();
+        clock.tick(Duration::from_secs_f32(12.5));
+        clock
+    });
+    world.insert_resource(CurrentArena(ArenaName::Labyrinth));
+    let (labyrinth, casino) = (
+        arenas.get(ArenaName::Labyrinth),
+        arenas.get(ArenaName::Casino),
+    );
+    (app, labyrinth, casino)
+}
+
+fn spawn_combatant(app: &mut App, arena: Entity, health: f32, name: &str) -> Entity {
+    app.world_mut()
+        .spawn((
+            Health::new(health),
+            Name::new(name.to_string()),
+            ChildOf(arena),
+        ))
+        .id()
+}
+
+#[test]
+fn test_casts_health_changes_statuses_and_deaths_land_in_their_arena_log() {
+    let (mut app, labyrinth, casino) = combat_log_app();
+    let hero = spawn_combatant(&mut app, labyrinth, 1_000.0, "Dean");
+    app.world_mut().entity_mut(hero).insert(HeroId(1));
+    let boss = spawn_combatant(&mut app, labyrinth, 150.0, "The Hunter");
+    app.world_mut().get_mut::<Health>(hero).unwrap().current = 900.0;
+    let bystander = spawn_combatant(&mut app, casino, 1_000.0, "Zephyr");
+    let auto_shot = AbilityType::Hunter(HunterAbility::AutoShot);
+
+    let world = app.world_mut();
+    world.send_event(AbilityCast {
+        caster: hero,
+        ability: auto_shot,
+    });
+    world.send_event(DamageEvent {
+        source: hero,
+        target: boss,
+        amount: 200.0,
+        cause: DamageCause::Ability(auto_shot),
+    });
+    world.send_event(HealEvent {
+        source: hero,
+        target: hero,
+        amount: 250.0,
+    });
+    world.send_event(StatusEffectApplied {
+        target: bystander,
+        source: bystander,
+        kind: StatusEffectKind::Haste,
+    });
+    app.update();
+
+    let log = app.world().resource::<CombatLog>();
+    let kinds: Vec<_> = log
+        .entries(ArenaName::Labyrinth)
+        .iter()
+        .map(|entry| entry.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            CombatLogKind::Cast(auto_shot),
+            CombatLogKind::Damage {
+                amount: 150.0,
+                cause: DamageCause::Ability(auto_shot)
+            },
+            CombatLogKind::Heal(100.0),
+            CombatLogKind::Death,
+        ]
+    );
+    let damage = &log.entries(ArenaName::Labyrinth)[1];
+    assert_eq!(damage.at, TimeStamp::new(12.5));
+    assert_eq!(damage.actor, "Dean (hero 1)");
+    assert_eq!(damage.target.as_deref(), Some("The Hunter"));
+    assert_eq!(damage.kind.detail(), "Hunter:AutoShot");
+    assert_eq!(log.entries(ArenaName::Labyrinth)[3].actor, "The Hunter");
+    assert_eq!(
+        log.entries(ArenaName::Casino)[0].kind,
+        CombatLogKind::StatusApplied(StatusEffectKind::Haste)
+    );
+}
+
+#[test]
+fn test_entries_are_numbered_by_the_loop_they_happened_in() {
+    let (mut app, labyrinth, _) = combat_log_app();
+    let hero = spawn_combatant(&mut app, labyrinth, 1_000.0, "Dean");
+    let cast = AbilityCast {
+        caster: hero,
+        ability: AbilityType::Hunter(HunterAbility::AutoShot),
+    };
+    app.world_mut().send_event(cast.clone());
+    app.update();
+    app.world_mut().send_event(ArenaReset {
+        arena: ArenaName::Labyrinth,
+    });
+    app.world_mut().send_event(cast);
+    app.update();
+
+    let log = app.world().resource::<CombatLog>();
+    let loops: Vec<_> = log
+        .entries(ArenaName::Labyrinth)
+        .iter()
+        .map(|entry| entry.loop_index)
+        .collect();
+    assert_eq!(loops, [0, 1]);
+}
+
+#[test]
+fn test_csv_and_json_exports_carry_the_same_columns() {
+    let hero = r#"Ada "Quill", Esq (hero 4)"#;
+    let mut log = CombatLog::default();
+    log.push(
+        ArenaName::Gala,
+        TimeStamp::new(3.0),
+        hero.to_string(),
+        None,
+        CombatLogKind::Cast(AbilityType::Hunter(HunterAbility::AutoShot)),
+    );
+    log.push(
+        ArenaName::Gala,
+        TimeStamp::new(3.25),
+        "The Bard".to_string(),
+        Some(hero.to_string()),
+        CombatLogKind::Damage {
+            amount: 150.0,
+            cause: DamageCause::BossAction("Charge Strike"),
+        },
+    );
+
+    assert_eq!(
+        log.to_csv(ArenaName::Gala),
+        "loop,time,event,actor,target,detail,amount\n\
+         0,3.00,cast,\"Ada \"\"Quill\"\", Esq (hero 4)\",,Hunter:AutoShot,\n\
+         0,3.25,damage,The Bard,\"Ada \"\"Quill\"\", Esq (hero 4)\",Charge Strike,150.0\n"
+    );
+    assert_eq!(
+        log.to_json(ArenaName::Gala),
+        "[\n  \
+         {\"loop\": 0, \"time\": 3.00, \"event\": \"cast\", \"actor\": \"Ada \\\"Quill\\\", Esq (hero 4)\", \"target\": null, \"detail\": \"Hunter:AutoShot\", \"amount\": null},\n  \
+         {\"loop\": 0, \"time\": 3.25, \"event\": \"damage\", \"actor\": \"The Bard\", \"target\": \"Ada \\\"Quill\\\", Esq (hero 4)\", \"detail\": \"Charge Strike\", \"amount\": 150.0}\n\
+         ]\n"
+    );
+    assert_eq!(
+        log.entries(ArenaName::Gala)[1].to_string(),
+        "#0 3.2s The Bard damage -> Ada \"Quill\", Esq (hero 4) Charge Strike 150.0"
+    );
+    assert!(matches!(
+        log.export(ArenaName::Labyrinth, CombatLogFormat::Csv, "unused.csv"),
+        Err(CombatLogError::Empty {
+            arena: ArenaName::Labyrinth
+        })
+    ));
+}
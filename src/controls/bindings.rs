@@ -0,0 +1,451 @@
+use bevy::prelude::{GamepadButton, KeyCode, Resource};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Named player intents - systems read these instead of raw inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    CycleHero,
+    Ability1,
+    Ability2,
+    Ability3,
+    Ability4,
+    Record,
+    Accept,
+    ToggleOverview,
+    PreviousArena,
+    NextArena,
+    ExportReplay,
+    ImportReplay,
+    Recruit,
+    CycleDifficulty,
+    Interact,
+    ToggleCombatLog,
+    ExportCombatLog,
+    ToggleMeters,
+}
+
+impl Action {
+    pub const ALL: [Self; 22] = [
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::CycleHero,
+        Self::Ability1,
+        Self::Ability2,
+        Self::Ability3,
+        Self::Ability4,
+        Self::Record,
+        Self::Accept,
+        Self::ToggleOverview,
+        Self::PreviousArena,
+        Self::NextArena,
+        Self::ExportReplay,
+        Self::ImportReplay,
+        Self::Recruit,
+        Self::CycleDifficulty,
+        Self::Interact,
+        Self::ToggleCombatLog,
+        Self::ExportCombatLog,
+        Self::ToggleMeters,
+    ];
+
+    /// Actions that cast the ability in each bar slot, left to right
+    pub const ABILITY_SLOTS: [Self; 4] = [
+        Self::Ability1,
+        Self::Ability2,
+        Self::Ability3,
+        Self::Ability4,
+    ];
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::MoveUp => "MoveUp",
+            Self::MoveDown => "MoveDown",
+            Self::MoveLeft => "MoveLeft",
+            Self::MoveRight => "MoveRight",
+            Self::CycleHero => "CycleHero",
+            Self::Ability1 => "Ability1",
+            Self::Ability2 => "Ability2",
+            Self::Ability3 => "Ability3",
+            Self::Ability4 => "Ability4",
+            Self::Record => "Record",
+            Self::Accept => "Accept",
+            Self::ToggleOverview => "ToggleOverview",
+            Self::PreviousArena => "PreviousArena",
+            Self::NextArena => "NextArena",
+            Self::ExportReplay => "ExportReplay",
+            Self::ImportReplay => "ImportReplay",
+            Self::Recruit => "Recruit",
+            Self::CycleDifficulty => "CycleDifficulty",
+            Self::Interact => "Interact",
+            Self::ToggleCombatLog => "ToggleCombatLog",
+            Self::ExportCombatLog => "ExportCombatLog",
+            Self::ToggleMeters => "ToggleMeters",
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Bindings used when the config file does not mention the action
+    #[must_use]
+    pub const fn default_bindings(self) -> &'static [Binding] {
+        use Binding::{Key, Pad};
+        match self {
+            Self::MoveUp => &[Key(KeyCode::KeyW), Pad(GamepadButton::DPadUp)],
+            Self::MoveDown => &[Key(KeyCode::KeyS), Pad(GamepadButton::DPadDown)],
+            Self::MoveLeft => &[Key(KeyCode::KeyA), Pad(GamepadButton::DPadLeft)],
+            Self::MoveRight => &[Key(KeyCode::KeyD), Pad(GamepadButton::DPadRight)],
+            Self::CycleHero => &[Key(KeyCode::Tab), Pad(GamepadButton::Select)],
+            Self::Ability1 => &[
+                Key(KeyCode::Digit1),
+                Key(KeyCode::Numpad1),
+                Pad(GamepadButton::South),
+            ],
+            Self::Ability2 => &[
+                Key(KeyCode::Digit2),
+                Key(KeyCode::Numpad2),
+                Pad(GamepadButton::East),
+            ],
+            Self::Ability3 => &[
+                Key(KeyCode::Digit3),
+                Key(KeyCode::Numpad3),
+                Pad(GamepadButton::West),
+            ],
+            Self::Ability4 => &[
+                Key(KeyCode::Digit4),
+                Key(KeyCode::Numpad4),
+                Pad(GamepadButton::North),
+            ],
+            Self::Record => &[Key(KeyCode::KeyR), Pad(GamepadButton::Start)],
+            Self::Accept => &[Key(KeyCode::Enter), Pad(GamepadButton::RightTrigger2)],
+            Self::ToggleOverview => &[Key(KeyCode::KeyP), Pad(GamepadButton::LeftTrigger2)],
+            Self::PreviousArena => &[Key(KeyCode::BracketLeft), Pad(GamepadButton::LeftTrigger)],
+            Self::NextArena => &[Key(KeyCode::BracketRight), Pad(GamepadButton::RightTrigger)],
+            Self::ExportReplay => &[Key(KeyCode::F5)],
+            Self::ImportReplay => &[Key(KeyCode::F9)],
+            Self::Recruit => &[Key(KeyCode::KeyG)],
+            Self::CycleDifficulty => &[Key(KeyCode::KeyT)],
+            Self::Interact => &[Key(KeyCode::KeyC)],
+            Self::ToggleCombatLog => &[Key(KeyCode::KeyL)],
+            Self::ExportCombatLog => &[Key(KeyCode::F6)],
+            Self::ToggleMeters => &[Key(KeyCode::KeyM)],
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Action {
+    type Err = BindingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.name() == s)
+            .ok_or_else(|| BindingsError::UnknownAction {
+                name: s.to_string(),
+            })
+    }
+}
+
+/// Keys that can appear in the bindings file, by their `KeyCode` variant name
+const KEY_NAMES: [(&str, KeyCode); 75] = [
+    ("KeyA", KeyCode::KeyA),
+    ("KeyB", KeyCode::KeyB),
+    ("KeyC", KeyCode::KeyC),
+    ("KeyD", KeyCode::KeyD),
+    ("KeyE", KeyCode::KeyE),
+    ("KeyF", KeyCode::KeyF),
+    ("KeyG", KeyCode::KeyG),
+    ("KeyH", KeyCode::KeyH),
+    ("KeyI", KeyCode::KeyI),
+    ("KeyJ", KeyCode::KeyJ),
+    ("KeyK", KeyCode::KeyK),
+    ("KeyL", KeyCode::KeyL),
+    ("KeyM", KeyCode::KeyM),
+    ("KeyN", KeyCode::KeyN),
+    ("KeyO", KeyCode::KeyO),
+    ("KeyP", KeyCode::KeyP),
+    ("KeyQ", KeyCode::KeyQ),
+    ("KeyR", KeyCode::KeyR),
+    ("KeyS", KeyCode::KeyS),
+    ("KeyT", KeyCode::KeyT),
+    ("KeyU", KeyCode::KeyU),
+    ("KeyV", KeyCode::KeyV),
+    ("KeyW", KeyCode::KeyW),
+    ("KeyX", KeyCode::KeyX),
+    ("KeyY", KeyCode::KeyY),
+    ("KeyZ", KeyCode::KeyZ),
+    ("Digit0", KeyCode::Digit0),
+    ("Digit1", KeyCode::Digit1),
+    ("Digit2", KeyCode::Digit2),
+    ("Digit3", KeyCode::Digit3),
+    ("Digit4", KeyCode::Digit4),
+    ("Digit5", KeyCode::Digit5),
+    ("Digit6", KeyCode::Digit6),
+    ("Digit7", KeyCode::Digit7),
+    ("Digit8", KeyCode::Digit8),
+    ("Digit9", KeyCode::Digit9),
+    ("Numpad0", KeyCode::Numpad0),
+    ("Numpad1", KeyCode::Numpad1),
+    ("Numpad2", KeyCode::Numpad2),
+    ("Numpad3", KeyCode::Numpad3),
+    ("Numpad4", KeyCode::Numpad4),
+    ("Numpad5", KeyCode::Numpad5),
+    ("Numpad6", KeyCode::Numpad6),
+    ("Numpad7", KeyCode::Numpad7),
+    ("Numpad8", KeyCode::Numpad8),
+    ("Numpad9", KeyCode::Numpad9),
+    ("ArrowUp", KeyCode::ArrowUp),
+    ("ArrowDown", KeyCode::ArrowDown),
+    ("ArrowLeft", KeyCode::ArrowLeft),
+    ("ArrowRight", KeyCode::ArrowRight),
+    ("BracketLeft", KeyCode::BracketLeft),
+    ("BracketRight", KeyCode::BracketRight),
+    ("Tab", KeyCode::Tab),
+    ("Space", KeyCode::Space),
+    ("Enter", KeyCode::Enter),
+    ("Escape", KeyCode::Escape),
+    ("Backspace", KeyCode::Backspace),
+    ("ShiftLeft", KeyCode::ShiftLeft),
+    ("ShiftRight", KeyCode::ShiftRight),
+    ("ControlLeft", KeyCode::ControlLeft),
+    ("ControlRight", KeyCode::ControlRight),
+    ("AltLeft", KeyCode::AltLeft),
+    ("AltRight", KeyCode::AltRight),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+];
+
+/// Gamepad buttons that can appear in the bindings file
+const PAD_NAMES: [(&str, GamepadButton); 16] = [
+    ("South", GamepadButton::South),
+    ("East", GamepadButton::East),
+    ("North", GamepadButton::North),
+    ("West", GamepadButton::West),
+    ("LeftTrigger", GamepadButton::LeftTrigger),
+    ("LeftTrigger2", GamepadButton::LeftTrigger2),
+    ("RightTrigger", GamepadButton::RightTrigger),
+    ("RightTrigger2", GamepadButton::RightTrigger2),
+    ("Select", GamepadButton::Select),
+    ("Start", GamepadButton::Start),
+    ("LeftThumb", GamepadButton::LeftThumb),
+    ("RightThumb", GamepadButton::RightThumb),
+    ("DPadUp", GamepadButton::DPadUp),
+    ("DPadDown", GamepadButton::DPadDown),
+    ("DPadLeft", GamepadButton::DPadLeft),
+    ("DPadRight", GamepadButton::DPadRight),
+];
+
+/// One physical input bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binding {
+    Key(KeyCode),
+    Pad(GamepadButton),
+}
+
+impl Binding {
+    /// Input name as written after `Key:` or `Pad:`, e.g. `Enter` or `South`
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Key(key) => KEY_NAMES
+                .iter()
+                .find(|(_, code)| *code == key)
+                .map_or("?", |(name, _)| *name),
+            Self::Pad(button) => PAD_NAMES
+                .iter()
+                .find(|(_, pad)| *pad == button)
+                .map_or("?", |(name, _)| *name),
+        }
+    }
+
+    /// Short on-screen label, e.g. `A` for `KeyA` and `1` for `Digit1`
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        let name = self.name();
+        name.strip_prefix("Key")
+            .or_else(|| name.strip_prefix("Digit"))
+            .unwrap_or(name)
+    }
+}
+
+impl Display for Binding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Key(_) => write!(f, "Key:{}", self.name()),
+            Self::Pad(_) => write!(f, "Pad:{}", self.name()),
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = BindingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = || BindingsError::UnknownBinding {
+            binding: s.to_string(),
+        };
+        match s.split_once(':') {
+            Some(("Key", name)) => KEY_NAMES
+                .iter()
+                .find(|(key_name, _)| *key_name == name)
+                .map(|(_, key)| Self::Key(*key))
+                .ok_or_else(unknown),
+            Some(("Pad", name)) => PAD_NAMES
+                .iter()
+                .find(|(pad_name, _)| *pad_name == name)
+                .map(|(_, button)| Self::Pad(*button))
+                .ok_or_else(unknown),
+            _ => Err(unknown()),
+        }
+    }
+}
+
+/// Error types for loading input bindings
+#[derive(Error, Debug)]
+pub enum BindingsError {
+    #[error("Could not read bindings file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unknown action: {name}")]
+    UnknownAction { name: String },
+    #[error("Unknown binding: {binding} (expected Key:<KeyCode> or Pad:<GamepadButton>)")]
+    UnknownBinding { binding: String },
+    #[error("Line {line}: expected `Action = Binding, Binding`")]
+    Malformed { line: usize },
+    #[error("{binding} is bound to both {first} and {second}")]
+    Duplicate {
+        binding: Binding,
+        first: Action,
+        second: Action,
+    },
+}
+
+/// Action-to-input map, loaded from the bindings config file
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct InputBindings(pub Vec<(Action, Vec<Binding>)>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self(
+            Action::ALL
+                .into_iter()
+                .map(|action| (action, action.default_bindings().to_vec()))
+                .collect(),
+        )
+    }
+}
+
+impl InputBindings {
+    /// Path of the bindings file, relative to the working directory
+    pub const CONFIG_PATH: &'static str = "assets/config/bindings.cfg";
+
+    /// Reads a bindings file; actions it leaves out keep their defaults
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BindingsError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    #[must_use]
+    pub fn for_action(&self, action: Action) -> &[Binding] {
+        self.0
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, bindings)| bindings.as_slice())
+    }
+
+    /// Label of the action's first binding for on-screen prompts
+    #[must_use]
+    pub fn label(&self, action: Action) -> &'static str {
+        self.for_action(action)
+            .first()
+            .map_or("unbound", |binding| binding.label())
+    }
+
+    /// Fails on the first input bound more than once, so one press never fires two actions
+    pub fn check_duplicates(&self) -> Result<(), BindingsError> {
+        let bound: Vec<(Action, Binding)> = self
+            .0
+            .iter()
+            .flat_map(|(action, bindings)| bindings.iter().map(|binding| (*action, *binding)))
+            .collect();
+        for (index, (second, binding)) in bound.iter().enumerate() {
+            if let Some((first, _)) = bound[..index].iter().find(|(_, other)| other == binding) {
+                return Err(BindingsError::Duplicate {
+                    binding: *binding,
+                    first: *first,
+                    second: *second,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces every binding for an action
+    pub fn rebind(&mut self, action: Action, bindings: Vec<Binding>) {
+        match self.0.iter_mut().find(|(bound, _)| *bound == action) {
+            Some((_, current)) => *current = bindings,
+            None => self.0.push((action, bindings)),
+        }
+    }
+}
+
+/// Config file format: one `Action = Binding, Binding` line per action, `#` comments
+impl Display for InputBindings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (action, bindings) in &self.0 {
+            let bindings: Vec<String> = bindings.iter().map(ToString::to_string).collect();
+            writeln!(f, "{action} = {}", bindings.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for InputBindings {
+    type Err = BindingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bindings = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (action, inputs) = line
+                .split_once('=')
+                .ok_or(BindingsError::Malformed { line: index + 1 })?;
+            let action: Action = action.trim().parse()?;
+            let inputs = inputs
+                .split(',')
+                .map(str::trim)
+                .filter(|input| !input.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<Binding>, _>>()?;
+            bindings.rebind(action, inputs);
+        }
+        bindings.check_duplicates()?;
+        Ok(bindings)
+    }
+}
@@ -0,0 +1,81 @@
+mod bindings;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+pub use bindings::*;
+
+/// Held / just-pressed state of every action for the current frame
+#[derive(Resource, Debug, Default)]
+pub struct ActionState {
+    pressed: Vec<Action>,
+    just_pressed: Vec<Action>,
+}
+
+impl ActionState {
+    #[must_use]
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    #[must_use]
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Records whether the action is held this frame, flagging fresh presses
+    pub fn set(&mut self, action: Action, held: bool) {
+        let was_held = self.pressed(action);
+        self.pressed.retain(|pressed| *pressed != action);
+        self.just_pressed.retain(|pressed| *pressed != action);
+        if held {
+            self.pressed.push(action);
+            if !was_held {
+                self.just_pressed.push(action);
+            }
+        }
+    }
+}
+
+/// Maps keyboard and gamepad input onto actions through the bindings
+pub fn update_action_state(
+    bindings: Res<InputBindings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut actions: ResMut<ActionState>,
+) {
+    for action in Action::ALL {
+        let held = bindings
+            .for_action(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(key) => keyboard.pressed(key),
+                Binding::Pad(button) => gamepads.iter().any(|gamepad| gamepad.pressed(button)),
+            });
+        actions.set(action, held);
+    }
+}
+
+/// Loads the bindings file, falling back to the built-in layout
+fn load_bindings() -> InputBindings {
+    match InputBindings::load(InputBindings::CONFIG_PATH) {
+        Ok(bindings) => bindings,
+        Err(error) => {
+            warn!("Using default input bindings: {error}");
+            InputBindings::default()
+        }
+    }
+}
+
+/// Plugin for the action-mapping layer
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_bindings())
+            .init_resource::<ActionState>()
+            .add_systems(PreUpdate, update_action_state.after(InputSystem));
+    }
+}
+
+#[cfg(test)]
+mod tests;
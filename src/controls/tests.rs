@@ -0,0 +1,116 @@
+use super::*;
+use bevy::app::{App, PreUpdate};
+
+#[test]
+fn test_default_bindings_round_trip_through_the_config_format() {
+    let defaults = InputBindings::default();
+    let parsed: InputBindings = defaults.to_string().parse().unwrap();
+    assert_eq!(parsed, defaults);
+}
+
+#[test]
+fn test_shipped_config_matches_the_defaults() {
+    let shipped = InputBindings::load(InputBindings::CONFIG_PATH).unwrap();
+    assert_eq!(shipped, InputBindings::default());
+}
+
+#[test]
+fn test_config_overrides_listed_actions_and_keeps_the_rest() {
+    let bindings: InputBindings = "# arrows for movement\nMoveUp = Key:ArrowUp, Pad:DPadUp\n"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        bindings.for_action(Action::MoveUp),
+        [
+            Binding::Key(KeyCode::ArrowUp),
+            Binding::Pad(GamepadButton::DPadUp)
+        ]
+    );
+    assert_eq!(
+        bindings.for_action(Action::CycleHero),
+        Action::CycleHero.default_bindings()
+    );
+}
+
+#[test]
+fn test_config_errors_name_the_problem() {
+    assert!(matches!(
+        "Jump = Key:Space".parse::<InputBindings>(),
+        Err(BindingsError::UnknownAction { name }) if name == "Jump"
+    ));
+    assert!(matches!(
+        "Record = Key:F13".parse::<InputBindings>(),
+        Err(BindingsError::UnknownBinding { binding }) if binding == "Key:F13"
+    ));
+    assert!(matches!(
+        "\nRecord Key:KeyR".parse::<InputBindings>(),
+        Err(BindingsError::Malformed { line: 2 })
+    ));
+    assert!(matches!(
+        "Accept = Key:Digit1".parse::<InputBindings>(),
+        Err(BindingsError::Duplicate {
+            binding: Binding::Key(KeyCode::Digit1),
+            first: Action::Ability1,
+            second: Action::Accept,
+        })
+    ));
+}
+
+#[test]
+fn test_no_two_actions_share_a_default_binding() {
+    InputBindings::default().check_duplicates().unwrap();
+}
+
+#[test]
+fn test_keyboard_and_gamepad_drive_the_same_action() {
+    let mut app = App::new();
+    app.init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<InputBindings>()
+        .init_resource::<ActionState>()
+        .add_systems(PreUpdate, update_action_state);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::KeyR);
+    app.update();
+    let actions = app.world().resource::<ActionState>();
+    assert!(actions.just_pressed(Action::Record));
+    assert!(!actions.just_pressed(Action::Accept));
+
+    // Still held on the next frame - pressed but no longer fresh
+    app.update();
+    let actions = app.world().resource::<ActionState>();
+    assert!(actions.pressed(Action::Record));
+    assert!(!actions.just_pressed(Action::Record));
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(KeyCode::KeyR);
+    let mut gamepad = Gamepad::default();
+    gamepad.digital_mut().press(GamepadButton::Start);
+    app.world_mut().spawn(gamepad);
+    app.update();
+    assert!(
+        app.world()
+            .resource::<ActionState>()
+            .pressed(Action::Record)
+    );
+}
+
+#[test]
+fn test_prompt_labels_follow_the_first_binding() {
+    let mut bindings = InputBindings::default();
+    assert_eq!(bindings.label(Action::Ability1), "1");
+
+    bindings.rebind(
+        Action::Accept,
+        vec![Binding::Key(KeyCode::Enter), Binding::Key(KeyCode::KeyY)],
+    );
+    assert_eq!(bindings.label(Action::Accept), "Enter");
+
+    bindings.rebind(Action::Accept, vec![Binding::Key(KeyCode::KeyY)]);
+    assert_eq!(bindings.label(Action::Accept), "Y");
+
+    bindings.rebind(Action::Accept, Vec::new());
+    assert_eq!(bindings.label(Action::Accept), "unbound");
+}
@@ -0,0 +1,206 @@
+use crate::arena::{ArenaEntities, CurrentArenaEntity};
+use crate::boss::{BOSS_MAX_HEALTH, BossPhase, BossPhaseChanged, Decoy, update_boss_phase};
+use crate::character::Boss;
+use crate::combat::{DamageMultiplier, Health};
+use crate::controls::{Action, ActionState};
+use crate::minion::{MinionKind, SpawnMinion, despawn_minions_on_phase_change};
+use crate::timeline::GridPositionComponent;
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Tiles, relative to the boss, that phase-change adds are summoned on
+const ADD_OFFSETS: [IVec2; 4] = [
+    IVec2::new(-2, 0),
+    IVec2::new(2, 0),
+    IVec2::new(0, -2),
+    IVec2::new(0, 2),
+];
+
+/// Per-arena challenge tier - scales the boss and unlocks its later phases and adds
+/// RULE 26 COMPLIANCE: One state component per state machine
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Difficulty {
+    #[default]
+    Normal,
+    Heroic,
+    Mythic,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 3] = [Self::Normal, Self::Heroic, Self::Mythic];
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Heroic => "Heroic",
+            Self::Mythic => "Mythic",
+        }
+    }
+
+    /// Next tier, wrapping from Mythic back to Normal
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::Heroic,
+            Self::Heroic => Self::Mythic,
+            Self::Mythic => Self::Normal,
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn boss_max_health(self) -> f32 {
+        match self {
+            Self::Normal => BOSS_MAX_HEALTH,
+            Self::Heroic => BOSS_MAX_HEALTH * 1.5,
+            Self::Mythic => BOSS_MAX_HEALTH * 2.5,
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn boss_damage_multiplier(self) -> f32 {
+        match self {
+            Self::Normal => 1.0,
+            Self::Heroic => 1.25,
+            Self::Mythic => 1.5,
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Last phase the boss can reach - only Mythic runs all four
+    #[must_use]
+    pub const fn final_phase(self) -> BossPhase {
+        match self {
+            Self::Normal => BossPhase::Two,
+            Self::Heroic => BossPhase::Three,
+            Self::Mythic => BossPhase::Four,
+        }
+    }
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Adds the boss summons each time it changes phase - Normal bosses fight alone
+    #[must_use]
+    pub const fn phase_adds(self) -> &'static [MinionKind] {
+        match self {
+            Self::Normal => &[],
+            Self::Heroic => &[MinionKind::Spiderling, MinionKind::Spiderling],
+            Self::Mythic => &[
+                MinionKind::Spiderling,
+                MinionKind::Spiderling,
+                MinionKind::Brood,
+            ],
+        }
+    }
+
+    /// Boss stats for this tier, at full health in the opening phase
+    pub fn boss_bundle(self) -> impl Bundle {
+        (
+            Health::new(self.boss_max_health()),
+            BossPhase::default(),
+            DamageMultiplier(self.boss_damage_multiplier()),
+        )
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.name())
+    }
+}
+
+/// Error for names that match no difficulty tier
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Unknown difficulty: {0} (expected Normal, Heroic or Mythic)")]
+pub struct UnknownDifficulty(pub String);
+
+impl FromStr for Difficulty {
+    type Err = UnknownDifficulty;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|tier| tier.name() == s)
+            .ok_or_else(|| UnknownDifficulty(s.to_string()))
+    }
+}
+
+/// Steps the current arena to the next difficulty tier
+pub fn cycle_current_arena_difficulty(
+    actions: Res<ActionState>,
+    current: CurrentArenaEntity,
+    mut arena_q: Query<&mut Difficulty>,
+) {
+    if !actions.just_pressed(Action::CycleDifficulty) {
+        return;
+    }
+    if let Ok(mut difficulty) = arena_q.get_mut(current.get()) {
+        *difficulty = difficulty.next();
+        info!("{} is now on {} difficulty", current.name(), *difficulty);
+    }
+}
+
+/// Restarts an arena's boss with its tier's stats whenever the tier is set
+pub fn apply_difficulty_to_bosses(
+    mut commands: Commands,
+    arena_q: Query<(&Difficulty, &Children), Changed<Difficulty>>,
+    boss_q: Query<Entity, (With<Boss>, Without<Decoy>)>,
+) {
+    for (difficulty, children) in arena_q.iter() {
+        for boss in boss_q.iter_many(children) {
+            commands.entity(boss).insert(difficulty.boss_bundle());
+        }
+    }
+}
+
+/// Heroic and Mythic bosses call in adds around themselves on every phase change
+pub fn summon_phase_adds(
+    mut phase_events: EventReader<BossPhaseChanged>,
+    arena_entities: Res<ArenaEntities>,
+    arena_q: Query<&Difficulty>,
+    boss_q: Query<&GridPositionComponent, With<Boss>>,
+    mut spawn_events: EventWriter<SpawnMinion>,
+) {
+    for event in phase_events.read() {
+        let Ok(difficulty) = arena_q.get(arena_entities.get(event.arena)) else {
+            continue;
+        };
+        let Ok(position) = boss_q.get(event.boss) else {
+            continue;
+        };
+        for (kind, offset) in difficulty.phase_adds().iter().zip(ADD_OFFSETS) {
+            spawn_events.write(SpawnMinion {
+                arena: event.arena,
+                kind: *kind,
+                tile: position.0 + offset,
+            });
+        }
+    }
+}
+
+/// Plugin for per-arena difficulty tiers
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BossPhaseChanged>()
+            .add_event::<SpawnMinion>()
+            .add_systems(
+                Update,
+                (
+                    (cycle_current_arena_difficulty, apply_difficulty_to_bosses).chain(),
+                    // Adds arrive after the phase change has cleared the previous wave
+                    summon_phase_adds
+                        .after(update_boss_phase)
+                        .before(despawn_minions_on_phase_change),
+                ),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,171 @@
+use super::*;
+use crate::arena::{ArenaName, create_test_arenas};
+use crate::character::{Character, Ghost};
+use crate::controls::ActionState;
+use crate::minion::{Minion, spawn_minions};
+use crate::recording::{
+    AcceptRecordingPrompt, GlobalPauseReason, GlobalRecordingMode, RecordingPlugin,
+};
+use crate::selectors::Active;
+use crate::timeline::{
+    DraftTimeline, OffTierRecording, PublishTimeline, ReplayOrigin, TimelineManager,
+    flag_off_tier_recordings,
+};
+use bevy::app::{App, Update};
+
+/// Spawns all nine arenas, with the given tiers on Labyrinth and Gala
+fn spawn_arenas(app: &mut App, labyrinth: Difficulty, gala: Difficulty) -> (Entity, Entity) {
+    let entities = create_test_arenas(app.world_mut(), |name| match name {
+        ArenaName::Labyrinth => labyrinth,
+        ArenaName::Gala => gala,
+        _ => Difficulty::default(),
+    });
+    (
+        entities.get(ArenaName::Labyrinth),
+        entities.get(ArenaName::Gala),
+    )
+}
+
+fn spawn_boss(app: &mut App, arena: Entity, difficulty: Difficulty) -> Entity {
+    app.world_mut()
+        .spawn((
+            Boss,
+            difficulty.boss_bundle(),
+            GridPositionComponent(IVec2::new(32, 10)),
+            ChildOf(arena),
+        ))
+        .id()
+}
+
+#[test]
+fn test_tiers_scale_the_boss_and_cap_its_phases() {
+    for tier in Difficulty::ALL {
+        assert_eq!(tier.to_string().parse::<Difficulty>(), Ok(tier));
+    }
+    assert!("Legendary".parse::<Difficulty>().is_err());
+
+    let mut app = App::new();
+    app.add_event::<BossPhaseChanged>()
+        .add_systems(Update, update_boss_phase);
+    let (labyrinth, gala) = spawn_arenas(&mut app, Difficulty::Normal, Difficulty::Mythic);
+    let normal = spawn_boss(&mut app, labyrinth, Difficulty::Normal);
+    let mythic = spawn_boss(&mut app, gala, Difficulty::Mythic);
+    assert_eq!(
+        app.world().get::<Health>(mythic).unwrap().max,
+        BOSS_MAX_HEALTH * 2.5
+    );
+
+    for boss in [normal, mythic] {
+        let mut health = app.world_mut().get_mut::<Health>(boss).unwrap();
+        health.current = health.max * 0.1;
+    }
+    app.update();
+    assert_eq!(app.world().get::<BossPhase>(normal), Some(&BossPhase::Two));
+    assert_eq!(app.world().get::<BossPhase>(mythic), Some(&BossPhase::Four));
+}
+
+#[test]
+fn test_switching_tier_restarts_the_boss_and_benches_off_tier_ghosts() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (apply_difficulty_to_bosses, flag_off_tier_recordings),
+    );
+    let (labyrinth, _) = spawn_arenas(&mut app, Difficulty::Normal, Difficulty::Normal);
+    let boss = spawn_boss(&mut app, labyrinth, Difficulty::Normal);
+    let mut manager = TimelineManager::new();
+    manager.set_timeline(
+        ArenaName::Labyrinth,
+        PublishTimeline::from_draft(DraftTimeline::new()),
+    );
+    let ghost = app
+        .world_mut()
+        .spawn((
+            Ghost,
+            ReplayOrigin {
+                arena: ArenaName::Labyrinth,
+                tile: IVec2::ZERO,
+            },
+            manager,
+            ChildOf(labyrinth),
+        ))
+        .id();
+    app.update();
+    assert!(!app.world().entity(ghost).contains::<OffTierRecording>());
+
+    app.world_mut().get_mut::<Health>(boss).unwrap().current = 10.0;
+    *app.world_mut().get_mut::<Difficulty>(labyrinth).unwrap() = Difficulty::Heroic;
+    app.update();
+    let health = app.world().get::<Health>(boss).unwrap();
+    assert_eq!((health.current, health.max), (15_000.0, 15_000.0));
+    assert_eq!(
+        app.world().get::<DamageMultiplier>(boss),
+        Some(&DamageMultiplier(1.25))
+    );
+    assert!(app.world().entity(ghost).contains::<OffTierRecording>());
+
+    *app.world_mut().get_mut::<Difficulty>(labyrinth).unwrap() = Difficulty::Normal;
+    app.update();
+    assert!(!app.world().entity(ghost).contains::<OffTierRecording>());
+}
+
+#[test]
+fn test_committed_recordings_carry_the_arena_tier() {
+    let mut app = App::new();
+    app.add_plugins(RecordingPlugin)
+        .init_resource::<ActionState>()
+        .init_resource::<Time>()
+        .insert_resource(DraftTimeline::new())
+        .insert_resource(GlobalRecordingMode::Paused(
+            GlobalPauseReason::CommitRequested,
+        ));
+    let (_, gala) = spawn_arenas(&mut app, Difficulty::Normal, Difficulty::Mythic);
+    let hero = app
+        .world_mut()
+        .spawn((Character, Active, TimelineManager::new(), ChildOf(gala)))
+        .id();
+
+    app.world_mut().send_event(AcceptRecordingPrompt);
+    app.update();
+
+    let manager = app.world().get::<TimelineManager>(hero).unwrap();
+    let timeline = manager
+        .get_timeline(ArenaName::Gala)
+        .expect("committing publishes the draft");
+    assert_eq!(timeline.difficulty, Difficulty::Mythic);
+}
+
+#[test]
+fn test_higher_tiers_summon_adds_on_phase_change() {
+    let mut app = App::new();
+    app.add_event::<BossPhaseChanged>()
+        .add_event::<SpawnMinion>()
+        .add_systems(
+            Update,
+            (
+                update_boss_phase,
+                summon_phase_adds,
+                despawn_minions_on_phase_change,
+                spawn_minions,
+            )
+                .chain(),
+        );
+    let (labyrinth, gala) = spawn_arenas(&mut app, Difficulty::Normal, Difficulty::Heroic);
+    for (arena, tier) in [(labyrinth, Difficulty::Normal), (gala, Difficulty::Heroic)] {
+        let boss = spawn_boss(&mut app, arena, tier);
+        let mut health = app.world_mut().get_mut::<Health>(boss).unwrap();
+        health.current = health.max * 0.7;
+    }
+    app.update();
+
+    let mut minion_q = app.world_mut().query::<(&Minion, &ChildOf)>();
+    let adds: Vec<Entity> = minion_q
+        .iter(app.world())
+        .map(|(_, child_of)| child_of.parent())
+        .collect();
+    assert_eq!(
+        adds,
+        vec![gala, gala],
+        "only the Heroic boss calls for help"
+    );
+}
@@ -0,0 +1,392 @@
+use crate::arena::ArenaName;
+use crate::boss::{ArenaClears, Decoy};
+use crate::character::{Boss, Ghost};
+use crate::class_type::ClassType;
+use crate::combat::CombatantDied;
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Level every recruit starts at, and the floor deaths cannot push below
+pub const MIN_HERO_LEVEL: u32 = 1;
+/// Level cap - XP stops accumulating once a hero reaches it
+pub const MAX_HERO_LEVEL: u32 = 20;
+
+/// XP shared by every roster hero in an arena when its boss falls
+pub const BOSS_KILL_XP: u32 = 250;
+
+/// Stable identity of a guild hero - survives saves, deaths and arena moves
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HeroId(pub u32);
+
+/// Error types for loading the guild roster
+#[derive(Error, Debug)]
+pub enum GuildError {
+    #[error("Could not access roster file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {line}: {message}")]
+    Malformed { line: usize, message: String },
+    #[error("Hero id {id} appears more than once")]
+    DuplicateId { id: u32 },
+    #[error("Hero name `{name}` must not be blank or contain `#` or line breaks")]
+    InvalidName { name: String },
+}
+
+/// One hero in the guild (value type)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeroRecord {
+    pub id: HeroId,
+    pub name: String,
+    pub class: ClassType,
+    pub level: u32,
+    /// Progress toward the next level
+    pub xp: u32,
+    /// Arena the hero is deployed to, if any
+    pub arena: Option<ArenaName>,
+}
+
+impl HeroRecord {
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// XP needed to advance from `level` to the next
+    #[must_use]
+    pub const fn xp_to_next_level(level: u32) -> u32 {
+        100 * level
+    }
+
+    /// Whether a name reads back unchanged from a save line, where `#` starts a
+    /// comment and surrounding whitespace is trimmed
+    #[must_use]
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name == name.trim()
+            && !name.contains(['#', '\n', '\r'])
+    }
+}
+
+/// Every hero the guild owns, persisted between sessions
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub struct GuildRoster {
+    heroes: Vec<HeroRecord>,
+    next_id: u32,
+}
+
+impl GuildRoster {
+    /// Path of the roster save, relative to the working directory
+    pub const SAVE_PATH: &'static str = "saves/guild.roster";
+
+    /// Roster a new guild starts with
+    #[must_use]
+    pub fn starter() -> Self {
+        let mut roster = Self::default();
+        for (name, class, arena) in [
+            ("Dean", ClassType::Hunter, ArenaName::GuildHouse),
+            ("Matthew", ClassType::Cardinal, ArenaName::GuildHouse),
+            ("Warrior", ClassType::Warrior, ArenaName::Labyrinth),
+            ("Bard", ClassType::Bard, ArenaName::Labyrinth),
+            ("Zephyr", ClassType::Alchemist, ArenaName::Bastion),
+        ] {
+            let id = roster
+                .recruit(name, class)
+                .expect("starter names are valid");
+            roster.assign(id, Some(arena));
+        }
+        roster
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GuildError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GuildError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Adds a level-one hero to the guild, unassigned
+    ///
+    /// Surrounding whitespace is trimmed; names the roster file could not read back
+    /// are rejected.
+    pub fn recruit(
+        &mut self,
+        name: impl Into<String>,
+        class: ClassType,
+    ) -> Result<HeroId, GuildError> {
+        let name = name.into().trim().to_string();
+        if !HeroRecord::is_valid_name(&name) {
+            return Err(GuildError::InvalidName { name });
+        }
+        self.next_id += 1;
+        let id = HeroId(self.next_id);
+        self.heroes.push(HeroRecord {
+            id,
+            name,
+            class,
+            level: MIN_HERO_LEVEL,
+            xp: 0,
+            arena: None,
+        });
+        Ok(id)
+    }
+
+    #[must_use]
+    pub fn get(&self, id: HeroId) -> Option<&HeroRecord> {
+        self.heroes.iter().find(|hero| hero.id == id)
+    }
+
+    fn get_mut(&mut self, id: HeroId) -> Option<&mut HeroRecord> {
+        self.heroes.iter_mut().find(|hero| hero.id == id)
+    }
+
+    pub fn assign(&mut self, id: HeroId, arena: Option<ArenaName>) {
+        if let Some(hero) = self.get_mut(id) {
+            hero.arena = arena;
+        }
+    }
+
+    /// Heroes deployed to an arena, in roster order
+    pub fn assigned_to(&self, arena: ArenaName) -> impl Iterator<Item = &HeroRecord> {
+        self.heroes
+            .iter()
+            .filter(move |hero| hero.arena == Some(arena))
+    }
+
+    /// Adds XP, levelling up as thresholds are crossed. Returns the levels gained.
+    pub fn grant_xp(&mut self, id: HeroId, amount: u32) -> u32 {
+        let Some(hero) = self.get_mut(id) else {
+            return 0;
+        };
+        let start = hero.level;
+        hero.xp += amount;
+        while hero.level < MAX_HERO_LEVEL && hero.xp >= HeroRecord::xp_to_next_level(hero.level) {
+            hero.xp -= HeroRecord::xp_to_next_level(hero.level);
+            hero.level += 1;
+        }
+        if hero.level == MAX_HERO_LEVEL {
+            hero.xp = 0;
+        }
+        hero.level - start
+    }
+
+    /// Death costs a level and all progress toward the next - the hero stays in the guild
+    pub fn record_death(&mut self, id: HeroId) {
+        if let Some(hero) = self.get_mut(id) {
+            hero.level = hero.level.saturating_sub(1).max(MIN_HERO_LEVEL);
+            hero.xp = 0;
+        }
+    }
+}
+
+/// File format: a `next-id <n>` line, then one
+/// `hero <id> <class> <level> <xp> <arena or -> <name>` line per hero, `#` comments
+impl Display for GuildRoster {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "next-id {}", self.next_id)?;
+        for hero in &self.heroes {
+            let arena = hero
+                .arena
+                .map_or_else(|| "-".to_string(), |arena| format!("{arena:?}"));
+            writeln!(
+                f,
+                "hero {} {} {} {} {arena} {}",
+                hero.id.0,
+                hero.class.name(),
+                hero.level,
+                hero.xp,
+                hero.name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GuildRoster {
+    type Err = GuildError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut roster = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = |message: String| GuildError::Malformed {
+                line: index + 1,
+                message,
+            };
+            let expected = || {
+                malformed(format!(
+                    "expected `hero <id> <class> <level> <xp> <arena> <name>`, got `{line}`"
+                ))
+            };
+
+            if let Some(next_id) = line.strip_prefix("next-id ") {
+                roster.next_id = next_id
+                    .trim()
+                    .parse()
+                    .map_err(|_| malformed(format!("invalid next-id `{next_id}`")))?;
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(7, ' ').collect();
+            let ["hero", id, class, level, xp, arena, name] = fields[..] else {
+                return Err(expected());
+            };
+            let id: u32 = id.parse().map_err(|_| expected())?;
+            if roster.get(HeroId(id)).is_some() {
+                return Err(GuildError::DuplicateId { id });
+            }
+            let arena = match arena {
+                "-" => None,
+                arena => Some(
+                    arena
+                        .parse()
+                        .map_err(|error| malformed(format!("{error}")))?,
+                ),
+            };
+            roster.heroes.push(HeroRecord {
+                id: HeroId(id),
+                name: name.trim().to_string(),
+                class: class
+                    .parse()
+                    .map_err(|error| malformed(format!("{error}")))?,
+                level: level.parse().map_err(|_| expected())?,
+                xp: xp.parse().map_err(|_| expected())?,
+                arena,
+            });
+            roster.next_id = roster.next_id.max(id);
+        }
+        Ok(roster)
+    }
+}
+
+/// Fallen roster heroes lose a level instead of leaving the guild;
+/// a fallen boss rewards every roster hero in its arena. Ghosts replay the same
+/// fight every loop, so only heroes fighting live gain or lose anything.
+pub fn apply_combat_progression(
+    mut death_events: EventReader<CombatantDied>,
+    hero_q: Query<&HeroId, Without<Ghost>>,
+    boss_q: Query<&ChildOf, (With<Boss>, Without<Decoy>)>,
+    arena_children_q: Query<&Children>,
+    mut roster: ResMut<GuildRoster>,
+) {
+    for event in death_events.read() {
+        if let Ok(id) = hero_q.get(event.entity) {
+            roster.record_death(*id);
+            if let Some(hero) = roster.get(*id) {
+                info!("{} fell and dropped to level {}", hero.name, hero.level);
+            }
+        } else if let Ok(child_of) = boss_q.get(event.entity) {
+            let Ok(children) = arena_children_q.get(child_of.parent()) else {
+                continue;
+            };
+            for id in hero_q.iter_many(children) {
+                let levels = roster.grant_xp(*id, BOSS_KILL_XP);
+                if levels > 0
+                    && let Some(hero) = roster.get(*id)
+                {
+                    info!("{} reached level {}", hero.name, hero.level);
+                }
+            }
+        }
+    }
+}
+
+/// Writes the roster to disk whenever it changes
+pub fn save_roster(roster: Res<GuildRoster>) {
+    if !roster.is_changed() || roster.is_added() {
+        return;
+    }
+    if let Err(error) = roster.save(GuildRoster::SAVE_PATH) {
+        warn!("Could not save guild roster: {error}");
+    }
+}
+
+/// Writes the arena clears to disk whenever a new arena falls
+pub fn save_arena_clears(clears: Res<ArenaClears>) {
+    if !clears.is_changed() || clears.is_added() {
+        return;
+    }
+    if let Err(error) = clears.save(ArenaClears::SAVE_PATH) {
+        warn!("Could not save arena clears: {error}");
+    }
+}
+
+/// Moves an unreadable save aside to `<path>.bak` so the next save can't overwrite it
+pub fn back_up_save(path: impl AsRef<Path>) -> std::io::Result<PathBuf> {
+    let path = path.as_ref();
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    std::fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+/// Loads the saved roster, starting a fresh guild when there is none. An
+/// unreadable save is backed up first rather than overwritten by the new guild.
+pub fn load_roster(path: impl AsRef<Path>) -> GuildRoster {
+    let path = path.as_ref();
+    match GuildRoster::load(path) {
+        Ok(roster) => roster,
+        Err(GuildError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+            info!("No saved guild found - starting a new roster");
+            GuildRoster::starter()
+        }
+        Err(error) => {
+            match back_up_save(path) {
+                Ok(backup) => warn!(
+                    "Using a new guild roster: {error} (old save kept at {})",
+                    backup.display()
+                ),
+                Err(backup_error) => warn!(
+                    "Using a new guild roster: {error} (could not back up the old save: {backup_error})"
+                ),
+            }
+            GuildRoster::starter()
+        }
+    }
+}
+
+/// Loads the saved arena clears, starting with none. An unreadable save is
+/// backed up first rather than overwritten.
+pub fn load_arena_clears(path: impl AsRef<Path>) -> ArenaClears {
+    let path = path.as_ref();
+    match ArenaClears::load(path) {
+        Ok(clears) => clears,
+        Err(GuildError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+            ArenaClears::default()
+        }
+        Err(error) => {
+            match back_up_save(path) {
+                Ok(backup) => warn!(
+                    "Starting with no arenas cleared: {error} (old save kept at {})",
+                    backup.display()
+                ),
+                Err(backup_error) => warn!(
+                    "Starting with no arenas cleared: {error} (could not back up the old save: {backup_error})"
+                ),
+            }
+            ArenaClears::default()
+        }
+    }
+}
+
+/// Plugin for the persistent guild roster, arena clears and hero progression
+pub struct GuildPlugin;
+
+impl Plugin for GuildPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_roster(GuildRoster::SAVE_PATH))
+            .insert_resource(load_arena_clears(ArenaClears::SAVE_PATH))
+            .add_event::<CombatantDied>()
+            .add_systems(Update, apply_combat_progression)
+            .add_systems(Last, (save_roster, save_arena_clears));
+    }
+}
+
+#[cfg(test)]
+mod tests;
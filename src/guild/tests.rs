@@ -0,0 +1,159 @@
+use super::*;
+use bevy::app::{App, Update};
+
+#[test]
+fn test_roster_round_trips_through_the_save_format() {
+    let mut roster = GuildRoster::starter();
+    let recruit = roster.recruit("Ada Quill", ClassType::Thief).unwrap();
+    roster.grant_xp(recruit, 150);
+
+    let parsed: GuildRoster = roster.to_string().parse().unwrap();
+    assert_eq!(parsed, roster);
+    let ada = parsed.get(recruit).unwrap();
+    assert_eq!((ada.level, ada.xp, ada.arena), (2, 50, None));
+    assert_eq!(ada.name, "Ada Quill");
+}
+
+#[test]
+fn test_names_that_would_not_read_back_are_rejected() {
+    let mut roster = GuildRoster::default();
+    for name in ["Rook #2", "", "Rook\nhero 9 Bard 1 0 - Spy"] {
+        assert!(matches!(
+            roster.recruit(name, ClassType::Warrior),
+            Err(GuildError::InvalidName { .. })
+        ));
+    }
+    assert_eq!(roster, GuildRoster::default(), "nothing was recruited");
+
+    let rook = roster.recruit("  Rook No. 2 ", ClassType::Warrior).unwrap();
+    let parsed: GuildRoster = roster.to_string().parse().unwrap();
+    assert_eq!(parsed.get(rook).unwrap().name, "Rook No. 2");
+    assert_eq!(parsed, roster);
+}
+
+#[test]
+fn test_ids_stay_unique_after_loading() {
+    let mut roster: GuildRoster = "hero 7 Bard 1 0 Gala Lyra\n".parse().unwrap();
+    assert_eq!(
+        roster.recruit("Pip", ClassType::Merchant).unwrap(),
+        HeroId(8)
+    );
+
+    assert!(matches!(
+        "hero 3 Bard 1 0 - A\nhero 3 Thief 1 0 - B".parse::<GuildRoster>(),
+        Err(GuildError::DuplicateId { id: 3 })
+    ));
+    assert!(matches!(
+        "next-id 2\nhero 1 Necromancer 1 0 - Mort".parse::<GuildRoster>(),
+        Err(GuildError::Malformed { line: 2, .. })
+    ));
+}
+
+#[test]
+fn test_death_costs_a_level_but_never_the_hero() {
+    let mut roster = GuildRoster::default();
+    let hero = roster.recruit("Rook", ClassType::Warrior).unwrap();
+    roster.grant_xp(hero, 100 + 200 + 50);
+    assert_eq!(roster.get(hero).map(|h| (h.level, h.xp)), Some((3, 50)));
+
+    roster.record_death(hero);
+    assert_eq!(roster.get(hero).map(|h| (h.level, h.xp)), Some((2, 0)));
+    roster.record_death(hero);
+    roster.record_death(hero);
+    assert_eq!(
+        roster.get(hero).map(|h| h.level),
+        Some(MIN_HERO_LEVEL),
+        "level floors at the minimum and the hero stays on the roster"
+    );
+}
+
+#[test]
+fn test_combat_deaths_update_the_roster() {
+    let mut app = App::new();
+    let mut roster = GuildRoster::default();
+    let fallen = roster.recruit("Rook", ClassType::Warrior).unwrap();
+    let survivor = roster.recruit("Lyra", ClassType::Bard).unwrap();
+    roster.grant_xp(fallen, 100);
+    app.insert_resource(roster)
+        .add_event::<CombatantDied>()
+        .add_systems(Update, apply_combat_progression);
+
+    let arena = app.world_mut().spawn_empty().id();
+    let fallen_entity = app.world_mut().spawn((fallen, ChildOf(arena))).id();
+    app.world_mut().spawn((survivor, ChildOf(arena)));
+    let boss = app.world_mut().spawn((Boss, ChildOf(arena))).id();
+
+    app.world_mut().send_event(CombatantDied {
+        entity: fallen_entity,
+    });
+    app.update();
+    let roster = app.world().resource::<GuildRoster>();
+    assert_eq!(roster.get(fallen).map(|h| (h.level, h.xp)), Some((1, 0)));
+
+    // Everyone in the arena shares the boss kill
+    app.world_mut().send_event(CombatantDied { entity: boss });
+    app.update();
+    let roster = app.world().resource::<GuildRoster>();
+    assert_eq!(roster.get(fallen).map(|h| (h.level, h.xp)), Some((2, 150)));
+    assert_eq!(
+        roster.get(survivor).map(|h| (h.level, h.xp)),
+        Some((2, 150))
+    );
+}
+
+#[test]
+fn test_ghost_deaths_and_kills_leave_the_roster_alone() {
+    let mut app = App::new();
+    let mut roster = GuildRoster::default();
+    let ghost = roster.recruit("Rook", ClassType::Warrior).unwrap();
+    roster.grant_xp(ghost, 100);
+    let expected = roster.clone();
+    app.insert_resource(roster)
+        .add_event::<CombatantDied>()
+        .add_systems(Update, apply_combat_progression);
+
+    let arena = app.world_mut().spawn_empty().id();
+    let ghost_entity = app.world_mut().spawn((ghost, Ghost, ChildOf(arena))).id();
+    let boss = app.world_mut().spawn((Boss, ChildOf(arena))).id();
+    app.world_mut().send_event(CombatantDied {
+        entity: ghost_entity,
+    });
+    app.world_mut().send_event(CombatantDied { entity: boss });
+    app.update();
+
+    assert_eq!(*app.world().resource::<GuildRoster>(), expected);
+}
+
+#[test]
+fn test_unreadable_saves_are_backed_up_not_overwritten() {
+    let dir = std::env::temp_dir().join(format!("arenic-guild-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("guild.roster");
+    std::fs::write(&path, "hero one two\n").unwrap();
+
+    assert_eq!(load_roster(&path), GuildRoster::starter());
+    assert!(!path.exists());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("guild.roster.bak")).unwrap(),
+        "hero one two\n"
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_arena_clears_round_trip_and_back_up_unreadable_saves() {
+    let mut clears = ArenaClears::default();
+    clears.record(ArenaName::Crucible);
+    clears.record(ArenaName::Gala);
+    assert_eq!(clears.to_string().parse::<ArenaClears>().unwrap(), clears);
+
+    let dir = std::env::temp_dir().join(format!("arenic-clears-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("arena.clears");
+    std::fs::write(&path, "cleared Atlantis\n").unwrap();
+
+    assert_eq!(load_arena_clears(&path), ArenaClears::default());
+    assert!(!path.exists());
+    assert!(dir.join("arena.clears.bak").exists());
+    std::fs::remove_dir_all(dir).unwrap();
+}
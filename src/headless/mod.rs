@@ -0,0 +1,371 @@
+use crate::ability::AbilityEffectPlugin;
+use crate::arena::{
+    Arena, ArenaEntities, ArenaName, ArenaOccupancy, CurrentArena, OccupancyPlugin, UnknownArena,
+};
+use crate::boss::BossPlugin;
+use crate::character::{Boss, Character};
+use crate::combat::{CombatPlugin, CombatantDied, DamageDealt, Health};
+use crate::difficulty::Difficulty;
+use crate::minion::MinionPlugin;
+use crate::recording::Playback;
+use crate::status_effect::StatusEffectPlugin;
+use crate::timeline::{
+    GridPositionComponent, SavedTimeline, TimeStamp, TimelineClock, TimelineFileError,
+    TimelinePlugin,
+};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::PathBuf;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Fixed simulation tick - every run advances time identically regardless of hardware
+pub const SIMULATION_STEP: Duration = Duration::from_millis(20);
+
+/// Ticks in one 120-second arena loop
+const LOOP_STEPS: u32 = (TimeStamp::MAX.0 as u128 * 1000 / SIMULATION_STEP.as_millis()) as u32;
+
+/// Where every arena's boss stands
+const BOSS_TILE: IVec2 = IVec2::new(32, 10);
+
+/// Error types for the headless command line
+#[derive(Error, Debug)]
+pub enum HeadlessError {
+    #[error("{flag} needs a value")]
+    MissingValue { flag: &'static str },
+    #[error("Unknown option: {flag}")]
+    UnknownFlag { flag: String },
+    #[error("Invalid loop count: {value}")]
+    InvalidLoops { value: String },
+    #[error(transparent)]
+    UnknownArena(#[from] UnknownArena),
+    #[error("No timeline files given")]
+    NoTimelines,
+    #[error("{path}: {source}")]
+    Timeline {
+        path: String,
+        source: TimelineFileError,
+    },
+    #[error("{path} was recorded in {recorded}, not {arena}")]
+    ArenaMismatch {
+        path: String,
+        recorded: ArenaName,
+        arena: ArenaName,
+    },
+    #[error("{path} was recorded on {recorded}, not {difficulty}")]
+    DifficultyMismatch {
+        path: String,
+        recorded: Difficulty,
+        difficulty: Difficulty,
+    },
+}
+
+/// Settings for a headless batch run, parsed from the command line
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessConfig {
+    /// Arena to simulate - defaults to the arena of the first timeline
+    pub arena: Option<ArenaName>,
+    pub loops: u32,
+    pub timelines: Vec<PathBuf>,
+}
+
+impl HeadlessConfig {
+    pub const USAGE: &'static str =
+        "Usage: arenic_bevy --headless [--arena <name>] [--loops <n>] <timeline files...>";
+
+    /// Reads `--headless` arguments, or None when the game should open its window
+    pub fn from_args(
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Option<Self>, HeadlessError> {
+        let mut args = args.into_iter().peekable();
+        if args.next_if(|arg| arg == "--headless").is_none() {
+            return Ok(None);
+        }
+
+        let mut config = Self {
+            arena: None,
+            loops: 1,
+            timelines: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--arena" => {
+                    let name = args
+                        .next()
+                        .ok_or(HeadlessError::MissingValue { flag: "--arena" })?;
+                    config.arena = Some(name.parse()?);
+                }
+                "--loops" => {
+                    let value = args
+                        .next()
+                        .ok_or(HeadlessError::MissingValue { flag: "--loops" })?;
+                    config.loops = value
+                        .parse()
+                        .ok()
+                        .filter(|loops| *loops > 0)
+                        .ok_or(HeadlessError::InvalidLoops { value })?;
+                }
+                flag if flag.starts_with("--") => {
+                    return Err(HeadlessError::UnknownFlag { flag: arg });
+                }
+                _ => config.timelines.push(PathBuf::from(arg)),
+            }
+        }
+        if config.timelines.is_empty() {
+            return Err(HeadlessError::NoTimelines);
+        }
+        Ok(Some(config))
+    }
+}
+
+/// Result of one simulated arena loop (value type)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopOutcome {
+    pub boss_health: f32,
+    pub boss_max_health: f32,
+    pub deaths: u32,
+    /// Damage landed by each hero, in timeline order
+    pub damage_by_hero: Vec<(String, f32)>,
+}
+
+/// Outcomes of every loop in a headless run (value type)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub arena: ArenaName,
+    pub difficulty: Difficulty,
+    pub loops: Vec<LoopOutcome>,
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(
+            f,
+            "{} on {} - {} loop(s)",
+            self.arena,
+            self.difficulty,
+            self.loops.len()
+        )?;
+        for (index, outcome) in self.loops.iter().enumerate() {
+            writeln!(
+                f,
+                "Loop {}: boss {:.0}/{:.0} ({:.1}%), {} death(s)",
+                index + 1,
+                outcome.boss_health,
+                outcome.boss_max_health,
+                outcome.boss_health / outcome.boss_max_health * 100.0,
+                outcome.deaths
+            )?;
+            for (hero, damage) in &outcome.damage_by_hero {
+                writeln!(f, "  {hero}: {damage:.0} damage")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Damage and deaths gathered since the last loop boundary
+#[derive(Resource, Debug, Default)]
+struct SimulationStats {
+    damage: Vec<(Entity, f32)>,
+    deaths: u32,
+}
+
+fn record_combat_outcomes(
+    mut stats: ResMut<SimulationStats>,
+    mut dealt_events: EventReader<DamageDealt>,
+    mut death_events: EventReader<CombatantDied>,
+    hero_q: Query<(), With<Character>>,
+) {
+    for event in dealt_events.read() {
+        match stats
+            .damage
+            .iter_mut()
+            .find(|(hero, _)| *hero == event.source)
+        {
+            Some((_, total)) => *total += event.amount,
+            None => stats.damage.push((event.source, event.amount)),
+        }
+    }
+    for event in death_events.read() {
+        if hero_q.contains(event.entity) {
+            stats.deaths += 1;
+        }
+    }
+}
+
+/// Loads the configured timelines and simulates the arena
+pub fn run(config: &HeadlessConfig) -> Result<SimulationReport, HeadlessError> {
+    let saved = config
+        .timelines
+        .iter()
+        .map(|path| {
+            SavedTimeline::load(path).map_err(|source| HeadlessError::Timeline {
+                path: path.display().to_string(),
+                source,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let arena = config
+        .arena
+        .or_else(|| saved.first().map(|timeline| timeline.arena))
+        .ok_or(HeadlessError::NoTimelines)?;
+    if let Some((path, timeline)) = config
+        .timelines
+        .iter()
+        .zip(&saved)
+        .find(|(_, timeline)| timeline.arena != arena)
+    {
+        return Err(HeadlessError::ArenaMismatch {
+            path: path.display().to_string(),
+            recorded: timeline.arena,
+            arena,
+        });
+    }
+    let difficulty = saved
+        .first()
+        .map_or_else(Difficulty::default, |timeline| timeline.timeline.difficulty);
+    if let Some((path, timeline)) = config
+        .timelines
+        .iter()
+        .zip(&saved)
+        .find(|(_, timeline)| timeline.timeline.difficulty != difficulty)
+    {
+        return Err(HeadlessError::DifficultyMismatch {
+            path: path.display().to_string(),
+            recorded: timeline.timeline.difficulty,
+            difficulty,
+        });
+    }
+
+    Ok(simulate(arena, difficulty, &saved, config.loops))
+}
+
+/// Replays saved timelines against the arena's boss at the given tier for a number
+/// of loops, ticking a fixed step as fast as possible with no window or renderer
+pub fn simulate(
+    arena: ArenaName,
+    difficulty: Difficulty,
+    saved: &[SavedTimeline],
+    loops: u32,
+) -> SimulationReport {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(SIMULATION_STEP))
+        .add_plugins((
+            TimelinePlugin,
+            OccupancyPlugin,
+            CombatPlugin,
+            StatusEffectPlugin,
+            BossPlugin,
+            MinionPlugin,
+            AbilityEffectPlugin,
+        ))
+        .init_resource::<SimulationStats>()
+        .add_systems(PostUpdate, record_combat_outcomes);
+
+    let world = app.world_mut();
+    let arenas = ArenaName::ALL_ARENAS.map(|name| {
+        let entity = world
+            .spawn((
+                Arena(name),
+                ArenaOccupancy::walled(name),
+                TimelineClock::default(),
+            ))
+            .id();
+        (name, entity)
+    });
+    let arena_entities = ArenaEntities::new(arenas);
+    let arena_entity = arena_entities.get(arena);
+    world
+        .entity_mut(arena_entity)
+        .insert((Playback, difficulty));
+    world.insert_resource(arena_entities);
+    world.insert_resource(CurrentArena(arena));
+
+    let boss = world
+        .spawn((
+            Boss,
+            difficulty.boss_bundle(),
+            GridPositionComponent(BOSS_TILE),
+            ChildOf(arena_entity),
+        ))
+        .id();
+    let heroes: Vec<(Entity, String)> = saved
+        .iter()
+        .map(|saved| {
+            let hero = world
+                .spawn((saved.ghost_bundle(arena), ChildOf(arena_entity)))
+                .id();
+            (hero, saved.name.clone())
+        })
+        .collect();
+
+    // First update only primes the clock
+    app.update();
+
+    let outcomes = (0..loops)
+        .map(|_| {
+            for _ in 0..LOOP_STEPS {
+                app.update();
+            }
+            let world = app.world_mut();
+            let stats = std::mem::take(&mut *world.resource_mut::<SimulationStats>());
+            let boss_health = world
+                .get::<Health>(boss)
+                .copied()
+                .unwrap_or(Health::new(0.0));
+
+            // Ghosts returned to their origin as the clock wrapped - the boss does too
+            world.entity_mut(boss).insert(difficulty.boss_bundle());
+
+            LoopOutcome {
+                boss_health: boss_health.current,
+                boss_max_health: boss_health.max,
+                deaths: stats.deaths,
+                damage_by_hero: heroes
+                    .iter()
+                    .map(|(hero, name)| {
+                        let damage = stats
+                            .damage
+                            .iter()
+                            .find(|(source, _)| source == hero)
+                            .map_or(0.0, |(_, damage)| *damage);
+                        (name.clone(), damage)
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    SimulationReport {
+        arena,
+        difficulty,
+        loops: outcomes,
+    }
+}
+
+/// Runs the headless mode when the arguments ask for it, returning the exit code
+pub fn run_from_args(args: impl IntoIterator<Item = String>) -> Option<i32> {
+    let config = match HeadlessConfig::from_args(args) {
+        Ok(config) => config?,
+        Err(error) => {
+            eprintln!("{error}\n{}", HeadlessConfig::USAGE);
+            return Some(2);
+        }
+    };
+    match run(&config) {
+        Ok(report) => {
+            print!("{report}");
+            Some(0)
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            Some(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
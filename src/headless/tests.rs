@@ -0,0 +1,88 @@
+use super::*;
+
+const SHIPPED: [&str; 2] = [
+    "assets/timelines/labyrinth_warrior.timeline",
+    "assets/timelines/labyrinth_bard.timeline",
+];
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+fn test_args_without_headless_flag_open_the_game() {
+    assert!(HeadlessConfig::from_args(args("")).unwrap().is_none());
+    assert!(
+        HeadlessConfig::from_args(args("run.timeline"))
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_headless_args_parse_arena_loops_and_files() {
+    let config = HeadlessConfig::from_args(args("--headless --arena GuildHouse --loops 3 a b"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        config,
+        HeadlessConfig {
+            arena: Some(ArenaName::GuildHouse),
+            loops: 3,
+            timelines: vec![PathBuf::from("a"), PathBuf::from("b")],
+        }
+    );
+
+    assert!(matches!(
+        HeadlessConfig::from_args(args("--headless --loops 0 a")),
+        Err(HeadlessError::InvalidLoops { value }) if value == "0"
+    ));
+    assert!(matches!(
+        HeadlessConfig::from_args(args("--headless --arena")),
+        Err(HeadlessError::MissingValue { flag: "--arena" })
+    ));
+    assert!(matches!(
+        HeadlessConfig::from_args(args("--headless")),
+        Err(HeadlessError::NoTimelines)
+    ));
+}
+
+#[test]
+fn test_timelines_from_another_arena_are_rejected() {
+    let config = HeadlessConfig {
+        arena: Some(ArenaName::Casino),
+        loops: 1,
+        timelines: SHIPPED.map(PathBuf::from).to_vec(),
+    };
+    assert!(matches!(
+        run(&config),
+        Err(HeadlessError::ArenaMismatch {
+            recorded: ArenaName::Labyrinth,
+            arena: ArenaName::Casino,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_simulated_loops_replay_identically() {
+    let config = HeadlessConfig {
+        arena: None,
+        loops: 2,
+        timelines: SHIPPED.map(PathBuf::from).to_vec(),
+    };
+    let report = run(&config).unwrap();
+
+    assert_eq!(report.arena, ArenaName::Labyrinth);
+    assert_eq!(report.difficulty, Difficulty::Normal);
+    let expected = LoopOutcome {
+        boss_health: Difficulty::Normal.boss_max_health() - 570.0,
+        boss_max_health: Difficulty::Normal.boss_max_health(),
+        deaths: 0,
+        damage_by_hero: vec![("Warrior".to_string(), 300.0), ("Bard".to_string(), 270.0)],
+    };
+    assert_eq!(report.loops, vec![expected.clone(), expected]);
+
+    // A second run from scratch produces the same report
+    assert_eq!(run(&config).unwrap(), report);
+}
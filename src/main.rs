@@ -2,6 +2,13 @@ mod arena;
 mod arena_camera;
 mod audio;
 mod battleground;
+mod boss;
+mod combat;
+mod combat_log;
+mod controls;
+mod difficulty;
+mod guild;
+mod headless;
 mod lights;
 
 // Uncomment these modules to debug pink material issues
@@ -9,9 +16,16 @@ mod ability;
 mod character;
 mod class_type;
 mod materials;
+mod meters;
+mod minion;
+mod pathfinding;
 // mod recording;
 mod recording;
+mod recruitment;
+mod replay;
+mod rng;
 mod selectors;
+mod status_effect;
 mod timeline;
 mod ui;
 
@@ -21,28 +35,49 @@ use bevy::window::WindowResolution;
 
 // Local crate modules - abilities
 use crate::ability::{
-    AcidFlask, AutoShot, Bash, Block, Dance, HolyNova, Ironskin, Mimic, Siphon, Transmute,
-    auto_shot_ability, holy_nova_ability, move_projectiles, update_holy_nova_vfx,
+    AbilityEffectPlugin, AcidFlask, AutoShot, Bash, Block, Dance, HolyNova, Ironskin, Mimic,
+    Siphon, Transmute, auto_shot_ability, cast_slotted_abilities, holy_nova_ability,
+    move_projectiles, tick_ability_cooldowns, update_holy_nova_vfx,
 };
 
 // Local crate modules - arena system
 use crate::arena::{
-    ARENA_HEIGHT, ARENA_WIDTH, Arena, ArenaEntities, ArenaName, CameraUpdate, CharacterMoved,
-    CurrentArena, CurrentArenaEntity, DEBUG_COLORS, GRID_HEIGHT, GRID_WIDTH, LastActiveHero,
-    TILE_SIZE, TOTAL_ARENAS, arena_update, decrement_current_arena, get_local_tile_space,
-    handle_character_moved, increment_current_arena,
+    ARENA_HEIGHT, ARENA_WIDTH, Arena, ArenaEntities, ArenaName, ArenaOccupancy, CameraUpdate,
+    CharacterMoved, CurrentArena, DEBUG_COLORS, GRID_HEIGHT, GRID_WIDTH, LastActiveHero,
+    OccupancyPlugin, TILE_SIZE, TOTAL_ARENAS, arena_update, decrement_current_arena,
+    handle_character_moved, increment_current_arena, sync_terrain_overlays,
+    tile_to_local_translation,
+};
+use crate::arena_camera::{
+    animate_camera_rig, draw_arena_border, follow_current_arena, setup_camera, toggle_camera_zoom,
 };
-use crate::arena_camera::{draw_arena_border, setup_camera, toggle_camera_zoom};
 
 // Local crate modules - core systems
 use crate::audio::Audio;
 use crate::battleground::BattleGround;
-use crate::character::{Boss, Character, move_active_character, toggle_active_character};
+use crate::boss::{BossPlugin, attach_treasure_visuals};
+use crate::character::{
+    Boss, CHARACTER_MAX_HEALTH, Character, interact_with_active_character, move_active_character,
+    toggle_active_character,
+};
 use crate::class_type::ClassType;
+use crate::combat::{CombatPlugin, Health};
+use crate::combat_log::CombatLogPlugin;
+use crate::controls::ControlsPlugin;
+use crate::difficulty::{Difficulty, DifficultyPlugin};
+use crate::guild::{GuildPlugin, GuildRoster};
 use crate::lights::spawn_lights;
 use crate::materials::Materials;
+use crate::meters::MetersPlugin;
+use crate::minion::{MinionPlugin, MinionVisualsPlugin};
+use crate::pathfinding::PathfindingPlugin;
+use crate::recording::Playback;
+use crate::recruitment::RecruitmentPlugin;
+use crate::replay::ReplayPlugin;
 use crate::selectors::Active;
-use crate::timeline::{TimelineClock, TimelineManager, TimelinePlugin};
+use crate::status_effect::StatusEffectPlugin;
+use crate::timeline::{GridPositionComponent, TimelineClock, TimelineManager, TimelinePlugin};
+use crate::ui::UiPlugin;
 
 // Fix for web audio and asset loading
 #[cfg(target_arch = "wasm32")]
@@ -58,6 +93,11 @@ enum GameState {
 }
 
 fn main() {
+    // `--headless` simulates saved timelines without opening a window
+    if let Some(exit_code) = headless::run_from_args(std::env::args().skip(1)) {
+        std::process::exit(exit_code);
+    }
+
     // Configure plugins differently for web vs. native
     #[cfg(target_arch = "wasm32")]
     let default_plugins = DefaultPlugins
@@ -86,50 +126,79 @@ fn main() {
 
     App::new()
         .add_plugins(default_plugins)
+        .add_plugins(GamePlugin)
+        .run();
+}
+
+/// Every game system and plugin, on top of whichever windowing plugins the caller adds
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
         // Initialize game state
-        .init_state::<GameState>()
-        // Register custom events
-        .add_event::<CameraUpdate>()
-        .add_event::<CharacterMoved>()
-        .add_systems(
-            Startup,
-            (
-                setup_scene,
-                spawn_lights,
-                setup_camera,
-                spawn_starting_bosses,
-                spawn_starting_hero,
-                spawn_labyrinth_characters,
-                spawn_bastion_characters,
-                mark_timeline_ghosts,
+        app.init_state::<GameState>()
+            // Register custom events
+            .add_event::<CameraUpdate>()
+            .add_event::<CharacterMoved>()
+            .add_systems(
+                Startup,
+                (
+                    setup_scene,
+                    spawn_lights,
+                    setup_camera,
+                    spawn_starting_bosses,
+                    spawn_roster_heroes,
+                    attach_demo_timelines,
+                    mark_timeline_ghosts,
+                )
+                    .chain(),
             )
-                .chain(),
-        )
-        .add_systems(
-            Update,
-            (
-                toggle_camera_zoom,
-                toggle_active_character,
-                increment_current_arena,
-                decrement_current_arena,
-                arena_update,
-                handle_character_moved,
-                move_active_character,
-                draw_arena_border,
-            ),
-        )
-        .add_systems(
-            Update,
-            (
-                auto_shot_ability,
-                move_projectiles,
-                holy_nova_ability,
-                update_holy_nova_vfx,
-            ),
-        )
-        .add_plugins(TimelinePlugin)
-        .add_plugins(recording::RecordingPlugin)
-        .run();
+            .add_systems(
+                Update,
+                (
+                    toggle_camera_zoom,
+                    (follow_current_arena, animate_camera_rig).chain(),
+                    toggle_active_character,
+                    increment_current_arena,
+                    decrement_current_arena,
+                    arena_update,
+                    handle_character_moved,
+                    move_active_character,
+                    interact_with_active_character,
+                    draw_arena_border,
+                    sync_terrain_overlays,
+                    attach_treasure_visuals,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    auto_shot_ability,
+                    move_projectiles,
+                    tick_ability_cooldowns,
+                    (cast_slotted_abilities, holy_nova_ability).chain(),
+                    update_holy_nova_vfx,
+                ),
+            )
+            .add_plugins(ControlsPlugin)
+            .add_plugins(OccupancyPlugin)
+            .add_plugins(PathfindingPlugin)
+            .add_plugins(TimelinePlugin)
+            .add_plugins(recording::RecordingPlugin)
+            .add_plugins(BossPlugin)
+            .add_plugins(MinionPlugin)
+            .add_plugins(MinionVisualsPlugin)
+            .add_plugins(DifficultyPlugin)
+            .add_plugins(CombatPlugin)
+            .add_plugins(CombatLogPlugin)
+            .add_plugins(MetersPlugin)
+            .add_plugins(AbilityEffectPlugin)
+            .add_plugins(ReplayPlugin)
+            .add_plugins(GuildPlugin)
+            .add_plugins(RecruitmentPlugin)
+            .add_plugins(StatusEffectPlugin)
+            .add_plugins(UiPlugin);
+    }
 }
 
 #[derive(Component, Debug)]
@@ -174,8 +243,11 @@ fn setup_scene(
                     .spawn((
                         Transform::from_xyz(offset_x, offset_y, 0.0),
                         Arena(ArenaName::from_index_safe(arena_index)),
+                        ArenaOccupancy::walled(arena_name_enum),
                         InheritedVisibility::default(),
                         TimelineClock::default(),
+                        // Every arena loops from the start so ghosts and bosses play out
+                        Playback,
                         class_type,
                         Name::new(arena_name),
                         LastActiveHero(None),
@@ -210,326 +282,131 @@ fn setup_scene(
     commands.insert_resource(ArenaEntities::new(arena_entities_array));
 }
 
-fn spawn_starting_hero(
-    mut commands: Commands,
-    mats: Res<Materials>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    current: CurrentArenaEntity,
-) {
-    // O(1) lookup for current arena entity
-    let arena_entity = current.get();
-
-    let sphere_radius = 0.125;
-    let sphere_mesh = meshes.add(Sphere::new(sphere_radius));
-    let local_position = get_local_tile_space(36.0, 15.0, 0.125);
-
-    // Spawn the character as a child and get its entity ID
-    let character_entity = commands
-        .spawn((
-            Character,
-            ClassType::Hunter,
-            AutoShot::new(16.0),
-            Active,
-            Mesh3d(sphere_mesh),
-            MeshMaterial3d(mats.blue.clone()),
-            Transform::from_translation(local_position),
-            ChildOf(arena_entity),
-            TimelineManager::new(),
-            Name::new("Dean"),
-        ))
-        .id();
-    let sphere_radius_v2 = 0.125;
-    let sphere_mesh_v2 = meshes.add(Sphere::new(sphere_radius_v2));
-    let local_position_v2 = get_local_tile_space(0.0, 0.0, 0.125);
-    commands.spawn((
-        Character,
-        ClassType::Cardinal,
-        HolyNova::new(),
-        Mesh3d(sphere_mesh_v2),
-        MeshMaterial3d(mats.gray.clone()),
-        Transform::from_translation(local_position_v2),
-        ChildOf(arena_entity),
-        TimelineManager::new(),
-        Name::new("Matthew"),
-    ));
-    println!("Character entity ID: {}", character_entity);
-    // Update the arena's LastActiveHero to point to this character
-    commands
-        .entity(arena_entity)
-        .insert(LastActiveHero(Some(character_entity)));
-}
-
-// TODO DELETE LATER
-/// Spawn a Warrior and Bard in the Labyrinth arena on startup
-fn spawn_labyrinth_characters(
+/// Spawns every deployed roster hero into its arena, lined up in rows of ten
+fn spawn_roster_heroes(
     mut commands: Commands,
     mats: Res<Materials>,
     mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    roster: Res<GuildRoster>,
     arena_entities: Res<ArenaEntities>,
+    current: Res<CurrentArena>,
 ) {
-    use crate::ability::{AbilityType, BardAbility, WarriorAbility};
-    use crate::timeline::{
-        DraftTimeline, EventType, PublishTimeline, TimeStamp, TimelineEvent, TimelineManager,
-    };
-
-    // Get the Labyrinth arena entity (index 0)
-    let labyrinth_entity = arena_entities.get(ArenaName::Labyrinth);
-
-    let character_radius = 0.125;
-    let character_mesh = meshes.add(Sphere::new(character_radius));
-
-    // Create a purple material for the Warrior
-    let purple_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.5, 0.0, 0.8), // Purple
-        metallic: 0.2,
-        perceptual_roughness: 0.5,
-        ..default()
-    });
-
-    // Create Warrior timeline with movements and abilities
-    let mut warrior_draft = DraftTimeline::new();
-    // Movement events - warrior patrols and attacks
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(5.0),
-            event_type: EventType::Movement(Vec3::new(1.0, 0.0, 0.0)), // Move right
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(10.0),
-            event_type: EventType::Ability(AbilityType::Warrior(WarriorAbility::Bash), None), // Warrior uses Bash
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(15.0),
-            event_type: EventType::Movement(Vec3::new(0.0, 1.0, 0.0)), // Move up
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(25.0),
-            event_type: EventType::Ability(AbilityType::Warrior(WarriorAbility::Block), None), // Warrior uses Block
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(35.0),
-            event_type: EventType::Movement(Vec3::new(-1.0, 0.0, 0.0)), // Move left
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(45.0),
-            event_type: EventType::Ability(AbilityType::Warrior(WarriorAbility::Bash), None), // Another Bash
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(60.0),
-            event_type: EventType::Movement(Vec3::new(0.0, -1.0, 0.0)), // Move down
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(75.0),
-            event_type: EventType::Ability(AbilityType::Warrior(WarriorAbility::Block), None), // Block again
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(90.0),
-            event_type: EventType::Movement(Vec3::new(1.0, 1.0, 0.0)), // Move diagonally
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(100.0),
-            event_type: EventType::Ability(AbilityType::Warrior(WarriorAbility::Bash), None), // Final Bash
-        })
-        .ok();
-    warrior_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(110.0),
-            event_type: EventType::Movement(Vec3::new(-1.0, -1.0, 0.0)), // Return movement
-        })
-        .ok();
-
-    let warrior_timeline = PublishTimeline::from_draft(warrior_draft);
-    let mut warrior_timeline_manager = TimelineManager::new();
-    warrior_timeline_manager.set_timeline(ArenaName::Labyrinth, warrior_timeline);
-
-    // Spawn Warrior at position (20, 15)
-    let warrior_position = get_local_tile_space(20.0, 15.0, character_radius);
-    commands.entity(labyrinth_entity).with_child((
-        Character,
-        ClassType::Warrior,
-        // Warrior abilities - instantiate the components
-        Bash::new(),
-        Block::new(),
-        warrior_timeline_manager,
-        Mesh3d(character_mesh.clone()),
-        MeshMaterial3d(purple_material),
-        Transform::from_translation(warrior_position),
-        Name::new("Warrior"),
-    ));
+    let radius = 0.125;
+    let mesh = meshes.add(Sphere::new(radius));
+
+    for arena in ArenaName::ALL_ARENAS {
+        let arena_entity = arena_entities.get(arena);
+        for (slot, hero) in roster.assigned_to(arena).enumerate() {
+            let slot = slot as i32;
+            let tile = IVec2::new(20 + 3 * (slot % 10), 15 + 2 * (slot / 10));
+            // The first hero in the starting arena is the one the player controls
+            let is_active = arena == current.0 && slot == 0;
+            let material = if is_active { &mats.blue } else { &mats.gray };
+
+            let mut entity = commands.spawn((
+                Character,
+                hero.id,
+                hero.class,
+                Health::new(CHARACTER_MAX_HEALTH),
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                GridPositionComponent(tile),
+                Transform::from_translation(tile_to_local_translation(tile, radius)),
+                ChildOf(arena_entity),
+                TimelineManager::new(),
+                Name::new(hero.name.clone()),
+            ));
+            insert_ability_kit(&mut entity, hero.class);
 
-    // Create Bard timeline with movements and abilities
-    let mut bard_draft = DraftTimeline::new();
-    // Movement events - bard dances around and uses abilities
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(3.0),
-            event_type: EventType::Movement(Vec3::new(0.0, 1.0, 0.0)), // Move up
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(8.0),
-            event_type: EventType::Ability(AbilityType::Bard(BardAbility::Dance), None), // Bard uses Dance
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(12.0),
-            event_type: EventType::Movement(Vec3::new(1.0, 0.0, 0.0)), // Move right
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(20.0),
-            event_type: EventType::Ability(AbilityType::Bard(BardAbility::Mimic), None), // Bard uses Mimic
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(30.0),
-            event_type: EventType::Movement(Vec3::new(-1.0, 1.0, 0.0)), // Move diagonally
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(40.0),
-            event_type: EventType::Ability(AbilityType::Bard(BardAbility::Dance), None), // Dance again
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(50.0),
-            event_type: EventType::Movement(Vec3::new(0.0, -1.0, 0.0)), // Move down
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(65.0),
-            event_type: EventType::Ability(AbilityType::Bard(BardAbility::Mimic), None), // Mimic again
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(80.0),
-            event_type: EventType::Movement(Vec3::new(1.0, -1.0, 0.0)), // Move diagonally
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(95.0),
-            event_type: EventType::Ability(AbilityType::Bard(BardAbility::Dance), None), // Final Dance
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(105.0),
-            event_type: EventType::Movement(Vec3::new(-1.0, 0.0, 0.0)), // Move left
-        })
-        .ok();
-    bard_draft
-        .add_event(TimelineEvent {
-            timestamp: TimeStamp::new(115.0),
-            event_type: EventType::Ability(AbilityType::Bard(BardAbility::Mimic), None), // Final Mimic
-        })
-        .ok();
-
-    let bard_timeline = PublishTimeline::from_draft(bard_draft);
-    let mut bard_timeline_manager = TimelineManager::new();
-    bard_timeline_manager.set_timeline(ArenaName::Labyrinth, bard_timeline);
-
-    // Spawn Bard at position (40, 15)
-    let bard_position = get_local_tile_space(40.0, 15.0, character_radius);
-    commands.entity(labyrinth_entity).with_child((
-        Character,
-        ClassType::Bard,
-        // Bard abilities - instantiate the components
-        Dance::new(),
-        Mimic::new(),
-        bard_timeline_manager,
-        Mesh3d(character_mesh.clone()),
-        MeshMaterial3d(mats.yellow.clone()), // Yellow for Bard
-        Transform::from_translation(bard_position),
-        Name::new("Bard"),
-    ));
+            if is_active {
+                let hero_entity = entity.insert(Active).id();
+                commands
+                    .entity(arena_entity)
+                    .insert(LastActiveHero(Some(hero_entity)));
+            }
+        }
+    }
+}
 
-    info!("Spawned Warrior and Bard characters with timelines in Labyrinth arena");
+/// Ability components for the classes whose abilities are implemented
+fn insert_ability_kit(entity: &mut EntityCommands, class: ClassType) {
+    match class {
+        ClassType::Hunter => {
+            entity.insert(AutoShot::new(16.0));
+        }
+        ClassType::Cardinal => {
+            entity.insert(HolyNova::new());
+        }
+        ClassType::Warrior => {
+            entity.insert((Bash::new(), Block::new()));
+        }
+        ClassType::Bard => {
+            entity.insert((Dance::new(), Mimic::new()));
+        }
+        ClassType::Alchemist => {
+            entity.insert((
+                AcidFlask::new(),
+                Ironskin::new(),
+                Siphon::new(),
+                Transmute::new(),
+            ));
+        }
+        _ => {}
+    }
 }
 
-/// Spawn an Alchemist in the Bastion arena on startup
-fn spawn_bastion_characters(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+// TODO DELETE LATER
+/// Demo recordings played by the starter Labyrinth heroes
+const DEMO_TIMELINES: [&str; 2] = [
+    include_str!("../assets/timelines/labyrinth_warrior.timeline"),
+    include_str!("../assets/timelines/labyrinth_bard.timeline"),
+];
+
+/// Hands the demo recordings to the roster heroes they were recorded by
+fn attach_demo_timelines(
     arena_entities: Res<ArenaEntities>,
+    mut character_q: Query<
+        (
+            &Name,
+            &ChildOf,
+            &mut TimelineManager,
+            &mut GridPositionComponent,
+        ),
+        With<Character>,
+    >,
 ) {
-    use crate::timeline::{DraftTimeline, PublishTimeline, TimelineManager};
-
-    // Get the Bastion arena entity
-    let bastion_entity = arena_entities.get(ArenaName::Bastion);
-
-    let character_radius = 0.125;
-    let character_mesh = meshes.add(Sphere::new(character_radius));
-
-    // Create a green material for the Alchemist
-    let green_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.2, 0.8, 0.3), // Green
-        metallic: 0.3,
-        perceptual_roughness: 0.5,
-        ..default()
-    });
-
-    // Create empty timeline for the Alchemist
-    let alchemist_draft = DraftTimeline::new();
-    let alchemist_timeline = PublishTimeline::from_draft(alchemist_draft);
-    let mut alchemist_timeline_manager = TimelineManager::new();
-    alchemist_timeline_manager.set_timeline(ArenaName::Bastion, alchemist_timeline);
-
-    // Spawn Alchemist at position (30, 20) in the Bastion
-    let alchemist_position = get_local_tile_space(30.0, 20.0, character_radius);
-    commands.entity(bastion_entity).with_child((
-        Character,
-        ClassType::Alchemist,
-        // All four Alchemist abilities
-        AcidFlask::new(),
-        Ironskin::new(),
-        Siphon::new(),
-        Transmute::new(),
-        alchemist_timeline_manager,
-        Mesh3d(character_mesh),
-        MeshMaterial3d(green_material),
-        Transform::from_translation(alchemist_position),
-        Name::new("Zephyr"), // Random name for the Alchemist
-    ));
-
-    info!("Spawned Alchemist character with empty timeline in Bastion arena");
+    use crate::timeline::SavedTimeline;
+
+    for text in DEMO_TIMELINES {
+        let saved: SavedTimeline = match text.parse() {
+            Ok(saved) => saved,
+            Err(error) => {
+                warn!("Skipping demo timeline: {error}");
+                continue;
+            }
+        };
+        let arena_entity = arena_entities.get(saved.arena);
+        let hero = character_q.iter_mut().find(|(name, child_of, ..)| {
+            name.as_str() == saved.name && child_of.parent() == arena_entity
+        });
+        if let Some((_, _, mut manager, mut position)) = hero {
+            manager.set_timeline(saved.arena, saved.timeline);
+            position.0 = saved.start;
+        }
+    }
 }
 
 /// Mark characters that have published timelines as ghosts
 fn mark_timeline_ghosts(
     mut commands: Commands,
     arena_q: Query<(Entity, &Children, &Arena), With<Arena>>,
-    character_q: Query<(Entity, &TimelineManager), (With<Character>, Without<character::Ghost>)>,
+    character_q: Query<
+        (Entity, &TimelineManager, &GridPositionComponent),
+        (With<Character>, Without<character::Ghost>),
+    >,
 ) {
     use crate::character::Ghost;
+    use crate::timeline::ReplayOrigin;
 
     // Go through each arena
     for (_arena_entity, children, arena) in arena_q.iter() {
@@ -537,11 +414,17 @@ fn mark_timeline_ghosts(
 
         // Check each child character in this arena
         for child in children.iter() {
-            if let Ok((character_entity, timeline_manager)) = character_q.get(child) {
+            if let Ok((character_entity, timeline_manager, position)) = character_q.get(child) {
                 // Check if this character has a timeline for their parent arena
                 if timeline_manager.has_recording_for(arena_name) {
                     // This character has a timeline for their arena, mark as ghost
-                    commands.entity(character_entity).insert(Ghost);
+                    commands.entity(character_entity).insert((
+                        Ghost,
+                        ReplayOrigin {
+                            arena: arena_name,
+                            tile: position.0,
+                        },
+                    ));
                     info!("Marked character as Ghost in arena {:?}", arena_name);
                 }
             }
@@ -559,14 +442,16 @@ fn spawn_starting_bosses(
     let guildhouse_entity = arena_entities.get(ArenaName::GuildHouse);
     let boss_radius = 0.125 * 4.0;
     let boss_mesh = meshes.add(Sphere::new(boss_radius));
-    let local_position = get_local_tile_space(32.0, 10.0, boss_radius);
+    let tile = IVec2::new(32, 10);
 
     commands.entity(guildhouse_entity).with_child((
         Boss,
         Active,
+        Difficulty::default().boss_bundle(),
         Mesh3d(boss_mesh.clone()),
         MeshMaterial3d(mats.red.clone()),
-        Transform::from_translation(local_position),
+        GridPositionComponent(tile),
+        Transform::from_translation(tile_to_local_translation(tile, boss_radius)),
     ));
 
     // Spawn regular (inactive) bosses in all other arenas
@@ -576,10 +461,15 @@ fn spawn_starting_bosses(
             let boss_mesh = meshes.add(Sphere::new(boss_radius));
             commands.entity(arena_entity).with_child((
                 Boss,
+                Difficulty::default().boss_bundle(),
                 Mesh3d(boss_mesh),
                 MeshMaterial3d(mats.red.clone()),
-                Transform::from_translation(local_position),
+                GridPositionComponent(tile),
+                Transform::from_translation(tile_to_local_translation(tile, boss_radius)),
             ));
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,249 @@
+use crate::arena::{Arena, ArenaName, ArenaReset};
+use crate::character::{Character, Ghost};
+use crate::combat::{DamageDealt, HealingDone};
+use crate::guild::HeroId;
+use crate::timeline::TimeStamp;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+
+/// Threat generated per point of damage dealt
+const THREAT_PER_DAMAGE: f32 = 1.0;
+/// Threat generated per point of healing done - healers draw half as much attention
+const THREAT_PER_HEALING: f32 = 0.5;
+
+/// Totals a meter can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MeterColumn {
+    #[default]
+    Damage,
+    Healing,
+    DamageTaken,
+    Threat,
+}
+
+impl MeterColumn {
+    /// RULE 3 COMPLIANCE: Static data lookup
+    /// Column heading, shown as a per-second rate except for threat
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Damage => "DPS",
+            Self::Healing => "HPS",
+            Self::DamageTaken => "DTPS",
+            Self::Threat => "Threat",
+        }
+    }
+}
+
+/// Whose line a meter total lands on - roster heroes by id, so a hero keeps its line
+/// whether fighting live or replaying, and ghosts from outside the roster (saved or
+/// imported recordings) by entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MeterOwner {
+    Hero(HeroId),
+    Ghost(Entity),
+}
+
+impl MeterOwner {
+    /// Owner for a combatant - None for units that are neither roster heroes nor ghosts
+    #[must_use]
+    pub fn of(entity: Entity, hero: Option<&HeroId>, is_ghost: bool) -> Option<Self> {
+        match hero {
+            Some(hero) => Some(Self::Hero(*hero)),
+            None => is_ghost.then_some(Self::Ghost(entity)),
+        }
+    }
+}
+
+impl From<HeroId> for MeterOwner {
+    fn from(hero: HeroId) -> Self {
+        Self::Hero(hero)
+    }
+}
+
+/// One hero's output over a loop (value type)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HeroMeter {
+    pub damage: f32,
+    pub healing: f32,
+    pub damage_taken: f32,
+    pub threat: f32,
+}
+
+impl HeroMeter {
+    #[must_use]
+    pub fn total(&self, column: MeterColumn) -> f32 {
+        match column {
+            MeterColumn::Damage => self.damage,
+            MeterColumn::Healing => self.healing,
+            MeterColumn::DamageTaken => self.damage_taken,
+            MeterColumn::Threat => self.threat,
+        }
+    }
+}
+
+/// Average per second of a loop total over the loop time elapsed
+#[must_use]
+pub fn per_second(total: f32, elapsed: TimeStamp) -> f32 {
+    if elapsed.as_secs() <= 0.0 {
+        return 0.0;
+    }
+    total / elapsed.as_secs()
+}
+
+/// Every hero's output in one arena loop, keyed by `MeterOwner`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoopMeters {
+    /// Arena loop the totals belong to, counting from 0 at startup
+    pub loop_index: u32,
+    heroes: BTreeMap<MeterOwner, HeroMeter>,
+}
+
+impl LoopMeters {
+    #[must_use]
+    pub fn hero(&self, owner: impl Into<MeterOwner>) -> Option<&HeroMeter> {
+        self.heroes.get(&owner.into())
+    }
+
+    fn hero_mut(&mut self, owner: MeterOwner) -> &mut HeroMeter {
+        self.heroes.entry(owner).or_default()
+    }
+
+    /// Heroes highest first by `column` - ties broken by owner so the order is stable
+    #[must_use]
+    pub fn sorted_by(&self, column: MeterColumn) -> Vec<(MeterOwner, HeroMeter)> {
+        let mut heroes: Vec<_> = self
+            .heroes
+            .iter()
+            .map(|(hero, meter)| (*hero, *meter))
+            .collect();
+        heroes.sort_by(|(a, a_meter), (b, b_meter)| {
+            b_meter
+                .total(column)
+                .total_cmp(&a_meter.total(column))
+                .then(a.cmp(b))
+        });
+        heroes
+    }
+}
+
+/// One arena's running loop and every loop it has finished
+#[derive(Debug, Clone, Default)]
+pub struct ArenaMeters {
+    pub current: LoopMeters,
+    /// Finished loops, oldest first
+    pub history: Vec<LoopMeters>,
+}
+
+impl ArenaMeters {
+    /// The loop finished just before the running one
+    #[must_use]
+    pub fn previous(&self) -> Option<&LoopMeters> {
+        self.history.last()
+    }
+}
+
+/// Damage, healing, damage taken and threat for every hero, per arena and per loop
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Meters([ArenaMeters; 9]);
+
+impl Meters {
+    #[must_use]
+    pub fn arena(&self, arena: ArenaName) -> &ArenaMeters {
+        &self.0[arena.as_u8() as usize]
+    }
+
+    fn current_mut(&mut self, arena: ArenaName) -> &mut LoopMeters {
+        &mut self.0[arena.as_u8() as usize].current
+    }
+
+    pub fn record_damage(&mut self, arena: ArenaName, hero: impl Into<MeterOwner>, amount: f32) {
+        let meter = self.current_mut(arena).hero_mut(hero.into());
+        meter.damage += amount;
+        meter.threat += amount * THREAT_PER_DAMAGE;
+    }
+
+    pub fn record_healing(&mut self, arena: ArenaName, hero: impl Into<MeterOwner>, amount: f32) {
+        let meter = self.current_mut(arena).hero_mut(hero.into());
+        meter.healing += amount;
+        meter.threat += amount * THREAT_PER_HEALING;
+    }
+
+    pub fn record_damage_taken(
+        &mut self,
+        arena: ArenaName,
+        hero: impl Into<MeterOwner>,
+        amount: f32,
+    ) {
+        self.current_mut(arena).hero_mut(hero.into()).damage_taken += amount;
+    }
+
+    /// Files the running loop into history and starts the next one
+    pub fn start_loop(&mut self, arena: ArenaName) {
+        let meters = &mut self.0[arena.as_u8() as usize];
+        let next = LoopMeters {
+            loop_index: meters.current.loop_index + 1,
+            heroes: BTreeMap::new(),
+        };
+        let finished = std::mem::replace(&mut meters.current, next);
+        meters.history.push(finished);
+    }
+}
+
+/// Heroes and ghosts, with what decides whose meter line they land on
+type MeteredUnitQuery<'w, 's> =
+    Query<'w, 's, (Option<&'static HeroId>, Has<Ghost>, &'static ChildOf), With<Character>>;
+
+/// Credits landed damage and healing to the heroes and ghosts involved
+pub fn accumulate_meters(
+    mut dealt_events: EventReader<DamageDealt>,
+    mut healing_events: EventReader<HealingDone>,
+    hero_q: MeteredUnitQuery,
+    arena_q: Query<&Arena>,
+    mut meters: ResMut<Meters>,
+) {
+    let hero_in_arena = |entity: Entity| {
+        let (hero, is_ghost, child_of) = hero_q.get(entity).ok()?;
+        let owner = MeterOwner::of(entity, hero, is_ghost)?;
+        let arena = arena_q.get(child_of.parent()).ok()?;
+        Some((arena.0, owner))
+    };
+    for event in dealt_events.read() {
+        if let Some((arena, hero)) = hero_in_arena(event.source) {
+            meters.record_damage(arena, hero, event.amount);
+        }
+        if let Some((arena, hero)) = hero_in_arena(event.target) {
+            meters.record_damage_taken(arena, hero, event.amount);
+        }
+    }
+    for event in healing_events.read() {
+        if let Some((arena, hero)) = hero_in_arena(event.source) {
+            meters.record_healing(arena, hero, event.amount);
+        }
+    }
+}
+
+/// Closes each arena's meters when its loop wraps
+pub fn roll_meter_loops(mut reset_events: EventReader<ArenaReset>, mut meters: ResMut<Meters>) {
+    for event in reset_events.read() {
+        meters.start_loop(event.arena);
+    }
+}
+
+/// Plugin measuring every hero's contribution per arena loop
+pub struct MetersPlugin;
+
+impl Plugin for MetersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Meters>()
+            .add_event::<DamageDealt>()
+            .add_event::<HealingDone>()
+            .add_event::<ArenaReset>()
+            // Same as the combat log: a loop that wrapped this frame is closed
+            // before the new loop's first hits are counted
+            .add_systems(PostUpdate, (roll_meter_loops, accumulate_meters).chain());
+    }
+}
+
+#[cfg(test)]
+mod tests;
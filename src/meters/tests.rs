@@ -0,0 +1,148 @@
+use super::*;
+use crate::ability::{AbilityType, HunterAbility};
+use crate::class_type::ClassType;
+use crate::combat::{CombatPlugin, DamageCause, DamageEvent, HealEvent, Health};
+use crate::timeline::{DraftTimeline, PublishTimeline, SavedTimeline};
+
+fn meters_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins((CombatPlugin, MetersPlugin));
+    let gala = app.world_mut().spawn(Arena(ArenaName::Gala)).id();
+    (app, gala)
+}
+
+fn spawn_hero(app: &mut App, arena: Entity, id: u32) -> Entity {
+    app.world_mut()
+        .spawn((Character, HeroId(id), Health::new(1_000.0), ChildOf(arena)))
+        .id()
+}
+
+#[test]
+fn test_landed_damage_healing_and_hits_taken_are_metered_per_hero() {
+    let (mut app, gala) = meters_app();
+    let hero = spawn_hero(&mut app, gala, 1);
+    let healer = spawn_hero(&mut app, gala, 2);
+    let boss = app
+        .world_mut()
+        .spawn((Health::new(150.0), ChildOf(gala)))
+        .id();
+
+    let world = app.world_mut();
+    world.send_event(DamageEvent {
+        source: hero,
+        target: boss,
+        amount: 200.0,
+        cause: DamageCause::Ability(AbilityType::Hunter(HunterAbility::AutoShot)),
+    });
+    world.send_event(DamageEvent {
+        source: boss,
+        target: hero,
+        amount: 100.0,
+        cause: DamageCause::BossAction("Charge Strike"),
+    });
+    world.send_event(HealEvent {
+        source: healer,
+        target: hero,
+        amount: 250.0,
+    });
+    app.update();
+
+    let meters = app.world().resource::<Meters>();
+    let current = &meters.arena(ArenaName::Gala).current;
+    let dealer = current.hero(HeroId(1)).unwrap();
+    // Overkill and overheal are not credited
+    assert_eq!(dealer.damage, 150.0);
+    assert_eq!(dealer.damage_taken, 100.0);
+    assert_eq!(dealer.threat, 150.0);
+    let mender = current.hero(HeroId(2)).unwrap();
+    assert_eq!(mender.healing, 100.0);
+    assert_eq!(mender.threat, 50.0);
+    // The boss is not a roster hero and gets no meter
+    assert_eq!(current.heroes.len(), 2);
+    assert_eq!(
+        current
+            .sorted_by(MeterColumn::Healing)
+            .iter()
+            .map(|(hero, _)| *hero)
+            .collect::<Vec<_>>(),
+        [MeterOwner::Hero(HeroId(2)), MeterOwner::Hero(HeroId(1))]
+    );
+}
+
+#[test]
+fn test_loops_are_kept_in_history_when_the_arena_resets() {
+    let (mut app, gala) = meters_app();
+    let hero = spawn_hero(&mut app, gala, 1);
+    let boss = app
+        .world_mut()
+        .spawn((Health::new(10_000.0), ChildOf(gala)))
+        .id();
+    let hit = DamageEvent {
+        source: hero,
+        target: boss,
+        amount: 120.0,
+        cause: DamageCause::Ability(AbilityType::Hunter(HunterAbility::AutoShot)),
+    };
+    app.world_mut().send_event(hit.clone());
+    app.update();
+    app.world_mut().send_event(ArenaReset {
+        arena: ArenaName::Gala,
+    });
+    app.world_mut().send_event(hit.clone());
+    app.world_mut().send_event(hit);
+    app.update();
+
+    let meters = app.world().resource::<Meters>().arena(ArenaName::Gala);
+    let previous = meters.previous().unwrap();
+    assert_eq!(previous.loop_index, 0);
+    assert_eq!(previous.hero(HeroId(1)).unwrap().damage, 120.0);
+    assert_eq!(meters.current.loop_index, 1);
+    assert_eq!(meters.current.hero(HeroId(1)).unwrap().damage, 240.0);
+    assert_eq!(
+        per_second(previous.hero(HeroId(1)).unwrap().damage, TimeStamp::MAX),
+        1.0
+    );
+}
+
+#[test]
+fn test_ghosts_from_outside_the_roster_are_metered_by_entity() {
+    let (mut app, gala) = meters_app();
+    let saved = SavedTimeline {
+        name: "Wren".to_string(),
+        class: ClassType::Hunter,
+        arena: ArenaName::Gala,
+        start: IVec2::new(20, 15),
+        timeline: PublishTimeline::from_draft(DraftTimeline::new()),
+    };
+    let ghost = app
+        .world_mut()
+        .spawn((saved.ghost_bundle(ArenaName::Gala), ChildOf(gala)))
+        .id();
+    let boss = app
+        .world_mut()
+        .spawn((Health::new(10_000.0), ChildOf(gala)))
+        .id();
+    app.world_mut().send_event(DamageEvent {
+        source: ghost,
+        target: boss,
+        amount: 90.0,
+        cause: DamageCause::Ability(AbilityType::Hunter(HunterAbility::AutoShot)),
+    });
+    app.world_mut().send_event(DamageEvent {
+        source: boss,
+        target: ghost,
+        amount: 40.0,
+        cause: DamageCause::BossAction("Charge Strike"),
+    });
+    app.update();
+
+    let meters = app.world().resource::<Meters>();
+    let meter = meters
+        .arena(ArenaName::Gala)
+        .current
+        .hero(MeterOwner::Ghost(ghost))
+        .unwrap();
+    assert_eq!(meter.damage, 90.0);
+    assert_eq!(meter.damage_taken, 40.0);
+}
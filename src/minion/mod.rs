@@ -0,0 +1,332 @@
+// Standard library and external crates
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::*;
+
+// Local crate modules
+use crate::arena::{
+    Arena, ArenaEntities, ArenaName, ArenaOccupancy, ArenaReset, MoveRequest,
+    tile_to_local_translation,
+};
+use crate::boss::{BossPhaseChanged, ScriptCursor};
+use crate::character::Character;
+use crate::combat::Health;
+use crate::materials::Materials;
+use crate::pathfinding::find_path;
+use crate::status_effect::StatusEffects;
+use crate::timeline::{GridPositionComponent, TimeStamp, TimelineClock};
+
+/// Maximum number of live minions per arena - keeps nine concurrent arenas performant
+pub const MAX_MINIONS_PER_ARENA: usize = 24;
+
+/// Kinds of boss adds summoned by encounters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MinionKind {
+    Spiderling,
+    Brood,
+    Decoy,
+    /// Bastion construct that fortifies the boss while it lives
+    Construct,
+    /// Sanctum add that spreads its debuffs when it dies
+    Infested,
+    /// Casino add that carries treasure back to the boss
+    Goldling,
+    /// Mountain add summoned by Vine Shield that chases down heroes
+    Vine,
+}
+
+/// Static per-kind minion data
+#[derive(Debug, Clone, Copy)]
+pub struct MinionStats {
+    pub max_health: f32,
+    /// Loop seconds between single-tile steps
+    pub move_interval: f32,
+    /// Visual sphere radius
+    pub radius: f32,
+    /// Whether the minion chases heroes or holds its spawn tile
+    pub chases: bool,
+}
+
+impl MinionKind {
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn stats(self) -> MinionStats {
+        match self {
+            Self::Spiderling => MinionStats {
+                max_health: 150.0,
+                move_interval: 0.5,
+                radius: 0.0625,
+                chases: true,
+            },
+            Self::Brood => MinionStats {
+                max_health: 1_200.0,
+                move_interval: 1.5,
+                radius: 0.25,
+                chases: true,
+            },
+            Self::Decoy => MinionStats {
+                max_health: 1.0,
+                move_interval: 1.0,
+                radius: 0.5,
+                chases: false,
+            },
+            Self::Construct => MinionStats {
+                max_health: 800.0,
+                move_interval: 1.0,
+                radius: 0.1875,
+                chases: false,
+            },
+            Self::Goldling => MinionStats {
+                max_health: 150.0,
+                move_interval: 0.5,
+                radius: 0.0625,
+                chases: false,
+            },
+            Self::Infested => MinionStats {
+                max_health: 250.0,
+                move_interval: 0.75,
+                radius: 0.09375,
+                chases: true,
+            },
+            Self::Vine => MinionStats {
+                max_health: 300.0,
+                move_interval: 1.0,
+                radius: 0.09375,
+                chases: true,
+            },
+        }
+    }
+}
+
+/// Component marking an entity as a boss add of the given kind
+#[derive(Component, Debug, Clone, Copy)]
+#[require(StatusEffects)]
+pub struct Minion(pub MinionKind);
+
+/// Minion AI state machine
+/// RULE 26 COMPLIANCE: One state component per state machine
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinionBehavior {
+    /// Holds position until a hero is available
+    Idle,
+    /// Walks one tile at a time toward the target hero
+    Chase(Entity),
+    /// Never moves (decoys, stationary adds)
+    Hold,
+}
+
+/// Request to summon a minion into an arena at a local tile
+#[derive(Event, Debug, Clone)]
+pub struct SpawnMinion {
+    pub arena: ArenaName,
+    pub kind: MinionKind,
+    pub tile: IVec2,
+}
+
+/// Spawns requested minions as children of their arena, enforcing the per-arena cap
+pub fn spawn_minions(
+    mut commands: Commands,
+    mut spawn_events: EventReader<SpawnMinion>,
+    arena_entities: Res<ArenaEntities>,
+    minion_q: Query<&ChildOf, With<Minion>>,
+    arena_q: Query<&Arena>,
+    clock_q: Query<&TimelineClock>,
+) {
+    if spawn_events.is_empty() {
+        return;
+    }
+
+    let mut counts = [0usize; 9];
+    for child_of in minion_q.iter() {
+        if let Ok(arena) = arena_q.get(child_of.parent()) {
+            counts[arena.0.as_u8() as usize] += 1;
+        }
+    }
+
+    for event in spawn_events.read() {
+        let count = &mut counts[event.arena.as_u8() as usize];
+        if *count >= MAX_MINIONS_PER_ARENA {
+            warn!(
+                "Minion cap of {} reached in {} - skipping {:?}",
+                MAX_MINIONS_PER_ARENA, event.arena, event.kind
+            );
+            continue;
+        }
+        *count += 1;
+
+        let arena_entity = arena_entities.get(event.arena);
+        let spawned_at = clock_q
+            .get(arena_entity)
+            .map_or(TimeStamp::ZERO, TimelineClock::current);
+        let stats = event.kind.stats();
+        let behavior = if stats.chases {
+            MinionBehavior::Idle
+        } else {
+            MinionBehavior::Hold
+        };
+        commands.spawn((
+            Minion(event.kind),
+            behavior,
+            Health::new(stats.max_health),
+            // Steps follow the arena clock so replayed loops move minions identically
+            ScriptCursor(spawned_at),
+            GridPositionComponent(event.tile),
+            Transform::from_translation(tile_to_local_translation(event.tile, stats.radius)),
+            ChildOf(arena_entity),
+            Name::new(format!("{:?}", event.kind)),
+        ));
+    }
+}
+
+/// Gives newly spawned minions a mesh and material
+pub fn attach_minion_visuals(
+    mut commands: Commands,
+    minion_q: Query<(Entity, &Minion), Added<Minion>>,
+    mats: Res<Materials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, minion) in minion_q.iter() {
+        let mesh = meshes.add(Sphere::new(minion.0.stats().radius));
+        commands
+            .entity(entity)
+            .insert((Mesh3d(mesh), MeshMaterial3d(mats.red.clone())));
+    }
+}
+
+/// Despawns every minion parented to the given arena entity
+fn despawn_arena_minions(
+    commands: &mut Commands,
+    arena_entity: Entity,
+    minion_q: &Query<(Entity, &ChildOf), With<Minion>>,
+) {
+    for (entity, child_of) in minion_q.iter() {
+        if child_of.parent() == arena_entity {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Clears an arena's minions when its boss changes phase
+pub fn despawn_minions_on_phase_change(
+    mut commands: Commands,
+    mut phase_events: EventReader<BossPhaseChanged>,
+    arena_entities: Res<ArenaEntities>,
+    minion_q: Query<(Entity, &ChildOf), With<Minion>>,
+) {
+    for event in phase_events.read() {
+        despawn_arena_minions(&mut commands, arena_entities.get(event.arena), &minion_q);
+    }
+}
+
+/// Clears an arena's minions when its 2-minute loop restarts
+pub fn despawn_minions_on_arena_reset(
+    mut commands: Commands,
+    mut reset_events: EventReader<ArenaReset>,
+    arena_entities: Res<ArenaEntities>,
+    minion_q: Query<(Entity, &ChildOf), With<Minion>>,
+) {
+    for event in reset_events.read() {
+        despawn_arena_minions(&mut commands, arena_entities.get(event.arena), &minion_q);
+    }
+}
+
+/// Points idle or chasing minions at the nearest hero in their own arena
+pub fn select_minion_targets(
+    mut minion_q: Query<(&mut MinionBehavior, &GridPositionComponent, &ChildOf), With<Minion>>,
+    character_q: Query<(Entity, &GridPositionComponent, &ChildOf), With<Character>>,
+) {
+    for (mut behavior, position, minion_parent) in minion_q.iter_mut() {
+        if *behavior == MinionBehavior::Hold {
+            continue;
+        }
+
+        let nearest = character_q
+            .iter()
+            .filter(|(_, _, parent)| parent.parent() == minion_parent.parent())
+            .min_by_key(|(_, hero, _)| hero.0.distance_squared(position.0))
+            .map(|(entity, _, _)| entity);
+
+        let next = nearest.map_or(MinionBehavior::Idle, MinionBehavior::Chase);
+        if *behavior != next {
+            *behavior = next;
+        }
+    }
+}
+
+/// Requests the first step of a path toward each chasing minion's target
+pub fn step_minions(
+    mut minion_q: Query<(
+        Entity,
+        &Minion,
+        &MinionBehavior,
+        &mut ScriptCursor,
+        &GridPositionComponent,
+        &ChildOf,
+    )>,
+    character_q: Query<&GridPositionComponent, With<Character>>,
+    arena_q: Query<(&Arena, &ArenaOccupancy, &TimelineClock)>,
+    mut move_requests: EventWriter<MoveRequest>,
+) {
+    for (entity, minion, behavior, mut cursor, position, child_of) in minion_q.iter_mut() {
+        let Ok((arena, occupancy, clock)) = arena_q.get(child_of.parent()) else {
+            continue;
+        };
+        let interval = minion.0.stats().move_interval;
+        if !cursor.advance(clock).crossed(interval, interval) {
+            continue;
+        }
+        let MinionBehavior::Chase(target) = *behavior else {
+            continue;
+        };
+        let Ok(target_position) = character_q.get(target) else {
+            continue;
+        };
+
+        let Some(next) = find_path(occupancy, entity, position.0, target_position.0)
+            .and_then(|path| path.first().copied())
+        else {
+            continue;
+        };
+        // Adjacent to the target - the last step would land on the hero
+        if next == target_position.0 {
+            continue;
+        }
+
+        move_requests.write(MoveRequest {
+            entity,
+            to_arena: arena.0,
+            to_tile: next,
+        });
+    }
+}
+
+/// Plugin for boss adds: spawning, per-arena caps, cleanup and simple chase AI.
+/// Renders nothing, so the headless simulation runs the same schedule as the game.
+pub struct MinionPlugin;
+
+impl Plugin for MinionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SpawnMinion>().add_systems(
+            Update,
+            (
+                despawn_minions_on_arena_reset,
+                despawn_minions_on_phase_change,
+                spawn_minions,
+                select_minion_targets,
+                step_minions,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Plugin giving spawned minions their mesh - windowed game only
+pub struct MinionVisualsPlugin;
+
+impl Plugin for MinionVisualsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, attach_minion_visuals.after(spawn_minions));
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,191 @@
+use super::*;
+use crate::arena::GRID_WIDTH;
+use bevy::app::{App, Update};
+use bevy::ecs::system::RunSystemOnce;
+use std::time::Duration;
+
+/// Builds an app with two arenas and the minion logic systems (no visuals)
+fn create_test_app() -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.add_event::<SpawnMinion>()
+        .add_event::<BossPhaseChanged>()
+        .add_event::<ArenaReset>();
+    app.add_systems(
+        Update,
+        (
+            despawn_minions_on_arena_reset,
+            despawn_minions_on_phase_change,
+            spawn_minions,
+            select_minion_targets,
+        )
+            .chain(),
+    );
+
+    let world = app.world_mut();
+    let labyrinth = world
+        .spawn((Arena(ArenaName::Labyrinth), Transform::default()))
+        .id();
+    let gala = world
+        .spawn((Arena(ArenaName::Gala), Transform::default()))
+        .id();
+    world.insert_resource(ArenaEntities::new([
+        (ArenaName::Labyrinth, labyrinth),
+        (ArenaName::GuildHouse, Entity::PLACEHOLDER),
+        (ArenaName::Sanctum, Entity::PLACEHOLDER),
+        (ArenaName::Mountain, Entity::PLACEHOLDER),
+        (ArenaName::Bastion, Entity::PLACEHOLDER),
+        (ArenaName::Pawnshop, Entity::PLACEHOLDER),
+        (ArenaName::Crucible, Entity::PLACEHOLDER),
+        (ArenaName::Casino, Entity::PLACEHOLDER),
+        (ArenaName::Gala, gala),
+    ]));
+
+    (app, labyrinth, gala)
+}
+
+fn minions_in(app: &mut App, arena_entity: Entity) -> usize {
+    app.world_mut()
+        .run_system_once(move |minion_q: Query<&ChildOf, With<Minion>>| {
+            minion_q
+                .iter()
+                .filter(|child_of| child_of.parent() == arena_entity)
+                .count()
+        })
+        .expect("Failed to count minions")
+}
+
+fn request_minions(app: &mut App, arena: ArenaName, count: usize) {
+    for i in 0..count {
+        app.world_mut().send_event(SpawnMinion {
+            arena,
+            kind: MinionKind::Spiderling,
+            tile: IVec2::new(i as i32 % GRID_WIDTH as i32, 0),
+        });
+    }
+}
+
+#[test]
+fn test_minions_spawn_as_children_of_their_arena() {
+    let (mut app, labyrinth, gala) = create_test_app();
+
+    request_minions(&mut app, ArenaName::Gala, 3);
+    app.update();
+
+    assert_eq!(minions_in(&mut app, gala), 3);
+    assert_eq!(minions_in(&mut app, labyrinth), 0);
+}
+
+#[test]
+fn test_minion_cap_is_enforced_per_arena() {
+    let (mut app, labyrinth, gala) = create_test_app();
+
+    request_minions(&mut app, ArenaName::Labyrinth, MAX_MINIONS_PER_ARENA + 5);
+    request_minions(&mut app, ArenaName::Gala, 2);
+    app.update();
+
+    assert_eq!(minions_in(&mut app, labyrinth), MAX_MINIONS_PER_ARENA);
+    assert_eq!(minions_in(&mut app, gala), 2);
+
+    // The cap also applies across frames
+    request_minions(&mut app, ArenaName::Labyrinth, 1);
+    app.update();
+    assert_eq!(minions_in(&mut app, labyrinth), MAX_MINIONS_PER_ARENA);
+}
+
+#[test]
+fn test_minions_despawn_on_arena_reset_and_phase_change() {
+    let (mut app, labyrinth, gala) = create_test_app();
+
+    request_minions(&mut app, ArenaName::Labyrinth, 4);
+    request_minions(&mut app, ArenaName::Gala, 4);
+    app.update();
+
+    app.world_mut().send_event(ArenaReset {
+        arena: ArenaName::Labyrinth,
+    });
+    app.update();
+    assert_eq!(minions_in(&mut app, labyrinth), 0);
+    assert_eq!(minions_in(&mut app, gala), 4);
+
+    app.world_mut().send_event(BossPhaseChanged {
+        boss: Entity::PLACEHOLDER,
+        arena: ArenaName::Gala,
+    });
+    app.update();
+    assert_eq!(minions_in(&mut app, gala), 0);
+}
+
+#[test]
+fn test_minions_only_chase_heroes_in_their_own_arena() {
+    let (mut app, labyrinth, gala) = create_test_app();
+
+    let hero = app
+        .world_mut()
+        .spawn((
+            Character,
+            GridPositionComponent(IVec2::new(10, 10)),
+            ChildOf(gala),
+        ))
+        .id();
+    request_minions(&mut app, ArenaName::Gala, 1);
+    request_minions(&mut app, ArenaName::Labyrinth, 1);
+    app.update();
+    app.update();
+
+    let behaviors = app
+        .world_mut()
+        .run_system_once(|minion_q: Query<(&MinionBehavior, &ChildOf)>| {
+            minion_q
+                .iter()
+                .map(|(behavior, child_of)| (*behavior, child_of.parent()))
+                .collect::<Vec<_>>()
+        })
+        .expect("Failed to read minion behaviors");
+
+    for (behavior, parent) in behaviors {
+        if parent == gala {
+            assert_eq!(behavior, MinionBehavior::Chase(hero));
+        } else {
+            assert_eq!(parent, labyrinth);
+            assert_eq!(behavior, MinionBehavior::Idle);
+        }
+    }
+}
+
+#[test]
+fn test_minions_step_on_the_arena_clock() {
+    let (mut app, _, gala) = create_test_app();
+    app.add_systems(Update, step_minions.after(select_minion_targets));
+    app.add_event::<MoveRequest>();
+    app.world_mut()
+        .entity_mut(gala)
+        .insert(TimelineClock::default());
+    app.world_mut().spawn((
+        Character,
+        GridPositionComponent(IVec2::new(10, 0)),
+        ChildOf(gala),
+    ));
+    request_minions(&mut app, ArenaName::Gala, 1);
+    app.update();
+
+    let advance_clock = |app: &mut App, seconds: f32| {
+        app.world_mut()
+            .get_mut::<TimelineClock>(gala)
+            .unwrap()
+            .tick(Duration::from_secs_f32(seconds));
+        app.update();
+        app.world()
+            .resource::<Events<MoveRequest>>()
+            .iter_current_update_events()
+            .count()
+    };
+
+    // A paused clock never moves the minion, however many frames pass
+    assert_eq!(advance_clock(&mut app, 0.0), 0);
+    assert_eq!(advance_clock(&mut app, 0.0), 0);
+
+    let interval = MinionKind::Spiderling.stats().move_interval;
+    assert_eq!(advance_clock(&mut app, interval * 0.5), 0);
+    assert_eq!(advance_clock(&mut app, interval * 0.5), 1);
+}
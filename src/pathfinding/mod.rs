@@ -0,0 +1,308 @@
+// Standard library and external crates
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+// Local crate modules
+use crate::arena::{
+    Arena, ArenaOccupancy, CurrentArenaEntity, GRID_WIDTH, MoveRequest, TILE_SIZE,
+    is_local_tile_in_bounds,
+};
+use crate::boss::ScriptCursor;
+use crate::character::Character;
+use crate::selectors::Active;
+use crate::timeline::{
+    DraftTimeline, GlobalTimelinePause, GridPositionComponent, TimelineClock, TimelineEvent,
+};
+
+/// Loop seconds between steps when a hero walks a clicked path
+pub const CLICK_MOVE_STEP_SECONDS: f32 = 0.15;
+
+/// Upper bound on expanded tiles - a full arena is 2046 tiles
+const MAX_EXPANDED_TILES: usize = 4_096;
+
+const NEIGHBOURS: [IVec2; 4] = [IVec2::Y, IVec2::NEG_Y, IVec2::NEG_X, IVec2::X];
+
+/// Finds the cheapest 4-way path between two local tiles of one arena
+///
+/// Costs come from each tile's terrain; tiles held by other units are impassable,
+/// except the goal itself so chasers can path up to an occupied target.
+/// Returns the tiles to step through, excluding `start` and including `goal`.
+#[must_use]
+pub fn find_path(
+    occupancy: &ArenaOccupancy,
+    mover: Entity,
+    start: IVec2,
+    goal: IVec2,
+) -> Option<Vec<IVec2>> {
+    if !is_local_tile_in_bounds(start) || occupancy.terrain(goal).blocks_movement() {
+        return None;
+    }
+    if start == goal {
+        return Some(Vec::new());
+    }
+
+    let heuristic = |tile: IVec2| (tile - goal).abs().element_sum() as u32;
+    let key = |tile: IVec2| tile.y * GRID_WIDTH as i32 + tile.x;
+
+    // Ordered by (f, h, row-major tile) so equal-cost paths are always chosen the same way
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<i32, IVec2> = HashMap::new();
+    let mut best_cost: HashMap<i32, u32> = HashMap::new();
+    open.push(Reverse((heuristic(start), heuristic(start), key(start))));
+    best_cost.insert(key(start), 0);
+
+    let mut expanded = 0;
+    while let Some(Reverse((estimate, _, current_key))) = open.pop() {
+        let current = IVec2::new(
+            current_key % GRID_WIDTH as i32,
+            current_key / GRID_WIDTH as i32,
+        );
+        let current_cost = best_cost[&current_key];
+        // Skip stale heap entries superseded by a cheaper route
+        if estimate > current_cost + heuristic(current) {
+            continue;
+        }
+        if current == goal {
+            let mut path = vec![goal];
+            let mut tile = goal;
+            while let Some(&previous) = came_from.get(&key(tile)) {
+                if previous == start {
+                    break;
+                }
+                path.push(previous);
+                tile = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        expanded += 1;
+        if expanded > MAX_EXPANDED_TILES {
+            return None;
+        }
+
+        for offset in NEIGHBOURS {
+            let next = current + offset;
+            let Some(step_cost) = occupancy.terrain(next).traversal_cost() else {
+                continue;
+            };
+            if next != goal && occupancy.occupant(next).is_some_and(|unit| unit != mover) {
+                continue;
+            }
+
+            let cost = current_cost + step_cost;
+            if best_cost
+                .get(&key(next))
+                .is_some_and(|&known| known <= cost)
+            {
+                continue;
+            }
+            best_cost.insert(key(next), cost);
+            came_from.insert(key(next), current);
+            open.push(Reverse((
+                cost + heuristic(next),
+                heuristic(next),
+                key(next),
+            )));
+        }
+    }
+
+    None
+}
+
+/// Walks a unit along a precomputed path, one tile per interval of its arena's clock
+#[derive(Component, Debug)]
+#[require(ScriptCursor)]
+pub struct PathFollower {
+    pub goal: IVec2,
+    pub steps: VecDeque<IVec2>,
+    pub step_seconds: f32,
+}
+
+impl PathFollower {
+    #[must_use]
+    pub fn new(goal: IVec2, path: Vec<IVec2>, step_seconds: f32) -> Self {
+        Self {
+            goal,
+            steps: path.into(),
+            step_seconds,
+        }
+    }
+}
+
+/// Window and camera that turn the cursor into a ray through the battleground
+#[derive(SystemParam)]
+pub struct CursorCamera<'w, 's> {
+    window: Single<'w, &'static Window, With<PrimaryWindow>>,
+    camera: Single<'w, (&'static Camera, &'static GlobalTransform), With<Camera3d>>,
+    interaction_q: Query<'w, 's, &'static Interaction>,
+}
+
+impl CursorCamera<'_, '_> {
+    /// Whether the cursor is over a HUD button rather than the battleground
+    #[must_use]
+    pub fn over_ui(&self) -> bool {
+        self.interaction_q
+            .iter()
+            .any(|interaction| *interaction != Interaction::None)
+    }
+
+    /// Ray from the camera through the cursor, if the cursor is over the window
+    #[must_use]
+    pub fn ray(&self) -> Option<Ray3d> {
+        let cursor = self.window.cursor_position()?;
+        let (camera, camera_transform) = *self.camera;
+        camera.viewport_to_world(camera_transform, cursor).ok()
+    }
+}
+
+/// The hero the player is controlling, with its tile
+pub type ActiveHero<'w> =
+    Single<'w, (Entity, &'static GridPositionComponent), (With<Character>, With<Active>)>;
+
+/// Units walking a path, with what decides whether their steps are recorded
+pub type FollowerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut PathFollower,
+        &'static mut ScriptCursor,
+        &'static GridPositionComponent,
+        &'static ChildOf,
+        Has<Active>,
+        Has<Character>,
+    ),
+>;
+
+/// Plans a path for the active hero to the clicked tile of the current arena
+pub fn click_to_move(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    cursor_camera: CursorCamera,
+    current: CurrentArenaEntity,
+    arena_q: Query<(&GlobalTransform, &ArenaOccupancy), With<Arena>>,
+    active_character_q: ActiveHero,
+    global_pause: Res<GlobalTimelinePause>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) || global_pause.is_paused {
+        return;
+    }
+
+    // Clicks on HUD buttons are not move orders
+    if cursor_camera.over_ui() {
+        return;
+    }
+
+    let Some(ray) = cursor_camera.ray() else {
+        return;
+    };
+    let Ok((arena_transform, occupancy)) = arena_q.get(current.get()) else {
+        return;
+    };
+    let Some(distance) =
+        ray.intersect_plane(arena_transform.translation(), InfinitePlane3d::new(Vec3::Z))
+    else {
+        return;
+    };
+
+    let local = ray.get_point(distance) - arena_transform.translation();
+    let goal = (local.truncate() / TILE_SIZE).round().as_ivec2();
+    if !is_local_tile_in_bounds(goal) {
+        return;
+    }
+
+    let (character_entity, grid_position) = active_character_q.into_inner();
+    match find_path(occupancy, character_entity, grid_position.0, goal) {
+        Some(path) => {
+            commands.entity(character_entity).insert(PathFollower::new(
+                goal,
+                path,
+                CLICK_MOVE_STEP_SECONDS,
+            ));
+        }
+        None => debug!("No path to {} in {}", goal, current.name()),
+    }
+}
+
+/// Issues move requests for units following paths, re-planning around new obstacles
+///
+/// Steps taken by the live active hero are recorded as regular Movement events.
+pub fn follow_paths(
+    mut commands: Commands,
+    global_pause: Res<GlobalTimelinePause>,
+    mut follower_q: FollowerQuery,
+    arena_q: Query<(&Arena, &ArenaOccupancy, &TimelineClock)>,
+    mut move_requests: EventWriter<MoveRequest>,
+    mut draft_timeline: ResMut<DraftTimeline>,
+) {
+    if global_pause.is_paused {
+        return;
+    }
+
+    for (entity, mut follower, mut cursor, position, child_of, is_active, is_character) in
+        follower_q.iter_mut()
+    {
+        let Ok((arena, occupancy, clock)) = arena_q.get(child_of.parent()) else {
+            continue;
+        };
+        // Steps follow the arena clock so a replayed loop walks the path identically
+        let interval = follower.step_seconds;
+        if !cursor.advance(clock).crossed(interval, interval) {
+            continue;
+        }
+
+        // Re-plan when a step was refused or something moved into the way
+        let next_is_clear = follower.steps.front().is_some_and(|&next| {
+            (next - position.0).abs().element_sum() == 1 && !occupancy.is_blocked(next, entity)
+        });
+        if !next_is_clear {
+            let goal = follower.goal;
+            match find_path(occupancy, entity, position.0, goal) {
+                Some(path)
+                    if path
+                        .first()
+                        .is_some_and(|&next| !occupancy.is_blocked(next, entity)) =>
+                {
+                    follower.steps = path.into();
+                }
+                _ => {
+                    commands.entity(entity).remove::<PathFollower>();
+                    continue;
+                }
+            }
+        }
+
+        let Some(next) = follower.steps.pop_front() else {
+            commands.entity(entity).remove::<PathFollower>();
+            continue;
+        };
+        move_requests.write(MoveRequest {
+            entity,
+            to_arena: arena.0,
+            to_tile: next,
+        });
+
+        if is_character && is_active {
+            let event = TimelineEvent::movement(clock.current(), next - position.0);
+            if let Err(e) = draft_timeline.add_event(event) {
+                warn!("Failed to record movement event: {:?}", e);
+            }
+        }
+    }
+}
+
+/// Plugin for A* pathfinding and click-to-move
+pub struct PathfindingPlugin;
+
+impl Plugin for PathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (click_to_move, follow_paths).chain());
+    }
+}
+
+#[cfg(test)]
+mod tests;
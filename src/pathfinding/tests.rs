@@ -0,0 +1,274 @@
+use super::*;
+use crate::ability::{
+    AbilityCast, AbilityType, ForagerAbility, HunterAbility, clear_cast_terrain_on_reset,
+    lay_cast_terrain,
+};
+use crate::arena::{
+    ArenaEntities, ArenaName, ArenaReset, CurrentArena, GRID_HEIGHT, OccupancyPlugin, TileTerrain,
+};
+use crate::character::Boss;
+use crate::recording::Playback;
+use crate::timeline::{EventType, update_timeline_clocks};
+use bevy::app::{App, Update};
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+const MOVER: Entity = Entity::PLACEHOLDER;
+
+fn path_cost(occupancy: &ArenaOccupancy, path: &[IVec2]) -> u32 {
+    path.iter()
+        .map(|&tile| occupancy.terrain(tile).traversal_cost().unwrap())
+        .sum()
+}
+
+#[test]
+fn test_open_ground_path_is_shortest_and_excludes_start() {
+    let occupancy = ArenaOccupancy::default();
+
+    let path = find_path(&occupancy, MOVER, IVec2::new(2, 2), IVec2::new(6, 4)).unwrap();
+
+    assert_eq!(path.len(), 6);
+    assert_eq!(path.last(), Some(&IVec2::new(6, 4)));
+    assert!(!path.contains(&IVec2::new(2, 2)));
+    let mut previous = IVec2::new(2, 2);
+    for tile in path {
+        assert_eq!((tile - previous).abs().element_sum(), 1);
+        previous = tile;
+    }
+}
+
+#[test]
+fn test_path_detours_around_walls_and_borders() {
+    let mut occupancy = ArenaOccupancy::default();
+    // Vertical wall at x = 5 with a single gap at y = 8
+    for y in 0..8 {
+        occupancy.set_terrain(IVec2::new(5, y), TileTerrain::Wall);
+    }
+
+    let path = find_path(&occupancy, MOVER, IVec2::new(3, 2), IVec2::new(7, 2)).unwrap();
+
+    assert!(path.contains(&IVec2::new(5, 8)));
+    assert!(path.iter().all(|&tile| !occupancy.is_blocked(tile, MOVER)));
+
+    // Closing the gap leaves no route
+    occupancy.set_terrain(IVec2::new(5, 8), TileTerrain::Border);
+    for y in 9..GRID_HEIGHT as i32 {
+        occupancy.set_terrain(IVec2::new(5, y), TileTerrain::Wall);
+    }
+    assert_eq!(
+        find_path(&occupancy, MOVER, IVec2::new(3, 2), IVec2::new(7, 2)),
+        None
+    );
+}
+
+#[test]
+fn test_path_prefers_cheaper_ground_over_hazards() {
+    let mut occupancy = ArenaOccupancy::default();
+    // Acid strip across the direct route, one tile of webbing further up
+    for x in 4..=8 {
+        occupancy.set_terrain(IVec2::new(x, 5), TileTerrain::Acid);
+    }
+    occupancy.set_terrain(IVec2::new(9, 5), TileTerrain::Web);
+
+    let path = find_path(&occupancy, MOVER, IVec2::new(6, 3), IVec2::new(6, 7)).unwrap();
+
+    // Straight through the acid costs 11; walking round the left end costs 10
+    assert!(
+        !path
+            .iter()
+            .any(|&tile| occupancy.terrain(tile) == TileTerrain::Acid)
+    );
+    assert_eq!(path_cost(&occupancy, &path), 10);
+}
+
+#[test]
+fn test_trap_slow_zones_cost_extra_and_clear_when_the_loop_wraps() {
+    let mut app = App::new();
+    app.add_event::<AbilityCast>()
+        .add_event::<ArenaReset>()
+        .add_systems(Update, (lay_cast_terrain, clear_cast_terrain_on_reset));
+    let arena = app
+        .world_mut()
+        .spawn((Arena(ArenaName::Labyrinth), ArenaOccupancy::default()))
+        .id();
+    let hunter = app
+        .world_mut()
+        .spawn((
+            Character,
+            GridPositionComponent(IVec2::new(6, 5)),
+            ChildOf(arena),
+        ))
+        .id();
+    app.world_mut().send_event(AbilityCast {
+        caster: hunter,
+        ability: AbilityType::Hunter(HunterAbility::Trap),
+    });
+    app.update();
+
+    let occupancy = app.world().get::<ArenaOccupancy>(arena).unwrap();
+    assert_eq!(occupancy.tiles_with(TileTerrain::SlowZone).count(), 9);
+    // Crossing the zone costs 12; walking round its end costs 10
+    let path = find_path(occupancy, MOVER, IVec2::new(6, 2), IVec2::new(6, 8)).unwrap();
+    assert!(
+        !path
+            .iter()
+            .any(|&tile| occupancy.terrain(tile) == TileTerrain::SlowZone)
+    );
+    assert_eq!(path_cost(occupancy, &path), 10);
+
+    app.world_mut().send_event(ArenaReset {
+        arena: ArenaName::Labyrinth,
+    });
+    app.update();
+    let occupancy = app.world().get::<ArenaOccupancy>(arena).unwrap();
+    assert_eq!(occupancy.tiles_with(TileTerrain::SlowZone).count(), 0);
+}
+
+#[test]
+fn test_border_raises_a_barrier_towards_the_boss_until_the_loop_wraps() {
+    let mut app = App::new();
+    app.add_event::<AbilityCast>()
+        .add_event::<ArenaReset>()
+        .add_systems(Update, (lay_cast_terrain, clear_cast_terrain_on_reset));
+    let arena = app
+        .world_mut()
+        .spawn((Arena(ArenaName::Mountain), ArenaOccupancy::default()))
+        .id();
+    app.world_mut().spawn((
+        Boss,
+        GridPositionComponent(IVec2::new(20, 11)),
+        ChildOf(arena),
+    ));
+    let forager = app
+        .world_mut()
+        .spawn((
+            Character,
+            GridPositionComponent(IVec2::new(10, 10)),
+            ChildOf(arena),
+        ))
+        .id();
+    app.world_mut().send_event(AbilityCast {
+        caster: forager,
+        ability: AbilityType::Forager(ForagerAbility::Border),
+    });
+    app.update();
+
+    // The boss lies mostly to the east, so the barrier runs north-south two tiles out
+    let occupancy = app.world().get::<ArenaOccupancy>(arena).unwrap();
+    assert_eq!(
+        occupancy
+            .tiles_with(TileTerrain::Border)
+            .collect::<Vec<_>>(),
+        [IVec2::new(12, 9), IVec2::new(12, 10), IVec2::new(12, 11)]
+    );
+    let path = find_path(occupancy, MOVER, IVec2::new(10, 10), IVec2::new(14, 10)).unwrap();
+    assert!(
+        !path
+            .iter()
+            .any(|&tile| occupancy.terrain(tile) == TileTerrain::Border)
+    );
+    assert_eq!(path_cost(occupancy, &path), 8);
+
+    app.world_mut().send_event(ArenaReset {
+        arena: ArenaName::Mountain,
+    });
+    app.update();
+    let occupancy = app.world().get::<ArenaOccupancy>(arena).unwrap();
+    assert_eq!(occupancy.tiles_with(TileTerrain::Border).count(), 0);
+}
+
+#[test]
+fn test_paths_avoid_units_but_may_end_on_an_occupied_goal() {
+    let mut world = World::new();
+    let hero = world.spawn_empty().id();
+    let blocker = world.spawn_empty().id();
+    let mut occupancy = ArenaOccupancy::default();
+    occupancy.set_occupant(IVec2::new(10, 10), Some(hero));
+    occupancy.set_occupant(IVec2::new(10, 7), Some(blocker));
+
+    let path = find_path(&occupancy, MOVER, IVec2::new(10, 5), IVec2::new(10, 10)).unwrap();
+
+    // Steps around the blocker but ends on the hero's tile
+    assert!(!path.contains(&IVec2::new(10, 7)));
+    assert_eq!(path.last(), Some(&IVec2::new(10, 10)));
+    assert_eq!(path.len(), 7);
+}
+
+#[test]
+fn test_equal_cost_paths_are_chosen_deterministically() {
+    let occupancy = ArenaOccupancy::default();
+
+    let first = find_path(&occupancy, MOVER, IVec2::new(0, 0), IVec2::new(9, 9));
+    for _ in 0..10 {
+        assert_eq!(
+            find_path(&occupancy, MOVER, IVec2::new(0, 0), IVec2::new(9, 9)),
+            first
+        );
+    }
+}
+
+#[test]
+fn test_followed_path_records_movement_events_for_the_active_hero() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        CLICK_MOVE_STEP_SECONDS,
+    )));
+    app.add_plugins(OccupancyPlugin);
+    app.insert_resource(CurrentArena(ArenaName::Labyrinth))
+        .init_resource::<DraftTimeline>()
+        .init_resource::<GlobalTimelinePause>();
+    app.add_systems(Update, (update_timeline_clocks, follow_paths).chain());
+
+    let arena = app
+        .world_mut()
+        .spawn((
+            Arena(ArenaName::Labyrinth),
+            TimelineClock::default(),
+            Playback,
+            Transform::default(),
+        ))
+        .id();
+    app.insert_resource(ArenaEntities::new(
+        ArenaName::ALL_ARENAS.map(|name| (name, arena)),
+    ));
+
+    let start = IVec2::new(3, 3);
+    let goal = IVec2::new(5, 4);
+    let path = find_path(&ArenaOccupancy::default(), MOVER, start, goal).unwrap();
+    let hero = app
+        .world_mut()
+        .spawn((
+            Character,
+            Active,
+            GridPositionComponent(start),
+            Transform::default(),
+            ChildOf(arena),
+            PathFollower::new(goal, path, CLICK_MOVE_STEP_SECONDS),
+        ))
+        .id();
+
+    // The first update only establishes the time baseline; the arena clock then
+    // crosses one step interval per update
+    for _ in 0..8 {
+        app.update();
+    }
+
+    assert_eq!(
+        app.world().get::<GridPositionComponent>(hero).unwrap().0,
+        goal
+    );
+    assert!(app.world().get::<PathFollower>(hero).is_none());
+
+    let steps: IVec2 = app
+        .world()
+        .resource::<DraftTimeline>()
+        .events
+        .iter()
+        .map(|event| match event.event_type {
+            EventType::Movement(step) => step.truncate().as_ivec2(),
+            _ => panic!("Only movement should be recorded"),
+        })
+        .sum();
+    assert_eq!(steps, goal - start);
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::Event;
+
+/// Fired once for each number of the 3-2-1 countdown as it comes up
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CountdownDisplayed {
+    pub seconds: u32,
+}
+
+/// Accepts the open commit or ghost prompt - the click equivalent of the Accept action
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AcceptRecordingPrompt;
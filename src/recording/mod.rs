@@ -1,12 +1,13 @@
 mod components;
+mod events;
 mod systems;
 
-use crate::recording::components::{GlobalPauseReason, GlobalRecordingMode};
 use crate::recording::systems::{
     handle_recording_input, show_commit_dialog, show_ghost_dialog, tick_countdown,
 };
 use bevy::prelude::*;
-pub use components::Playback;
+pub use components::{GlobalPauseReason, GlobalRecordingMode, Playback};
+pub use events::*;
 
 /// Plugin for managing recording state and input
 pub struct RecordingPlugin;
@@ -14,6 +15,8 @@ pub struct RecordingPlugin;
 impl Plugin for RecordingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GlobalRecordingMode>()
+            .add_event::<CountdownDisplayed>()
+            .add_event::<AcceptRecordingPrompt>()
             .add_systems(Update, handle_recording_input)
             .add_systems(Update, show_commit_dialog.run_if(in_commit_requested_state))
             .add_systems(Update, show_ghost_dialog.run_if(in_ghost_requested_state))
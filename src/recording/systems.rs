@@ -1,13 +1,22 @@
+use crate::arena::Arena;
 use crate::character::{Character, Ghost};
-use crate::recording::GlobalRecordingMode;
+use crate::controls::{Action, ActionState};
+use crate::difficulty::Difficulty;
 use crate::recording::components::{CountdownDestination, CountdownStatus, GlobalPauseReason};
+use crate::recording::{AcceptRecordingPrompt, CountdownDisplayed, GlobalRecordingMode};
 use crate::selectors::Active;
-use bevy::input::ButtonInput;
+use crate::timeline::{DraftTimeline, PublishTimeline, TimelineManager};
 use bevy::log::{debug, info};
-use bevy::prelude::{Entity, KeyCode, Res, ResMut, Single, Time, With};
+use bevy::prelude::{
+    ChildOf, Entity, EventReader, EventWriter, Query, Res, ResMut, Single, Time, With,
+};
 
 /// System that ticks the countdown and transitions to Recording when complete
-pub fn tick_countdown(mut recording_mode: ResMut<GlobalRecordingMode>, time: Res<Time>) {
+pub fn tick_countdown(
+    mut recording_mode: ResMut<GlobalRecordingMode>,
+    time: Res<Time>,
+    mut countdown_events: EventWriter<CountdownDisplayed>,
+) {
     if let GlobalRecordingMode::Countdown(countdown_state) = &mut *recording_mode {
         let delta = time.delta();
 
@@ -15,6 +24,7 @@ pub fn tick_countdown(mut recording_mode: ResMut<GlobalRecordingMode>, time: Res
         if let Some(seconds) = countdown_state.should_display_number() {
             info!("Countdown: {}...", seconds);
             countdown_state.mark_displayed(seconds);
+            countdown_events.write(CountdownDisplayed { seconds });
         }
 
         // Tick the countdown
@@ -39,10 +49,18 @@ pub fn tick_countdown(mut recording_mode: ResMut<GlobalRecordingMode>, time: Res
     }
 }
 
+/// The active hero being recorded, with the arena it is recording in
+type RecordingHero<'w> =
+    Single<'w, (&'static mut TimelineManager, &'static ChildOf), (With<Character>, With<Active>)>;
+
 /// System that shows the commit dialog (only runs when in CommitRequested state)
 pub fn show_commit_dialog(
     mut recording_mode: ResMut<GlobalRecordingMode>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
+    mut accept_events: EventReader<AcceptRecordingPrompt>,
+    mut draft: ResMut<DraftTimeline>,
+    hero: Option<RecordingHero>,
+    arena_q: Query<(&Arena, &Difficulty)>,
 ) {
     // This system will only run when the run condition is true
     info!("Showing commit dialog - recording is paused and waiting for commit");
@@ -51,8 +69,20 @@ pub fn show_commit_dialog(
     if let GlobalRecordingMode::Paused(GlobalPauseReason::CommitRequested) = *recording_mode {
         debug!("Commit dialog is active. Waiting for user input...");
 
-        // Check if 'A' key is pressed to accept and start countdown
-        if keyboard.just_pressed(KeyCode::KeyA) {
+        // Accept with the Accept action or the HUD prompt button, then start countdown
+        let clicked = accept_events.read().count() > 0;
+        if actions.just_pressed(Action::Accept) || clicked {
+            if let Some(hero) = hero {
+                let (mut manager, child_of) = hero.into_inner();
+                if let Ok((arena, difficulty)) = arena_q.get(child_of.parent()) {
+                    // Stamped with the arena's tier so playback can bench it on another tier
+                    let draft = std::mem::replace(&mut *draft, DraftTimeline::new());
+                    manager.set_timeline(
+                        arena.0,
+                        PublishTimeline::from_draft(draft).with_difficulty(*difficulty),
+                    );
+                }
+            }
             *recording_mode = GlobalRecordingMode::start_countdown_to_idle();
             info!("Commit accepted. Starting countdown to return to idle...");
         }
@@ -60,15 +90,17 @@ pub fn show_commit_dialog(
 }
 pub fn show_ghost_dialog(
     mut recording_mode: ResMut<GlobalRecordingMode>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
+    mut accept_events: EventReader<AcceptRecordingPrompt>,
 ) {
     info!("Showing ghost dialog - recording is paused and waiting for ghost type");
 
     if let GlobalRecordingMode::Paused(GlobalPauseReason::GhostType) = *recording_mode {
         debug!("Ghost dialog is active. Waiting for user input...");
 
-        // Check if 'A' key is pressed to accept and start countdown
-        if keyboard.just_pressed(KeyCode::KeyA) {
+        // Accept with the Accept action or the HUD prompt button, then start countdown
+        let clicked = accept_events.read().count() > 0;
+        if actions.just_pressed(Action::Accept) || clicked {
             *recording_mode = GlobalRecordingMode::start_countdown_to_recording();
             info!("Ghost type accepted. Starting countdown to recording...");
         }
@@ -78,14 +110,14 @@ pub fn show_ghost_dialog(
 /// System that handles recording input and state transitions
 pub fn handle_recording_input(
     mut recording_mode: ResMut<GlobalRecordingMode>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     active_character: Option<Single<(Entity, Option<&Ghost>), (With<Character>, With<Active>)>>,
 ) {
-    if keyboard.just_pressed(KeyCode::KeyR) {
+    if actions.just_pressed(Action::Record) {
         // Check if there's an active character selected (should be exactly one by convention)
         let Some(active_char) = active_character else {
             info!(
-                "Cannot start recording: No active character selected. Press Tab to select a character."
+                "Cannot start recording: No active character selected. Use CycleHero to select a character."
             );
             return;
         };
@@ -0,0 +1,401 @@
+mod table;
+
+pub use table::*;
+
+use crate::arena::{Arena, ArenaName, CurrentArenaEntity};
+use crate::boss::Decoy;
+use crate::character::{Boss, Character, Ghost};
+use crate::class_type::ClassType;
+use crate::combat::{CombatantDied, DamageDealt};
+use crate::controls::{Action, ActionState};
+use crate::guild::{GuildError, GuildRoster, HeroId, back_up_save};
+use crate::rng::{RngSeed, SplitMix64};
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Names given to new recruits, picked by the same seeded draw as their class
+const RECRUIT_NAMES: [&str; 16] = [
+    "Ash", "Bram", "Cato", "Dara", "Esme", "Finn", "Greer", "Hale", "Iris", "Jory", "Kit", "Lark",
+    "Mira", "Nell", "Orin", "Pell",
+];
+
+/// Error types for the drop table, recruitment save and draws
+#[derive(Error, Debug)]
+pub enum RecruitmentError {
+    #[error("Could not access recruitment file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {line}: {message}")]
+    Malformed { line: usize, message: String },
+    #[error("Drop table has no class with a weight above zero")]
+    EmptyTable,
+    #[error("{arena} has {have} token(s), a draw costs {cost}")]
+    NotEnoughTokens {
+        arena: ArenaName,
+        have: u32,
+        cost: u32,
+    },
+    #[error("{0}")]
+    Guild(#[from] GuildError),
+}
+
+/// Everything needed to re-derive one draw from the seed (value type)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawRecord {
+    /// Global draw counter - the seed plus this number reproduces the roll
+    pub number: u64,
+    pub seed: u64,
+    pub arena: ArenaName,
+    pub roll: u64,
+    /// Sum of the weights the roll was taken against
+    pub total_weight: u64,
+    /// The themed class was guaranteed by pity instead of rolled
+    pub pity: bool,
+    pub class: ClassType,
+    pub hero: HeroId,
+    pub name: String,
+}
+
+/// Audit log line: `#<n> <arena> seed <hex> roll <r>/<total> -> <class> [(pity)] hero <id> <name>`
+impl Display for DrawRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "#{} {:?} seed {:016x} roll {}/{} -> {}",
+            self.number,
+            self.arena,
+            self.seed,
+            self.roll,
+            self.total_weight,
+            self.class.name()
+        )?;
+        if self.pity {
+            f.write_str(" (pity)")?;
+        }
+        write!(f, " hero {} {}", self.hero.0, self.name)
+    }
+}
+
+/// Per-arena tokens and pity counters, persisted between sessions
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub struct RecruitmentState {
+    tokens: [u32; 9],
+    pity: [u32; 9],
+    /// Boss damage not yet banked as a whole token - not persisted
+    damage: [f32; 9],
+    draws: u64,
+}
+
+impl RecruitmentState {
+    /// Path of the recruitment save, relative to the working directory
+    pub const SAVE_PATH: &'static str = "saves/recruitment.state";
+    /// Append-only record of every draw
+    pub const AUDIT_LOG_PATH: &'static str = "saves/recruitment.log";
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecruitmentError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RecruitmentError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn tokens(&self, arena: ArenaName) -> u32 {
+        self.tokens[arena.as_u8() as usize]
+    }
+
+    /// Draws in a row in the arena that missed its themed class
+    #[must_use]
+    pub fn pity(&self, arena: ArenaName) -> u32 {
+        self.pity[arena.as_u8() as usize]
+    }
+
+    pub fn grant(&mut self, arena: ArenaName, tokens: u32) {
+        let slot = &mut self.tokens[arena.as_u8() as usize];
+        *slot = slot.saturating_add(tokens);
+    }
+
+    /// Banks boss damage, returning the whole tokens it completed
+    pub fn earn_damage(&mut self, arena: ArenaName, amount: f32, table: &DropTable) -> u32 {
+        let index = arena.as_u8() as usize;
+        self.damage[index] += amount;
+        let earned = (self.damage[index] / table.damage_per_token).floor();
+        self.damage[index] -= earned * table.damage_per_token;
+        let earned = earned as u32;
+        self.grant(arena, earned);
+        earned
+    }
+
+    /// Spends an arena's tokens on one seeded draw and adds the recruit to the roster
+    pub fn draw(
+        &mut self,
+        arena: ArenaName,
+        table: &DropTable,
+        seed: u64,
+        roster: &mut GuildRoster,
+    ) -> Result<DrawRecord, RecruitmentError> {
+        let index = arena.as_u8() as usize;
+        let have = self.tokens[index];
+        if have < table.cost {
+            return Err(RecruitmentError::NotEnoughTokens {
+                arena,
+                have,
+                cost: table.cost,
+            });
+        }
+
+        let themed = arena.themed_class();
+        let weights = table.weights_for(themed);
+        let total_weight: u64 = weights.iter().map(|(_, weight)| u64::from(*weight)).sum();
+        let number = self.draws;
+        let mut rng = SplitMix64::new(seed.wrapping_add(number));
+        let roll = rng.below(total_weight);
+        let pity = table.has_pity_for(themed) && self.pity[index] + 1 >= table.pity;
+        let class = if pity {
+            themed
+        } else {
+            let mut remaining = roll;
+            weights
+                .iter()
+                .find(|(_, weight)| {
+                    let hit = remaining < u64::from(*weight);
+                    remaining = remaining.saturating_sub(u64::from(*weight));
+                    hit
+                })
+                .map_or(themed, |(class, _)| *class)
+        };
+        let name = RECRUIT_NAMES[rng.below(RECRUIT_NAMES.len() as u64) as usize];
+        let hero = roster.recruit(name, class)?;
+
+        self.tokens[index] -= table.cost;
+        self.pity[index] = if class == themed {
+            0
+        } else {
+            self.pity[index] + 1
+        };
+        self.draws += 1;
+        Ok(DrawRecord {
+            number,
+            seed,
+            arena,
+            roll,
+            total_weight,
+            pity,
+            class,
+            hero,
+            name: name.to_string(),
+        })
+    }
+}
+
+/// File format: a `draws <n>` line, then one `arena <name> <tokens> <pity>` line per arena
+impl Display for RecruitmentState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "draws {}", self.draws)?;
+        for arena in ArenaName::ALL_ARENAS {
+            writeln!(
+                f,
+                "arena {arena:?} {} {}",
+                self.tokens(arena),
+                self.pity(arena)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RecruitmentState {
+    type Err = RecruitmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut state = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = |message: String| RecruitmentError::Malformed {
+                line: index + 1,
+                message,
+            };
+            let expected = || {
+                malformed(format!(
+                    "expected `draws <n>` or `arena <name> <tokens> <pity>`, got `{line}`"
+                ))
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                ["draws", draws] => state.draws = draws.parse().map_err(|_| expected())?,
+                ["arena", arena, tokens, pity] => {
+                    let arena: ArenaName = arena
+                        .parse()
+                        .map_err(|error| malformed(format!("{error}")))?;
+                    let slot = arena.as_u8() as usize;
+                    state.tokens[slot] = tokens.parse().map_err(|_| expected())?;
+                    state.pity[slot] = pity.parse().map_err(|_| expected())?;
+                }
+                _ => return Err(expected()),
+            }
+        }
+        Ok(state)
+    }
+}
+
+/// Damage to a boss earns tokens for the boss's arena; felling it pays the clear bonus.
+/// Ghosts replay the same fight every loop, so only live combat earns anything.
+pub fn earn_arena_tokens(
+    mut damage_events: EventReader<DamageDealt>,
+    mut death_events: EventReader<CombatantDied>,
+    boss_q: Query<&ChildOf, (With<Boss>, Without<Decoy>)>,
+    arena_q: Query<&Arena>,
+    hero_q: Query<(&ChildOf, Has<Ghost>), With<Character>>,
+    table: Res<DropTable>,
+    mut state: ResMut<RecruitmentState>,
+) {
+    let arena_of = |entity: Entity| {
+        let child_of = boss_q.get(entity).ok()?;
+        arena_q.get(child_of.parent()).ok().map(|arena| arena.0)
+    };
+    let mut earned = 0;
+    for event in damage_events.read() {
+        if hero_q.get(event.source).is_ok_and(|(_, ghost)| ghost) {
+            continue;
+        }
+        if let Some(arena) = arena_of(event.target) {
+            // Partial tokens accumulate silently so the save only runs when one is banked
+            earned += state
+                .bypass_change_detection()
+                .earn_damage(arena, event.amount, &table);
+        }
+    }
+    for event in death_events.read() {
+        let Ok(child_of) = boss_q.get(event.entity) else {
+            continue;
+        };
+        let fought_live = hero_q
+            .iter()
+            .any(|(hero, ghost)| !ghost && hero.parent() == child_of.parent());
+        if fought_live && let Some(arena) = arena_of(event.entity) {
+            state
+                .bypass_change_detection()
+                .grant(arena, table.clear_bonus);
+            earned += table.clear_bonus;
+            info!(
+                "{arena} cleared - {} recruitment token(s) earned",
+                table.clear_bonus
+            );
+        }
+    }
+    if earned > 0 {
+        state.set_changed();
+    }
+}
+
+/// Spends the current arena's tokens on a draw, logging it to the audit file
+pub fn recruit_in_current_arena(
+    actions: Res<ActionState>,
+    current: CurrentArenaEntity,
+    seed: Res<RngSeed>,
+    table: Res<DropTable>,
+    mut state: ResMut<RecruitmentState>,
+    mut roster: ResMut<GuildRoster>,
+) {
+    if !actions.just_pressed(Action::Recruit) {
+        return;
+    }
+    let arena = current.name();
+    match state.draw(arena, &table, seed.0, &mut roster) {
+        Ok(record) => {
+            info!("Recruited {} the {}", record.name, record.class.name());
+            if let Err(error) = append_audit(&record, RecruitmentState::AUDIT_LOG_PATH) {
+                warn!("Could not write recruitment audit log: {error}");
+            }
+        }
+        Err(error) => warn!("Recruitment failed: {error}"),
+    }
+}
+
+fn append_audit(record: &DrawRecord, path: impl AsRef<Path>) -> Result<(), RecruitmentError> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(log, "{record}")?;
+    Ok(())
+}
+
+/// Writes tokens and pity to disk whenever they change
+pub fn save_recruitment(state: Res<RecruitmentState>) {
+    if !state.is_changed() || state.is_added() {
+        return;
+    }
+    if let Err(error) = state.save(RecruitmentState::SAVE_PATH) {
+        warn!("Could not save recruitment state: {error}");
+    }
+}
+
+/// Loads the drop table, falling back to the built-in rates
+fn load_drop_table() -> DropTable {
+    match DropTable::load(DropTable::CONFIG_PATH) {
+        Ok(table) => table,
+        Err(error) => {
+            warn!("Using default recruitment rates: {error}");
+            DropTable::default()
+        }
+    }
+}
+
+/// Loads saved tokens and pity, starting empty when there are none. An
+/// unreadable save is backed up first rather than overwritten by the fresh state.
+pub fn load_state(path: impl AsRef<Path>) -> RecruitmentState {
+    let path = path.as_ref();
+    match RecruitmentState::load(path) {
+        Ok(state) => state,
+        Err(RecruitmentError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+            RecruitmentState::default()
+        }
+        Err(error) => {
+            match back_up_save(path) {
+                Ok(backup) => warn!(
+                    "Starting recruitment from scratch: {error} (old save kept at {})",
+                    backup.display()
+                ),
+                Err(backup_error) => warn!(
+                    "Starting recruitment from scratch: {error} (could not back up the old save: {backup_error})"
+                ),
+            }
+            RecruitmentState::default()
+        }
+    }
+}
+
+/// Plugin for arena-token gacha recruitment into the guild roster
+pub struct RecruitmentPlugin;
+
+impl Plugin for RecruitmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_drop_table())
+            .insert_resource(load_state(RecruitmentState::SAVE_PATH))
+            .init_resource::<RngSeed>()
+            .add_event::<DamageDealt>()
+            .add_event::<CombatantDied>()
+            .add_systems(Update, (earn_arena_tokens, recruit_in_current_arena))
+            .add_systems(Last, save_recruitment);
+    }
+}
+
+#[cfg(test)]
+mod tests;
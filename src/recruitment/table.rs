@@ -0,0 +1,155 @@
+use crate::class_type::ClassType;
+use bevy::prelude::Resource;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::Path;
+use std::str::FromStr;
+
+use super::RecruitmentError;
+
+/// Drop rates, costs and pity for recruitment draws, loaded from the config file
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DropTable {
+    /// Arena tokens spent per draw
+    pub cost: u32,
+    /// Boss damage that earns one token in the arena it was dealt in
+    pub damage_per_token: f32,
+    /// Tokens for felling an arena's boss
+    pub clear_bonus: u32,
+    /// Extra weight for the arena's themed class
+    pub themed_bonus: u32,
+    /// Draws in a row without the themed class before it is guaranteed (0 disables pity)
+    pub pity: u32,
+    /// Base weight of every recruitable class - classes left out never drop
+    pub weights: Vec<(ClassType, u32)>,
+}
+
+impl Default for DropTable {
+    fn default() -> Self {
+        Self {
+            cost: 100,
+            damage_per_token: 100.0,
+            clear_bonus: 100,
+            themed_bonus: 50,
+            pity: 10,
+            weights: [
+                ClassType::Hunter,
+                ClassType::Cardinal,
+                ClassType::Forager,
+                ClassType::Warrior,
+                ClassType::Thief,
+                ClassType::Alchemist,
+                ClassType::Merchant,
+                ClassType::Bard,
+            ]
+            .map(|class| (class, 10))
+            .into_iter()
+            // The capstone class is rare everywhere but the Guild House themes it
+            .chain([(ClassType::GuildMaster, 2)])
+            .collect(),
+        }
+    }
+}
+
+impl DropTable {
+    /// Path of the drop table, relative to the working directory
+    pub const CONFIG_PATH: &'static str = "assets/config/recruitment.cfg";
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecruitmentError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Draw weights with the themed bonus applied, if the themed class can drop at all
+    #[must_use]
+    pub fn weights_for(&self, themed: ClassType) -> Vec<(ClassType, u32)> {
+        self.weights
+            .iter()
+            .map(|&(class, weight)| {
+                let bonus = if class == themed {
+                    self.themed_bonus
+                } else {
+                    0
+                };
+                (class, weight + bonus)
+            })
+            .collect()
+    }
+
+    /// Whether pity can ever trigger for this themed class
+    #[must_use]
+    pub fn has_pity_for(&self, themed: ClassType) -> bool {
+        self.pity > 0 && self.weights.iter().any(|(class, _)| *class == themed)
+    }
+}
+
+/// Config file format: `cost`, `damage-per-token`, `clear-bonus`, `themed-bonus` and
+/// `pity` lines, then one `weight <class> <n>` line per recruitable class, `#` comments
+impl Display for DropTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "cost {}", self.cost)?;
+        writeln!(f, "damage-per-token {}", self.damage_per_token)?;
+        writeln!(f, "clear-bonus {}", self.clear_bonus)?;
+        writeln!(f, "themed-bonus {}", self.themed_bonus)?;
+        writeln!(f, "pity {}", self.pity)?;
+        for (class, weight) in &self.weights {
+            writeln!(f, "weight {} {weight}", class.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DropTable {
+    type Err = RecruitmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table = Self {
+            weights: Vec::new(),
+            ..Self::default()
+        };
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = |message: String| RecruitmentError::Malformed {
+                line: index + 1,
+                message,
+            };
+            let (head, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim();
+            let invalid = || malformed(format!("invalid {head} `{value}`"));
+            match head {
+                "cost" => table.cost = value.parse().map_err(|_| invalid())?,
+                "damage-per-token" => {
+                    table.damage_per_token = value
+                        .parse()
+                        .ok()
+                        .filter(|amount: &f32| *amount > 0.0)
+                        .ok_or_else(invalid)?;
+                }
+                "clear-bonus" => table.clear_bonus = value.parse().map_err(|_| invalid())?,
+                "themed-bonus" => table.themed_bonus = value.parse().map_err(|_| invalid())?,
+                "pity" => table.pity = value.parse().map_err(|_| invalid())?,
+                "weight" => {
+                    let (class, weight) = value.split_once(' ').ok_or_else(invalid)?;
+                    let class: ClassType = class
+                        .parse()
+                        .map_err(|error| malformed(format!("{error}")))?;
+                    let weight = weight.trim().parse().map_err(|_| invalid())?;
+                    match table
+                        .weights
+                        .iter_mut()
+                        .find(|(listed, _)| *listed == class)
+                    {
+                        Some((_, listed)) => *listed = weight,
+                        None => table.weights.push((class, weight)),
+                    }
+                }
+                _ => return Err(malformed(format!("unknown setting `{line}`"))),
+            }
+        }
+        if table.weights.iter().all(|(_, weight)| *weight == 0) {
+            return Err(RecruitmentError::EmptyTable);
+        }
+        Ok(table)
+    }
+}
@@ -0,0 +1,224 @@
+use super::*;
+use crate::ability::{
+    AbilityEffectPlugin, AbilityType, HunterAbility, cast_slotted_abilities, resolve_ability_casts,
+};
+use crate::arena::{CurrentArena, create_test_arenas};
+use crate::combat::{CombatPlugin, DamageCause, Health, apply_damage};
+use crate::selectors::Active;
+use crate::timeline::{DraftTimeline, GlobalTimelinePause};
+use bevy::app::{App, Update};
+
+#[test]
+fn test_shipped_drop_table_matches_the_defaults() {
+    let shipped = DropTable::load(DropTable::CONFIG_PATH).unwrap();
+    assert_eq!(shipped, DropTable::default());
+
+    assert!(matches!(
+        "cost 100\nweight Necromancer 5".parse::<DropTable>(),
+        Err(RecruitmentError::Malformed { line: 2, .. })
+    ));
+    assert!(matches!(
+        "weight Hunter 0".parse::<DropTable>(),
+        Err(RecruitmentError::EmptyTable)
+    ));
+}
+
+#[test]
+fn test_draws_are_seeded_spend_tokens_and_join_the_roster() {
+    let table = DropTable::default();
+    let run = |seed: u64| {
+        let mut state = RecruitmentState::default();
+        let mut roster = GuildRoster::default();
+        state.grant(ArenaName::Labyrinth, 3 * table.cost + 10);
+        let records: Vec<DrawRecord> = (0..3)
+            .map(|_| {
+                state
+                    .draw(ArenaName::Labyrinth, &table, seed, &mut roster)
+                    .unwrap()
+            })
+            .collect();
+        (state, roster, records)
+    };
+
+    let (mut state, mut roster, records) = run(7);
+    assert_eq!(records, run(7).2, "same seed, same draws");
+    assert_eq!(state.tokens(ArenaName::Labyrinth), 10);
+    for record in &records {
+        let hero = roster.get(record.hero).unwrap();
+        assert_eq!(
+            (hero.class, hero.name.as_str()),
+            (record.class, record.name.as_str())
+        );
+    }
+    assert!(matches!(
+        state.draw(ArenaName::Labyrinth, &table, 7, &mut roster),
+        Err(RecruitmentError::NotEnoughTokens {
+            have: 10,
+            cost: 100,
+            ..
+        })
+    ));
+
+    let parsed: RecruitmentState = state.to_string().parse().unwrap();
+    assert_eq!(parsed, state);
+}
+
+#[test]
+fn test_pity_guarantees_the_themed_class() {
+    let table: DropTable = "cost 1\nthemed-bonus 0\npity 3\nweight Hunter 1\nweight Thief 1000"
+        .parse()
+        .unwrap();
+    let mut state = RecruitmentState::default();
+    let mut roster = GuildRoster::default();
+    state.grant(ArenaName::Labyrinth, 3);
+
+    let records: Vec<DrawRecord> = (0..3)
+        .map(|_| {
+            state
+                .draw(ArenaName::Labyrinth, &table, 1, &mut roster)
+                .unwrap()
+        })
+        .collect();
+    let last = &records[2];
+    assert!(last.pity);
+    assert_eq!(
+        last.class,
+        ClassType::Hunter,
+        "Labyrinth is the Hunter arena"
+    );
+    assert_eq!(state.pity(ArenaName::Labyrinth), 0);
+    assert!(last.to_string().contains("-> Hunter (pity)"));
+}
+
+#[test]
+fn test_boss_damage_and_clears_earn_tokens_for_that_arena() {
+    let mut app = App::new();
+    app.insert_resource(DropTable::default())
+        .init_resource::<RecruitmentState>()
+        .add_event::<DamageDealt>()
+        .add_event::<CombatantDied>()
+        .add_systems(Update, earn_arena_tokens);
+
+    let gala = app.world_mut().spawn(Arena(ArenaName::Gala)).id();
+    let boss = app.world_mut().spawn((Boss, ChildOf(gala))).id();
+    let hero = app.world_mut().spawn((Character, ChildOf(gala))).id();
+    let ghost = app.world_mut().spawn((Character, Ghost, ChildOf(gala))).id();
+
+    for (target, amount) in [(boss, 150.0), (hero, 500.0), (boss, 60.0)] {
+        app.world_mut().send_event(DamageDealt {
+            source: hero,
+            target,
+            amount,
+            cause: DamageCause::Ability(AbilityType::Hunter(HunterAbility::AutoShot)),
+        });
+    }
+    app.update();
+    let state = app.world().resource::<RecruitmentState>();
+    assert_eq!(
+        state.tokens(ArenaName::Gala),
+        2,
+        "210 boss damage, hero damage ignored"
+    );
+
+    app.world_mut().send_event(DamageDealt {
+        source: ghost,
+        target: boss,
+        amount: 500.0,
+        cause: DamageCause::Ability(AbilityType::Hunter(HunterAbility::AutoShot)),
+    });
+    app.update();
+    let state = app.world().resource::<RecruitmentState>();
+    assert_eq!(state.tokens(ArenaName::Gala), 2, "ghost damage earns nothing");
+
+    app.world_mut().send_event(CombatantDied { entity: boss });
+    app.update();
+    let state = app.world().resource::<RecruitmentState>();
+    assert_eq!(state.tokens(ArenaName::Gala), 102);
+    assert_eq!(state.tokens(ArenaName::Labyrinth), 0);
+}
+
+#[test]
+fn test_a_live_ability_press_earns_tokens() {
+    let mut app = App::new();
+    app.add_plugins((CombatPlugin, AbilityEffectPlugin))
+        .insert_resource(DropTable::default())
+        .init_resource::<RecruitmentState>()
+        .init_resource::<ActionState>()
+        .init_resource::<DraftTimeline>()
+        .init_resource::<GlobalTimelinePause>()
+        .insert_resource(CurrentArena(ArenaName::Gala))
+        .add_systems(
+            Update,
+            (
+                cast_slotted_abilities.before(resolve_ability_casts),
+                earn_arena_tokens.after(apply_damage),
+            ),
+        );
+    let gala = create_test_arenas(app.world_mut(), |_| ()).get(ArenaName::Gala);
+    app.world_mut().spawn((Boss, Health::new(1000.0), ChildOf(gala)));
+    app.world_mut().spawn((
+        Character,
+        Active,
+        ClassType::Hunter,
+        Health::new(100.0),
+        ChildOf(gala),
+    ));
+
+    // Poison Shot strikes for 60 on impact and Sniper for 250
+    for action in [Action::Ability2, Action::Ability3] {
+        app.world_mut()
+            .resource_mut::<ActionState>()
+            .set(action, true);
+    }
+    app.update();
+
+    let state = app.world().resource::<RecruitmentState>();
+    assert_eq!(state.tokens(ArenaName::Gala), 3, "310 boss damage");
+}
+
+#[test]
+fn test_ghost_only_clears_pay_no_bonus() {
+    let mut app = App::new();
+    app.insert_resource(DropTable::default())
+        .init_resource::<RecruitmentState>()
+        .add_event::<DamageDealt>()
+        .add_event::<CombatantDied>()
+        .add_systems(Update, earn_arena_tokens);
+
+    let gala = app.world_mut().spawn(Arena(ArenaName::Gala)).id();
+    let boss = app.world_mut().spawn((Boss, ChildOf(gala))).id();
+    app.world_mut().spawn((Character, Ghost, ChildOf(gala)));
+
+    app.world_mut().send_event(CombatantDied { entity: boss });
+    app.update();
+    assert_eq!(
+        app.world()
+            .resource::<RecruitmentState>()
+            .tokens(ArenaName::Gala),
+        0
+    );
+}
+
+#[test]
+fn test_every_arena_can_pity_its_themed_class() {
+    let table = DropTable::default();
+    for arena in ArenaName::ALL_ARENAS {
+        assert!(table.has_pity_for(arena.themed_class()), "{arena}");
+    }
+}
+
+#[test]
+fn test_unreadable_saves_are_backed_up_not_overwritten() {
+    let dir = std::env::temp_dir().join(format!("arenic-recruitment-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("recruitment.state");
+    std::fs::write(&path, "draws many\n").unwrap();
+
+    assert_eq!(load_state(&path), RecruitmentState::default());
+    assert!(!path.exists());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("recruitment.state.bak")).unwrap(),
+        "draws many\n"
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
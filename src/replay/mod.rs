@@ -0,0 +1,392 @@
+use crate::arena::{ArenaName, CurrentArenaEntity, tile_to_local_translation};
+use crate::boss::BOSS_DEFINITION_VERSION;
+use crate::character::Ghost;
+use crate::class_type::ClassType;
+use crate::controls::{Action, ActionState};
+use crate::guild::HeroRecord;
+use crate::materials::Materials;
+use crate::rng::RngSeed;
+use crate::timeline::{ReplayOrigin, SavedTimeline, TimelineFileError, TimelineManager};
+use bevy::prelude::*;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Revision of the replay file layout itself
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// First word of every replay file
+const MAGIC: &str = "arenic-replay";
+
+/// Line separating the header and each timeline layer
+const LAYER_SEPARATOR: &str = "---";
+
+/// Folder replays are exported to and imported from, relative to the working directory
+const REPLAY_DIR: &str = "replays";
+
+/// Error types for exporting and importing replay files
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("Could not access replay file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Not a replay file")]
+    NotAReplay,
+    #[error("Replay format version {found} is not supported (expected {expected})")]
+    FormatVersion { found: u32, expected: u32 },
+    #[error(
+        "Replay was made against boss version {found}, this build has version {expected} - re-record it"
+    )]
+    BossVersion { found: u32, expected: u32 },
+    #[error(
+        "Replay checksum mismatch (file says {expected:016x}, contents hash to {computed:016x})"
+    )]
+    Checksum { expected: u64, computed: u64 },
+    #[error("Missing `{field}` header")]
+    MissingHeader { field: &'static str },
+    #[error("Line {line}: {message}")]
+    Malformed { line: usize, message: String },
+    #[error("Layer {layer}: {source}")]
+    Layer {
+        layer: usize,
+        source: TimelineFileError,
+    },
+    #[error("No ghosts to export in {arena}")]
+    Empty { arena: ArenaName },
+}
+
+/// Marks ghosts spawned from a replay file so a later import can replace them
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ImportedGhost;
+
+/// One arena's full layered strategy, self-contained for sharing (value type)
+#[derive(Clone)]
+pub struct ReplayFile {
+    /// Arena the layers were recorded in
+    pub arena: ArenaName,
+    pub seed: u64,
+    pub boss_version: u32,
+    pub layers: Vec<SavedTimeline>,
+}
+
+impl ReplayFile {
+    /// Bundles the layers recorded in an arena against the current boss definitions
+    pub fn new(
+        arena: ArenaName,
+        seed: u64,
+        layers: Vec<SavedTimeline>,
+    ) -> Result<Self, ReplayError> {
+        if layers.is_empty() {
+            return Err(ReplayError::Empty { arena });
+        }
+        // The `name` header is cut at `#` when read back, like every other line
+        if let Some(index) = layers
+            .iter()
+            .position(|layer| !HeroRecord::is_valid_name(&layer.name))
+        {
+            return Err(ReplayError::Layer {
+                layer: index + 1,
+                source: TimelineFileError::InvalidName {
+                    name: layers[index].name.clone(),
+                },
+            });
+        }
+        Ok(Self {
+            arena,
+            seed,
+            boss_version: BOSS_DEFINITION_VERSION,
+            layers,
+        })
+    }
+
+    /// Default location of an arena's replay file
+    #[must_use]
+    pub fn path_for(arena: ArenaName) -> PathBuf {
+        Path::new(REPLAY_DIR).join(format!("{arena:?}.replay"))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Spawns a ghost for every layer into `arena`, returning them in layer order
+    pub fn spawn_ghosts(
+        &self,
+        commands: &mut Commands,
+        arena: ArenaName,
+        arena_entity: Entity,
+    ) -> Vec<Entity> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                commands
+                    .spawn((
+                        layer.ghost_bundle(arena),
+                        ImportedGhost,
+                        ChildOf(arena_entity),
+                    ))
+                    .id()
+            })
+            .collect()
+    }
+
+    fn header(&self) -> String {
+        format!(
+            "{MAGIC} {REPLAY_FORMAT_VERSION}\narena {:?}\nseed {}\nboss-version {}\n",
+            self.arena, self.seed, self.boss_version
+        )
+    }
+
+    fn body(&self) -> String {
+        self.layers
+            .iter()
+            .map(|layer| format!("{LAYER_SEPARATOR}\n{layer}"))
+            .collect()
+    }
+}
+
+/// FNV-1a 64-bit hash - stable across platforms and builds, unlike std's hasher
+const fn checksum(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        i += 1;
+    }
+    hash
+}
+
+/// Checksum of the whole file apart from the `checksum` header line itself
+fn file_checksum(header: &str, body: &str) -> u64 {
+    let signed: String = header
+        .split_inclusive('\n')
+        .filter(|line| !line.trim_start().starts_with("checksum"))
+        .chain([body])
+        .collect();
+    checksum(signed.as_bytes())
+}
+
+/// File format: `arenic-replay <version>`, `arena`, `seed`, `boss-version` and
+/// `checksum` headers, then each layer in the saved-timeline format after a `---`
+/// line. The checksum covers every line except its own, so headers can't be edited either.
+impl Display for ReplayFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let header = self.header();
+        let body = self.body();
+        f.write_str(&header)?;
+        writeln!(f, "checksum {:016x}", file_checksum(&header, &body))?;
+        f.write_str(&body)
+    }
+}
+
+impl FromStr for ReplayFile {
+    type Err = ReplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Files checked out or edited on Windows hash the same as the exported original
+        let s = s.replace("\r\n", "\n");
+        let body_start = s
+            .find(&format!("\n{LAYER_SEPARATOR}\n"))
+            .map_or(s.len(), |i| i + 1);
+        let (header, body) = s.split_at(body_start);
+
+        let mut lines = header.lines().enumerate();
+        let version = lines
+            .next()
+            .and_then(|(_, line)| line.trim().strip_prefix(MAGIC))
+            .ok_or(ReplayError::NotAReplay)?;
+        let version: u32 = version
+            .trim()
+            .parse()
+            .map_err(|_| ReplayError::NotAReplay)?;
+        if version != REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::FormatVersion {
+                found: version,
+                expected: REPLAY_FORMAT_VERSION,
+            });
+        }
+
+        let mut arena = None;
+        let mut seed = None;
+        let mut boss_version = None;
+        let mut expected = None;
+        for (index, line) in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = |message: String| ReplayError::Malformed {
+                line: index + 1,
+                message,
+            };
+            let (head, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim();
+            let invalid = || malformed(format!("invalid {head} `{value}`"));
+            match head {
+                "arena" => {
+                    arena = Some(
+                        value
+                            .parse()
+                            .map_err(|error| malformed(format!("{error}")))?,
+                    );
+                }
+                "seed" => seed = Some(value.parse().map_err(|_| invalid())?),
+                "boss-version" => boss_version = Some(value.parse().map_err(|_| invalid())?),
+                "checksum" => {
+                    expected = Some(u64::from_str_radix(value, 16).map_err(|_| invalid())?);
+                }
+                _ => return Err(malformed(format!("unknown header `{line}`"))),
+            }
+        }
+
+        let expected = expected.ok_or(ReplayError::MissingHeader { field: "checksum" })?;
+        let computed = file_checksum(header, body);
+        if expected != computed {
+            return Err(ReplayError::Checksum { expected, computed });
+        }
+        let boss_version = boss_version.ok_or(ReplayError::MissingHeader {
+            field: "boss-version",
+        })?;
+        if boss_version != BOSS_DEFINITION_VERSION {
+            return Err(ReplayError::BossVersion {
+                found: boss_version,
+                expected: BOSS_DEFINITION_VERSION,
+            });
+        }
+
+        let mut layers = Vec::new();
+        let mut layer = String::new();
+        for line in body.lines().skip(1) {
+            if line.trim() == LAYER_SEPARATOR {
+                layers.push(std::mem::take(&mut layer));
+            } else {
+                layer.push_str(line);
+                layer.push('\n');
+            }
+        }
+        if !body.is_empty() {
+            layers.push(layer);
+        }
+        let layers = layers
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                text.parse().map_err(|source| ReplayError::Layer {
+                    layer: index + 1,
+                    source,
+                })
+            })
+            .collect::<Result<Vec<SavedTimeline>, _>>()?;
+
+        Self::new(
+            arena.ok_or(ReplayError::MissingHeader { field: "arena" })?,
+            seed.ok_or(ReplayError::MissingHeader { field: "seed" })?,
+            layers,
+        )
+    }
+}
+
+/// Writes every ghost layered into the current arena to its replay file
+pub fn export_current_arena_replay(
+    actions: Res<ActionState>,
+    current: CurrentArenaEntity,
+    seed: Res<RngSeed>,
+    ghost_q: Query<(&Name, &ClassType, &ReplayOrigin, &TimelineManager), With<Ghost>>,
+) {
+    if !actions.just_pressed(Action::ExportReplay) {
+        return;
+    }
+    let arena = current.name();
+    let layers = ghost_q
+        .iter()
+        .filter(|(_, _, origin, _)| origin.arena == arena)
+        .filter_map(|(name, class, origin, manager)| {
+            Some(SavedTimeline {
+                name: name.to_string(),
+                class: *class,
+                arena,
+                start: origin.tile,
+                timeline: manager.get_timeline(arena)?.clone(),
+            })
+        })
+        .collect();
+
+    let path = ReplayFile::path_for(arena);
+    match ReplayFile::new(arena, seed.0, layers).and_then(|replay| replay.save(&path)) {
+        Ok(()) => info!("Exported {arena} replay to {}", path.display()),
+        Err(error) => warn!("Replay export failed: {error}"),
+    }
+}
+
+/// Loads the current arena's replay file and spawns its ghosts there, replacing
+/// any ghosts an earlier import left in that arena
+pub fn import_current_arena_replay(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    current: CurrentArenaEntity,
+    imported_q: Query<(Entity, &ChildOf), With<ImportedGhost>>,
+    mats: Res<Materials>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut seed: ResMut<RngSeed>,
+) {
+    if !actions.just_pressed(Action::ImportReplay) {
+        return;
+    }
+    let arena = current.name();
+    let path = ReplayFile::path_for(arena);
+    let replay = match ReplayFile::load(&path) {
+        Ok(replay) => replay,
+        Err(error) => {
+            warn!("Replay import from {} failed: {error}", path.display());
+            return;
+        }
+    };
+
+    for (ghost, child_of) in imported_q.iter() {
+        if child_of.parent() == current.get() {
+            commands.entity(ghost).despawn();
+        }
+    }
+
+    let radius = 0.125;
+    let mesh = meshes.add(Sphere::new(radius));
+    let ghosts = replay.spawn_ghosts(&mut commands, arena, current.get());
+    for (ghost, layer) in ghosts.iter().zip(&replay.layers) {
+        commands.entity(*ghost).insert((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(mats.gray.clone()),
+            Transform::from_translation(tile_to_local_translation(layer.start, radius)),
+        ));
+    }
+    seed.0 = replay.seed;
+    info!(
+        "Imported {} ghost(s) from the {} replay into {arena}",
+        ghosts.len(),
+        replay.arena
+    );
+}
+
+/// Plugin for sharing an arena's layered strategy as a replay file
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RngSeed>().add_systems(
+            Update,
+            (export_current_arena_replay, import_current_arena_replay),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+use super::*;
+use bevy::ecs::world::World;
+
+fn shipped_replay() -> ReplayFile {
+    let layers = [
+        "assets/timelines/labyrinth_warrior.timeline",
+        "assets/timelines/labyrinth_bard.timeline",
+    ]
+    .map(|path| SavedTimeline::load(path).unwrap())
+    .to_vec();
+    ReplayFile::new(ArenaName::Labyrinth, 42, layers).unwrap()
+}
+
+#[test]
+fn test_replay_round_trips_through_the_file_format() {
+    let text = shipped_replay().to_string();
+    let parsed: ReplayFile = text.parse().unwrap();
+
+    assert_eq!(parsed.arena, ArenaName::Labyrinth);
+    assert_eq!(parsed.seed, 42);
+    assert_eq!(parsed.boss_version, BOSS_DEFINITION_VERSION);
+    assert_eq!(parsed.layers.len(), 2);
+    assert_eq!(parsed.to_string(), text);
+}
+
+#[test]
+fn test_layer_names_that_would_not_read_back_are_rejected() {
+    let mut layers = shipped_replay().layers;
+    layers[1].name = "Rook #2".to_string();
+    assert!(matches!(
+        ReplayFile::new(ArenaName::Labyrinth, 42, layers.clone()),
+        Err(ReplayError::Layer {
+            layer: 2,
+            source: TimelineFileError::InvalidName { .. }
+        })
+    ));
+
+    layers[1].name = "Rook No. 2".to_string();
+    let text = ReplayFile::new(ArenaName::Labyrinth, 42, layers)
+        .unwrap()
+        .to_string();
+    let parsed: ReplayFile = text.parse().unwrap();
+    assert_eq!(parsed.layers[1].name, "Rook No. 2");
+}
+
+#[test]
+fn test_edited_replays_fail_the_checksum() {
+    let text = shipped_replay()
+        .to_string()
+        .replace("ability Warrior:Bash", "ability Thief:Backstab");
+    assert!(matches!(
+        text.parse::<ReplayFile>(),
+        Err(ReplayError::Checksum { .. })
+    ));
+
+    let reseeded = shipped_replay()
+        .to_string()
+        .replacen("seed 42", "seed 43", 1);
+    assert!(matches!(
+        reseeded.parse::<ReplayFile>(),
+        Err(ReplayError::Checksum { .. })
+    ));
+}
+
+#[test]
+fn test_crlf_replays_pass_the_checksum() {
+    let text = shipped_replay().to_string();
+    let parsed: ReplayFile = text.replace('\n', "\r\n").parse().unwrap();
+    assert_eq!(parsed.to_string(), text);
+}
+
+#[test]
+fn test_version_mismatches_are_reported_not_panicked() {
+    let mut stale = shipped_replay();
+    stale.boss_version = BOSS_DEFINITION_VERSION + 1;
+    assert!(matches!(
+        stale.to_string().parse::<ReplayFile>(),
+        Err(ReplayError::BossVersion { found, expected })
+            if found == BOSS_DEFINITION_VERSION + 1 && expected == BOSS_DEFINITION_VERSION
+    ));
+
+    let future =
+        shipped_replay()
+            .to_string()
+            .replacen(&format!("{MAGIC} 1"), &format!("{MAGIC} 9"), 1);
+    assert!(matches!(
+        future.parse::<ReplayFile>(),
+        Err(ReplayError::FormatVersion {
+            found: 9,
+            expected: 1
+        })
+    ));
+
+    assert!(matches!(
+        "name Warrior".parse::<ReplayFile>(),
+        Err(ReplayError::NotAReplay)
+    ));
+}
+
+#[test]
+fn test_import_spawns_ghosts_into_the_chosen_arena() {
+    let replay = shipped_replay();
+    let mut world = World::new();
+    let casino = world.spawn_empty().id();
+
+    let mut commands = world.commands();
+    let ghosts = replay.spawn_ghosts(&mut commands, ArenaName::Casino, casino);
+    world.flush();
+
+    assert_eq!(ghosts.len(), 2);
+    for (ghost, layer) in ghosts.into_iter().zip(&replay.layers) {
+        let ghost = world.entity(ghost);
+        assert!(ghost.contains::<Ghost>());
+        assert!(ghost.contains::<ImportedGhost>());
+        assert_eq!(ghost.get::<ChildOf>().unwrap().parent(), casino);
+        assert_eq!(
+            ghost.get::<ReplayOrigin>(),
+            Some(&ReplayOrigin {
+                arena: ArenaName::Casino,
+                tile: layer.start
+            })
+        );
+        let manager = ghost.get::<TimelineManager>().unwrap();
+        assert!(manager.has_recording_for(ArenaName::Casino));
+        assert!(!manager.has_recording_for(ArenaName::Labyrinth));
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::Resource;
+
+/// Seed for every random roll in a run - replays store it so they reproduce exactly
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngSeed(pub u64);
+
+impl Default for RngSeed {
+    fn default() -> Self {
+        Self(0x5EED_A4E7_1C00_0001)
+    }
+}
+
+/// SplitMix64 generator - tiny, fast and identical on every platform,
+/// so a seed and a draw number are enough to audit any roll (value type)
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound` (multiply-shift, no modulo bias worth measuring)
+    pub fn below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+}
@@ -0,0 +1,569 @@
+// Standard library and external crates
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+// Local crate modules
+use crate::arena::{Arena, ArenaReset};
+use crate::boss::ScriptCursor;
+use crate::combat::{DamageCause, DamageEvent, HealEvent, apply_damage};
+use crate::recording::Playback;
+use crate::timeline::{TimelineClock, emit_arena_resets, update_timeline_clocks};
+
+/// Buffs help their holder; debuffs hurt it. Cleanse removes debuffs, Pickpocket steals buffs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectCategory {
+    Buff,
+    Debuff,
+}
+
+/// What happens when an effect is applied to a target that already has it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackingRule {
+    /// Reset the duration, keep a single stack
+    Refresh,
+    /// Add a stack (up to the cap) and reset the duration
+    Stack { max_stacks: u8 },
+}
+
+/// Every status effect in the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusEffectKind {
+    /// Poison Shot damage over time
+    Poison,
+    /// Forager Mushroom healing over time
+    Regeneration,
+    /// Ironskin Draft damage reduction
+    Ironskin,
+    /// Helix haste aura
+    Haste,
+    /// Boss immunity window (Crucible Catalyst Core)
+    Shielded,
+    /// Bastion Armor Plating damage reduction
+    ArmorPlating,
+    /// Bastion armor stacked by living constructs
+    Fortified,
+    /// Sanctum healing-received reduction
+    WitheringTouch,
+    /// Sanctum Corrupted Tide shield, one stack per living minion
+    TideWard,
+    /// Casino damage absorption while the boss stands by its treasure
+    TreasureShield,
+    /// Pawnshop damage reduction while the boss stands in shadow
+    ShadowCloak,
+    /// Mountain damage reduction while its vines are summoned
+    VineShield,
+}
+
+/// Static per-kind effect data
+#[derive(Debug, Clone, Copy)]
+pub struct StatusEffectData {
+    pub category: EffectCategory,
+    pub stacking: StackingRule,
+    pub duration: Duration,
+    /// Interval between periodic ticks, if the effect ticks at all
+    pub tick_interval: Option<Duration>,
+    /// Damage or healing per stack per tick, or a fractional modifier for passive effects
+    pub magnitude: f32,
+}
+
+impl StatusEffectKind {
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn data(self) -> StatusEffectData {
+        match self {
+            Self::Poison => StatusEffectData {
+                category: EffectCategory::Debuff,
+                stacking: StackingRule::Stack { max_stacks: 5 },
+                duration: Duration::from_secs(6),
+                tick_interval: Some(Duration::from_secs(1)),
+                magnitude: 20.0,
+            },
+            Self::Regeneration => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(10),
+                tick_interval: Some(Duration::from_secs(1)),
+                magnitude: 15.0,
+            },
+            Self::Ironskin => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(8),
+                tick_interval: None,
+                magnitude: 0.5,
+            },
+            Self::Haste => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(10),
+                tick_interval: None,
+                magnitude: 0.2,
+            },
+            Self::Shielded => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(10),
+                tick_interval: None,
+                magnitude: 1.0,
+            },
+            Self::ArmorPlating => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(10),
+                tick_interval: None,
+                magnitude: 0.4,
+            },
+            Self::Fortified => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Stack { max_stacks: 5 },
+                duration: Duration::from_secs(6),
+                tick_interval: None,
+                magnitude: 0.1,
+            },
+            Self::WitheringTouch => StatusEffectData {
+                category: EffectCategory::Debuff,
+                stacking: StackingRule::Stack { max_stacks: 5 },
+                duration: Duration::from_secs(15),
+                tick_interval: None,
+                magnitude: 0.15,
+            },
+            Self::TideWard => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Stack { max_stacks: 8 },
+                duration: Duration::from_secs(10),
+                tick_interval: None,
+                magnitude: 0.1,
+            },
+            Self::TreasureShield => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(5),
+                tick_interval: None,
+                magnitude: 0.2,
+            },
+            Self::ShadowCloak => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(5),
+                tick_interval: None,
+                magnitude: 0.5,
+            },
+            Self::VineShield => StatusEffectData {
+                category: EffectCategory::Buff,
+                stacking: StackingRule::Refresh,
+                duration: Duration::from_secs(5),
+                tick_interval: None,
+                magnitude: 0.5,
+            },
+        }
+    }
+}
+
+/// A single active effect instance - value type stored in the StatusEffects stack
+/// Time is tracked as integer `Duration`s so replays produce identical ticks
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub source: Entity,
+    pub stacks: u8,
+    pub remaining: Duration,
+    /// Time accumulated toward the next periodic tick
+    pub tick_progress: Duration,
+}
+
+impl StatusEffect {
+    #[must_use]
+    pub fn new(kind: StatusEffectKind, source: Entity) -> Self {
+        Self {
+            kind,
+            source,
+            stacks: 1,
+            remaining: kind.data().duration,
+            tick_progress: Duration::ZERO,
+        }
+    }
+}
+
+/// Result of applying an effect to a stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplyOutcome {
+    Applied,
+    Refreshed { stacks: u8 },
+}
+
+/// Periodic tick produced while advancing a stack
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectTick {
+    pub kind: StatusEffectKind,
+    pub source: Entity,
+    /// Total magnitude across all ticks fired this step (magnitude × stacks × ticks)
+    pub amount: f32,
+}
+
+/// Active status effects on a character, boss or minion, in application order
+#[derive(Component, Debug, Clone, Default)]
+pub struct StatusEffects(pub Vec<StatusEffect>);
+
+impl StatusEffects {
+    /// Applies an effect following its kind's stacking rule
+    pub fn apply(&mut self, kind: StatusEffectKind, source: Entity) -> ApplyOutcome {
+        let data = kind.data();
+        let Some(existing) = self.0.iter_mut().find(|effect| effect.kind == kind) else {
+            self.0.push(StatusEffect::new(kind, source));
+            return ApplyOutcome::Applied;
+        };
+
+        if let StackingRule::Stack { max_stacks } = data.stacking {
+            existing.stacks = (existing.stacks + 1).min(max_stacks);
+        }
+        existing.remaining = data.duration;
+        existing.source = source;
+        ApplyOutcome::Refreshed {
+            stacks: existing.stacks,
+        }
+    }
+
+    /// Advances every effect by `delta`, returning periodic ticks and expired effects
+    pub fn advance(&mut self, delta: Duration) -> (Vec<EffectTick>, Vec<StatusEffect>) {
+        let mut ticks = Vec::new();
+        for effect in self.0.iter_mut() {
+            let step = delta.min(effect.remaining);
+            effect.remaining -= step;
+
+            let Some(interval) = effect.kind.data().tick_interval else {
+                continue;
+            };
+            effect.tick_progress += step;
+            let mut fired = 0u32;
+            while effect.tick_progress >= interval {
+                effect.tick_progress -= interval;
+                fired += 1;
+            }
+            if fired > 0 {
+                ticks.push(EffectTick {
+                    kind: effect.kind,
+                    source: effect.source,
+                    amount: effect.kind.data().magnitude * effect.stacks as f32 * fired as f32,
+                });
+            }
+        }
+
+        let (expired, active) = self
+            .0
+            .drain(..)
+            .partition(|effect| effect.remaining.is_zero());
+        self.0 = active;
+        (ticks, expired)
+    }
+
+    /// Removes every effect of the given category (Cleanse removes debuffs)
+    pub fn remove_category(&mut self, category: EffectCategory) -> Vec<StatusEffect> {
+        let (removed, kept) = self
+            .0
+            .drain(..)
+            .partition(|effect| effect.kind.data().category == category);
+        self.0 = kept;
+        removed
+    }
+
+    /// Removes and returns the most recently applied buff (Pickpocket steals it)
+    pub fn take_newest_buff(&mut self) -> Option<StatusEffect> {
+        let index = self
+            .0
+            .iter()
+            .rposition(|effect| effect.kind.data().category == EffectCategory::Buff)?;
+        Some(self.0.remove(index))
+    }
+
+    #[must_use]
+    pub fn stacks_of(&self, kind: StatusEffectKind) -> u8 {
+        self.0
+            .iter()
+            .find(|effect| effect.kind == kind)
+            .map_or(0, |effect| effect.stacks)
+    }
+
+    /// Multiplier applied to incoming damage (Ironskin, Armor Plating, Treasure Shield,
+    /// Shadow Cloak, Vine Shield and each Fortified or Tide Ward stack reduce it,
+    /// Shielded negates it)
+    #[must_use]
+    pub fn damage_taken_multiplier(&self) -> f32 {
+        self.0
+            .iter()
+            .filter(|effect| {
+                matches!(
+                    effect.kind,
+                    StatusEffectKind::Ironskin
+                        | StatusEffectKind::Shielded
+                        | StatusEffectKind::ArmorPlating
+                        | StatusEffectKind::Fortified
+                        | StatusEffectKind::TideWard
+                        | StatusEffectKind::TreasureShield
+                        | StatusEffectKind::ShadowCloak
+                        | StatusEffectKind::VineShield
+                )
+            })
+            .fold(1.0, |multiplier, effect| {
+                multiplier * (1.0 - effect.kind.data().magnitude * f32::from(effect.stacks))
+            })
+    }
+
+    /// Multiplier applied to incoming healing (each Withering Touch stack reduces it)
+    #[must_use]
+    pub fn healing_received_multiplier(&self) -> f32 {
+        let stacks = self.stacks_of(StatusEffectKind::WitheringTouch);
+        (1.0 - StatusEffectKind::WitheringTouch.data().magnitude * f32::from(stacks)).max(0.0)
+    }
+
+    /// Multiplier applied to ability cooldown recovery (Haste increases it)
+    #[must_use]
+    pub fn speed_multiplier(&self) -> f32 {
+        self.0
+            .iter()
+            .filter(|effect| effect.kind == StatusEffectKind::Haste)
+            .fold(1.0, |multiplier, effect| {
+                multiplier * (1.0 + effect.kind.data().magnitude)
+            })
+    }
+}
+
+/// Request to apply an effect to a target
+#[derive(Event, Debug, Clone)]
+pub struct ApplyStatusEffect {
+    pub target: Entity,
+    pub source: Entity,
+    pub kind: StatusEffectKind,
+}
+
+/// Request to remove all debuffs from a target (Cleanse)
+#[derive(Event, Debug, Clone)]
+pub struct CleanseStatusEffects {
+    pub target: Entity,
+}
+
+/// Request to move the target's newest buff onto the thief (Pickpocket)
+#[derive(Event, Debug, Clone)]
+pub struct StealStatusEffect {
+    pub thief: Entity,
+    pub target: Entity,
+}
+
+/// Fired when an effect lands on a target that did not have it
+#[derive(Event, Debug, Clone)]
+pub struct StatusEffectApplied {
+    pub target: Entity,
+    pub source: Entity,
+    pub kind: StatusEffectKind,
+}
+
+/// Fired when an effect is re-applied, resetting its duration and possibly adding a stack
+#[derive(Event, Debug, Clone)]
+pub struct StatusEffectRefreshed {
+    pub target: Entity,
+    pub source: Entity,
+    pub kind: StatusEffectKind,
+    pub stacks: u8,
+}
+
+/// Fired when an effect leaves a target, whether it ran out, was cleansed or was stolen
+#[derive(Event, Debug, Clone)]
+pub struct StatusEffectExpired {
+    pub target: Entity,
+    pub kind: StatusEffectKind,
+}
+
+/// Applies requested effects in the order they were sent
+pub fn apply_status_effects(
+    mut apply_events: EventReader<ApplyStatusEffect>,
+    mut target_q: Query<&mut StatusEffects>,
+    mut applied_events: EventWriter<StatusEffectApplied>,
+    mut refreshed_events: EventWriter<StatusEffectRefreshed>,
+) {
+    for event in apply_events.read() {
+        let Ok(mut effects) = target_q.get_mut(event.target) else {
+            continue;
+        };
+        match effects.apply(event.kind, event.source) {
+            ApplyOutcome::Applied => {
+                applied_events.write(StatusEffectApplied {
+                    target: event.target,
+                    source: event.source,
+                    kind: event.kind,
+                });
+            }
+            ApplyOutcome::Refreshed { stacks } => {
+                refreshed_events.write(StatusEffectRefreshed {
+                    target: event.target,
+                    source: event.source,
+                    kind: event.kind,
+                    stacks,
+                });
+            }
+        }
+    }
+}
+
+/// Advances effects on entities in arenas that are playing back by the slice of loop
+/// time their arena's TimelineClock covered, so replays tick identically and paused
+/// clocks hold every effect still
+pub fn tick_status_effects(
+    mut cursors: Local<HashMap<Entity, ScriptCursor>>,
+    clock_q: Query<(Entity, &TimelineClock), With<Playback>>,
+    mut target_q: Query<(Entity, &mut StatusEffects, &ChildOf)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut heal_events: EventWriter<HealEvent>,
+    mut expired_events: EventWriter<StatusEffectExpired>,
+) {
+    let deltas: HashMap<Entity, Duration> = clock_q
+        .iter()
+        .map(|(arena, clock)| {
+            let window = cursors.entry(arena).or_default().advance(clock);
+            let seconds = window.to.as_secs() - window.from.as_secs();
+            (arena, Duration::from_secs_f32(seconds))
+        })
+        .collect();
+
+    for (target, mut effects, child_of) in target_q.iter_mut() {
+        let Some(&delta) = deltas.get(&child_of.parent()) else {
+            continue;
+        };
+        if effects.0.is_empty() || delta.is_zero() {
+            continue;
+        }
+
+        let (ticks, expired) = effects.advance(delta);
+        for tick in ticks {
+            match tick.kind.data().category {
+                EffectCategory::Debuff => {
+                    damage_events.write(DamageEvent {
+                        source: tick.source,
+                        target,
+                        amount: tick.amount,
+                        cause: DamageCause::Status(tick.kind),
+                    });
+                }
+                EffectCategory::Buff => {
+                    heal_events.write(HealEvent {
+                        source: tick.source,
+                        target,
+                        amount: tick.amount,
+                    });
+                }
+            }
+        }
+        for effect in expired {
+            expired_events.write(StatusEffectExpired {
+                target,
+                kind: effect.kind,
+            });
+        }
+    }
+}
+
+/// Removes every debuff from cleansed targets
+pub fn cleanse_status_effects(
+    mut cleanse_events: EventReader<CleanseStatusEffects>,
+    mut target_q: Query<&mut StatusEffects>,
+    mut expired_events: EventWriter<StatusEffectExpired>,
+) {
+    for event in cleanse_events.read() {
+        let Ok(mut effects) = target_q.get_mut(event.target) else {
+            continue;
+        };
+        for effect in effects.remove_category(EffectCategory::Debuff) {
+            expired_events.write(StatusEffectExpired {
+                target: event.target,
+                kind: effect.kind,
+            });
+        }
+    }
+}
+
+/// Moves the target's newest buff onto the thief with its remaining duration intact
+pub fn steal_status_effects(
+    mut steal_events: EventReader<StealStatusEffect>,
+    mut target_q: Query<&mut StatusEffects>,
+    mut expired_events: EventWriter<StatusEffectExpired>,
+    mut applied_events: EventWriter<StatusEffectApplied>,
+) {
+    for event in steal_events.read() {
+        let Ok([mut thief_effects, mut target_effects]) =
+            target_q.get_many_mut([event.thief, event.target])
+        else {
+            continue;
+        };
+        let Some(mut stolen) = target_effects.take_newest_buff() else {
+            continue;
+        };
+
+        expired_events.write(StatusEffectExpired {
+            target: event.target,
+            kind: stolen.kind,
+        });
+        thief_effects.0.retain(|effect| effect.kind != stolen.kind);
+        stolen.source = event.thief;
+        applied_events.write(StatusEffectApplied {
+            target: event.thief,
+            source: event.thief,
+            kind: stolen.kind,
+        });
+        thief_effects.0.push(stolen);
+    }
+}
+
+/// Strips every effect in an arena when its 2-minute loop restarts, so each loop
+/// starts clean the same way health does
+pub fn clear_status_effects_on_arena_reset(
+    mut reset_events: EventReader<ArenaReset>,
+    arena_q: Query<&Arena>,
+    mut target_q: Query<(Entity, &mut StatusEffects, &ChildOf)>,
+    mut expired_events: EventWriter<StatusEffectExpired>,
+) {
+    for event in reset_events.read() {
+        for (target, mut effects, child_of) in target_q.iter_mut() {
+            let in_arena = arena_q
+                .get(child_of.parent())
+                .is_ok_and(|arena| arena.0 == event.arena);
+            if !in_arena || effects.0.is_empty() {
+                continue;
+            }
+            for effect in effects.0.drain(..) {
+                expired_events.write(StatusEffectExpired {
+                    target,
+                    kind: effect.kind,
+                });
+            }
+        }
+    }
+}
+
+/// Plugin for buffs, debuffs, damage over time and healing over time
+pub struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ApplyStatusEffect>()
+            .add_event::<CleanseStatusEffects>()
+            .add_event::<StealStatusEffect>()
+            .add_event::<StatusEffectApplied>()
+            .add_event::<StatusEffectRefreshed>()
+            .add_event::<StatusEffectExpired>()
+            .add_event::<ArenaReset>()
+            .add_systems(
+                Update,
+                (
+                    clear_status_effects_on_arena_reset.after(emit_arena_resets),
+                    apply_status_effects,
+                    cleanse_status_effects,
+                    steal_status_effects,
+                    tick_status_effects.after(update_timeline_clocks),
+                )
+                    .chain()
+                    .before(apply_damage),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,333 @@
+use super::*;
+use crate::ability::{
+    AbilityCast, AbilityEffectPlugin, AbilityType, AlchemistAbility, BardAbility, ForagerAbility,
+    HunterAbility, ThiefAbility,
+};
+use crate::arena::ArenaName;
+use crate::character::{Boss, Character};
+use crate::combat::{CombatPlugin, Health};
+use crate::timeline::{TimelineClock, update_timeline_clocks};
+use bevy::app::App;
+use bevy::time::TimeUpdateStrategy;
+
+const SOURCE: Entity = Entity::PLACEHOLDER;
+
+#[test]
+fn test_refresh_effects_keep_a_single_stack() {
+    let mut effects = StatusEffects::default();
+
+    assert_eq!(
+        effects.apply(StatusEffectKind::Ironskin, SOURCE),
+        ApplyOutcome::Applied
+    );
+    effects.advance(Duration::from_secs(5));
+    assert_eq!(
+        effects.apply(StatusEffectKind::Ironskin, SOURCE),
+        ApplyOutcome::Refreshed { stacks: 1 }
+    );
+
+    assert_eq!(effects.0.len(), 1);
+    assert_eq!(
+        effects.0[0].remaining,
+        StatusEffectKind::Ironskin.data().duration
+    );
+}
+
+#[test]
+fn test_stacking_effects_cap_at_max_stacks() {
+    let mut effects = StatusEffects::default();
+
+    for _ in 0..10 {
+        effects.apply(StatusEffectKind::Poison, SOURCE);
+    }
+
+    assert_eq!(effects.stacks_of(StatusEffectKind::Poison), 5);
+}
+
+#[test]
+fn test_dot_ticks_are_independent_of_frame_rate() {
+    let mut coarse = StatusEffects::default();
+    let mut fine = StatusEffects::default();
+    coarse.apply(StatusEffectKind::Poison, SOURCE);
+    fine.apply(StatusEffectKind::Poison, SOURCE);
+
+    let coarse_total: f32 = (0..4)
+        .flat_map(|_| coarse.advance(Duration::from_millis(1_500)).0)
+        .map(|tick| tick.amount)
+        .sum();
+    let fine_total: f32 = (0..360)
+        .flat_map(|_| fine.advance(Duration::from_micros(16_667)).0)
+        .map(|tick| tick.amount)
+        .sum();
+
+    // Six one-second ticks over the six-second duration, regardless of step size
+    let expected = StatusEffectKind::Poison.data().magnitude * 6.0;
+    assert_eq!(coarse_total, expected);
+    assert_eq!(fine_total, expected);
+    assert!(coarse.0.is_empty());
+    assert!(fine.0.is_empty());
+}
+
+#[test]
+fn test_effects_expire_after_their_duration() {
+    let mut effects = StatusEffects::default();
+    effects.apply(StatusEffectKind::Haste, SOURCE);
+
+    let (_, expired) = effects.advance(Duration::from_secs(9));
+    assert!(expired.is_empty());
+    assert!(effects.speed_multiplier() > 1.0);
+
+    let (_, expired) = effects.advance(Duration::from_secs(1));
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].kind, StatusEffectKind::Haste);
+    assert_eq!(effects.speed_multiplier(), 1.0);
+}
+
+#[test]
+fn test_cleanse_removes_only_debuffs_and_pickpocket_takes_newest_buff() {
+    let mut effects = StatusEffects::default();
+    effects.apply(StatusEffectKind::Ironskin, SOURCE);
+    effects.apply(StatusEffectKind::Poison, SOURCE);
+    effects.apply(StatusEffectKind::Haste, SOURCE);
+
+    let removed = effects.remove_category(EffectCategory::Debuff);
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].kind, StatusEffectKind::Poison);
+
+    let stolen = effects.take_newest_buff().expect("Should steal a buff");
+    assert_eq!(stolen.kind, StatusEffectKind::Haste);
+    assert_eq!(effects.0.len(), 1);
+    assert_eq!(effects.0[0].kind, StatusEffectKind::Ironskin);
+}
+
+#[test]
+fn test_ironskin_reduces_incoming_damage() {
+    let mut effects = StatusEffects::default();
+    assert_eq!(effects.damage_taken_multiplier(), 1.0);
+
+    effects.apply(StatusEffectKind::Ironskin, SOURCE);
+    assert_eq!(effects.damage_taken_multiplier(), 0.5);
+}
+
+#[test]
+fn test_poison_ticks_on_running_arena_clocks_only() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        250,
+    )));
+    app.add_plugins((CombatPlugin, StatusEffectPlugin));
+    app.add_systems(Update, update_timeline_clocks);
+
+    let playing_arena = app
+        .world_mut()
+        .spawn((
+            Arena(ArenaName::Labyrinth),
+            TimelineClock::default(),
+            Playback,
+        ))
+        .id();
+    let idle_arena = app
+        .world_mut()
+        .spawn((Arena(ArenaName::Gala), TimelineClock::default()))
+        .id();
+    let mut paused_clock = TimelineClock::default();
+    paused_clock.pause();
+    let paused_arena = app
+        .world_mut()
+        .spawn((Arena(ArenaName::Bastion), paused_clock, Playback))
+        .id();
+    let targets = [playing_arena, idle_arena, paused_arena].map(|arena| {
+        app.world_mut()
+            .spawn((Health::new(100.0), StatusEffects::default(), ChildOf(arena)))
+            .id()
+    });
+
+    for target in targets {
+        app.world_mut().send_event(ApplyStatusEffect {
+            target,
+            source: SOURCE,
+            kind: StatusEffectKind::Poison,
+        });
+    }
+
+    // The first update only establishes the time baseline
+    for _ in 0..=4 {
+        app.update();
+    }
+
+    let health = |app: &App, entity: Entity| app.world().get::<Health>(entity).unwrap().current;
+    assert_eq!(health(&app, targets[0]), 80.0);
+    assert_eq!(health(&app, targets[1]), 100.0);
+    assert_eq!(health(&app, targets[2]), 100.0);
+    let remaining = |app: &App, entity: Entity| {
+        app.world().get::<StatusEffects>(entity).unwrap().0[0].remaining
+    };
+    assert_eq!(
+        remaining(&app, targets[2]),
+        StatusEffectKind::Poison.data().duration
+    );
+}
+
+#[test]
+fn test_arena_reset_clears_effects_in_that_arena_only() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.add_plugins((CombatPlugin, StatusEffectPlugin));
+
+    let labyrinth = app.world_mut().spawn(Arena(ArenaName::Labyrinth)).id();
+    let gala = app.world_mut().spawn(Arena(ArenaName::Gala)).id();
+    let targets = [labyrinth, gala].map(|arena| {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffectKind::Ironskin, SOURCE);
+        effects.apply(StatusEffectKind::Poison, SOURCE);
+        app.world_mut()
+            .spawn((Health::new(100.0), effects, ChildOf(arena)))
+            .id()
+    });
+
+    app.world_mut().send_event(ArenaReset {
+        arena: ArenaName::Labyrinth,
+    });
+    app.update();
+
+    let effects =
+        |app: &App, entity: Entity| app.world().get::<StatusEffects>(entity).unwrap().0.len();
+    assert_eq!(effects(&app, targets[0]), 0);
+    assert_eq!(effects(&app, targets[1]), 2);
+}
+
+#[test]
+fn test_cleanse_and_pickpocket_casts_move_effects() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.add_plugins((CombatPlugin, StatusEffectPlugin, AbilityEffectPlugin));
+
+    let arena = app.world_mut().spawn(Arena(ArenaName::Labyrinth)).id();
+    let spawn_with = |app: &mut App, kind: StatusEffectKind, character: bool| {
+        let mut effects = StatusEffects::default();
+        effects.apply(kind, SOURCE);
+        let mut entity = app
+            .world_mut()
+            .spawn((Health::new(100.0), effects, ChildOf(arena)));
+        if character {
+            entity.insert(Character);
+        } else {
+            entity.insert(Boss);
+        }
+        entity.id()
+    };
+    let poisoned = spawn_with(&mut app, StatusEffectKind::Poison, true);
+    let thief = spawn_with(&mut app, StatusEffectKind::Haste, true);
+    let boss = spawn_with(&mut app, StatusEffectKind::Ironskin, false);
+
+    app.world_mut().send_event(AbilityCast {
+        caster: poisoned,
+        ability: AbilityType::Bard(BardAbility::Cleanse),
+    });
+    app.world_mut().send_event(AbilityCast {
+        caster: thief,
+        ability: AbilityType::Thief(ThiefAbility::Pickpocket),
+    });
+    app.update();
+
+    let effects =
+        |app: &App, entity: Entity| app.world().get::<StatusEffects>(entity).unwrap().clone();
+    assert_eq!(
+        effects(&app, poisoned).stacks_of(StatusEffectKind::Poison),
+        0
+    );
+    assert_eq!(
+        effects(&app, thief).stacks_of(StatusEffectKind::Ironskin),
+        1
+    );
+    assert_eq!(effects(&app, boss).stacks_of(StatusEffectKind::Ironskin), 0);
+}
+
+#[test]
+fn test_ironskin_draft_hardens_the_caster() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.add_plugins((CombatPlugin, StatusEffectPlugin, AbilityEffectPlugin));
+
+    let arena = app.world_mut().spawn(Arena(ArenaName::Labyrinth)).id();
+    let alchemist = app
+        .world_mut()
+        .spawn((
+            Character,
+            Health::new(100.0),
+            StatusEffects::default(),
+            ChildOf(arena),
+        ))
+        .id();
+    app.world_mut().send_event(AbilityCast {
+        caster: alchemist,
+        ability: AbilityType::Alchemist(AlchemistAbility::Ironskin),
+    });
+    app.update();
+
+    let effects = app.world().get::<StatusEffects>(alchemist).unwrap();
+    assert_eq!(effects.stacks_of(StatusEffectKind::Ironskin), 1);
+    assert!(effects.damage_taken_multiplier() < 1.0);
+}
+
+#[test]
+fn test_poison_shot_helix_and_mushroom_casts_tick_through_the_arena() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        250,
+    )));
+    app.add_plugins((CombatPlugin, StatusEffectPlugin, AbilityEffectPlugin));
+    app.add_systems(Update, update_timeline_clocks);
+
+    let arena = app
+        .world_mut()
+        .spawn((
+            Arena(ArenaName::Labyrinth),
+            TimelineClock::default(),
+            Playback,
+        ))
+        .id();
+    let boss = app
+        .world_mut()
+        .spawn((Boss, Health::new(1_000.0), ChildOf(arena)))
+        .id();
+    let [hunter, bard, forager] = [(); 3].map(|_| {
+        app.world_mut()
+            .spawn((
+                Character,
+                Health {
+                    current: 50.0,
+                    max: 100.0,
+                },
+                ChildOf(arena),
+            ))
+            .id()
+    });
+    for (caster, ability) in [
+        (hunter, AbilityType::Hunter(HunterAbility::PoisonShot)),
+        (bard, AbilityType::Bard(BardAbility::Helix)),
+        (forager, AbilityType::Forager(ForagerAbility::Mushroom)),
+    ] {
+        app.world_mut().send_event(AbilityCast { caster, ability });
+    }
+
+    // The casts land on the first update; the clock then covers one second
+    for _ in 0..=4 {
+        app.update();
+    }
+
+    let world = app.world();
+    let boss_effects = world.get::<StatusEffects>(boss).unwrap();
+    assert_eq!(boss_effects.stacks_of(StatusEffectKind::Poison), 1);
+    // Poison Shot's 60 and Helix's 90 on impact, then one 20-point poison tick
+    assert_eq!(world.get::<Health>(boss).unwrap().current, 830.0);
+    for hero in [hunter, bard, forager] {
+        let effects = world.get::<StatusEffects>(hero).unwrap();
+        assert_eq!(effects.stacks_of(StatusEffectKind::Haste), 1);
+        assert_eq!(effects.stacks_of(StatusEffectKind::Regeneration), 1);
+        // One 15-point regeneration tick
+        assert_eq!(world.get::<Health>(hero).unwrap().current, 65.0);
+    }
+}
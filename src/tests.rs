@@ -0,0 +1,150 @@
+use super::*;
+use crate::ability::{AbilityCooldowns, AbilityType, CardinalAbility};
+use crate::class_type::ClassType;
+use crate::combat::{DamageCause, DamageEvent};
+use crate::pathfinding::{CLICK_MOVE_STEP_SECONDS, PathFollower};
+use crate::timeline::{DraftTimeline, EventType, TimeStamp};
+use bevy::asset::AssetPlugin;
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::{ButtonState, InputPlugin};
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+/// The game's own plugin set, without a window or renderer
+fn game_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        InputPlugin,
+        bevy::state::app::StatesPlugin,
+    ))
+    // Gizmos keep their line shaders as assets even with no renderer to draw them
+    .init_asset::<Shader>()
+    .add_plugins(bevy::gizmos::GizmoPlugin)
+    .init_asset::<Mesh>()
+    .init_asset::<StandardMaterial>()
+    .init_asset::<Image>()
+    .init_asset::<Font>()
+    .init_asset::<AudioSource>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        50,
+    )))
+    .add_plugins(GamePlugin);
+    app
+}
+
+/// Presses a key the way a window would report it
+fn press_key(app: &mut App, key_code: KeyCode) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+/// The hero the player controls once the game has started
+fn active_hero(app: &mut App) -> Entity {
+    let world = app.world_mut();
+    world
+        .query_filtered::<Entity, (With<Character>, With<Active>)>()
+        .single(world)
+        .unwrap()
+}
+
+#[test]
+fn test_arena_loops_run_in_the_windowed_plugin_set() {
+    let mut app = game_app();
+    let labyrinth = {
+        app.update();
+        app.world()
+            .resource::<ArenaEntities>()
+            .get(ArenaName::Labyrinth)
+    };
+    let ghosts = |app: &mut App| {
+        let world = app.world_mut();
+        let mut ghost_q =
+            world.query_filtered::<(&ChildOf, &GridPositionComponent), With<character::Ghost>>();
+        ghost_q
+            .iter(world)
+            .filter(|(child_of, _)| child_of.parent() == labyrinth)
+            .map(|(_, position)| position.0)
+            .collect::<Vec<_>>()
+    };
+    let starts = ghosts(&mut app);
+    assert!(!starts.is_empty());
+
+    // The demo recordings move and strike within their first ten seconds
+    for _ in 0..240 {
+        app.update();
+    }
+
+    let world = app.world();
+    let clock = world.get::<TimelineClock>(labyrinth).unwrap();
+    assert!(clock.current() > TimeStamp::new(10.0));
+    assert_ne!(ghosts(&mut app), starts);
+    let mut boss_q = app
+        .world_mut()
+        .query_filtered::<(&ChildOf, &Health), With<Boss>>();
+    let (_, health) = boss_q
+        .iter(app.world())
+        .find(|(child_of, _)| child_of.parent() == labyrinth)
+        .unwrap();
+    assert!(health.current < health.max);
+}
+
+#[test]
+fn test_a_clicked_path_walks_the_active_hero_in_the_windowed_plugin_set() {
+    let mut app = game_app();
+    app.update();
+    let world = app.world_mut();
+    let mut active_q = world.query_filtered::<(Entity, &GridPositionComponent), With<Active>>();
+    let (hero, start) = active_q
+        .iter(world)
+        .map(|(entity, position)| (entity, position.0))
+        .find(|(entity, _)| world.get::<Character>(*entity).is_some())
+        .unwrap();
+    // Roster heroes stand side by side, so walk down the column
+    let goal = start - IVec2::new(0, 3);
+    world.entity_mut(hero).insert(PathFollower::new(
+        goal,
+        vec![start - IVec2::Y, start - IVec2::new(0, 2), goal],
+        CLICK_MOVE_STEP_SECONDS,
+    ));
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    assert_eq!(
+        app.world().get::<GridPositionComponent>(hero).unwrap().0,
+        goal
+    );
+}
+
+#[test]
+fn test_an_ability_key_casts_for_the_active_hero_in_the_windowed_plugin_set() {
+    let mut app = game_app();
+    app.update();
+    let hero = active_hero(&mut app);
+    app.world_mut().entity_mut(hero).insert(ClassType::Cardinal);
+
+    press_key(&mut app, KeyCode::Digit1);
+    app.update();
+
+    let nova = AbilityType::Cardinal(CardinalAbility::HolyNova);
+    let world = app.world();
+    assert!(!world.get::<AbilityCooldowns>(hero).unwrap().is_ready(nova));
+    let mut damage = world.resource::<Events<DamageEvent>>().get_cursor();
+    assert!(
+        damage
+            .read(world.resource::<Events<DamageEvent>>())
+            .any(|event| event.source == hero && event.cause == DamageCause::Ability(nova))
+    );
+    assert!(world.resource::<DraftTimeline>().events.iter().any(
+        |event| matches!(event.event_type, EventType::Ability(ability, _) if ability == nova)
+    ));
+}
@@ -1,5 +1,10 @@
-use crate::ability::AbilityType;
-use crate::arena::{Arena, ArenaName, CurrentArenaEntity};
+mod playback;
+mod save;
+
+use crate::ability::{AbilityCast, AbilityType, resolve_ability_casts};
+use crate::arena::{Arena, ArenaName, ArenaReset, CurrentArenaEntity, MoveRequest};
+use crate::character::Interact;
+use crate::difficulty::Difficulty;
 use crate::recording::Playback;
 use bevy::ecs::change_detection::DetectChanges;
 use bevy::log::trace;
@@ -11,6 +16,9 @@ use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
 
+pub use playback::*;
+pub use save::*;
+
 // RULE 3 COMPLIANCE: Events for timeline communication
 /// Event to notify systems when timeline reaches major checkpoints
 #[derive(Event)]
@@ -51,6 +59,35 @@ pub struct TimelineEvent {
     pub event_type: EventType,
 }
 
+impl TimelineEvent {
+    /// A single-tile movement step, as recorded from keyboard or click-to-move input
+    #[must_use]
+    pub fn movement(timestamp: TimeStamp, step: IVec2) -> Self {
+        Self {
+            timestamp,
+            event_type: EventType::Movement(step.as_vec2().extend(0.0)),
+        }
+    }
+
+    /// An ability, as recorded from one of the ability bar actions
+    #[must_use]
+    pub fn ability(timestamp: TimeStamp, ability: AbilityType) -> Self {
+        Self {
+            timestamp,
+            event_type: EventType::Ability(ability, None),
+        }
+    }
+
+    /// An interaction, as recorded from the Interact action
+    #[must_use]
+    pub fn interaction(timestamp: TimeStamp) -> Self {
+        Self {
+            timestamp,
+            event_type: EventType::Interact,
+        }
+    }
+}
+
 /// Newtype for timeline timestamps (0.0 to 120.0 seconds)
 /// PR Gate: TimeStamp + Duration pattern for type safety (not raw f32)
 ///
@@ -122,6 +159,8 @@ pub enum EventType {
     Ability(AbilityType, Option<TargetData>),
     /// Character death event
     Death,
+    /// Interaction with whatever lies on or beside the character's tile (treasure piles)
+    Interact,
 }
 
 /// Target data for events
@@ -135,7 +174,7 @@ pub enum TargetData {
 /// Component for entity's grid position - NEVER used in events
 /// Only attached to entities, never in event payloads
 #[derive(Component, Clone, Copy, Debug, PartialEq)]
-pub struct GridPositionComponent(IVec2);
+pub struct GridPositionComponent(pub IVec2);
 
 impl Display for GridPositionComponent {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -211,6 +250,8 @@ pub struct PublishTimeline {
     /// APPROVED: Arc<[T]> for immutable shared timeline data
     /// Zero-cost cloning, cache-friendly iteration
     pub events: Arc<[TimelineEvent]>,
+    /// Tier the arena was on when this was recorded
+    pub difficulty: Difficulty,
 }
 
 /// Component that stores multiple timelines per character using array indexing
@@ -268,9 +309,17 @@ impl PublishTimeline {
     pub fn from_draft(draft: DraftTimeline) -> Self {
         Self {
             events: draft.events.into(),
+            difficulty: Difficulty::default(),
         }
     }
 
+    /// Marks the timeline as recorded at the given tier
+    #[must_use]
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
     /// Zero-alloc helper: Get events within a time range
     /// Returns events where start <= timestamp < end
     #[must_use]
@@ -488,6 +537,18 @@ pub fn update_timeline_clocks(
     }
 }
 
+/// System to announce arenas whose clock wrapped past 120 seconds this frame
+pub fn emit_arena_resets(
+    arena_q: Query<(&Arena, &TimelineClock)>,
+    mut reset_events: EventWriter<ArenaReset>,
+) {
+    for (arena, clock) in arena_q.iter() {
+        if clock.timer.just_finished() {
+            reset_events.write(ArenaReset { arena: arena.0 });
+        }
+    }
+}
+
 /// Control virtual time based on GlobalTimelinePause state
 pub fn control_virtual_time_pause(
     global_pause: Res<GlobalTimelinePause>,
@@ -528,12 +589,19 @@ impl Plugin for TimelinePlugin {
         app.init_resource::<GlobalTimelinePause>()
             .init_resource::<DraftTimeline>()
             .add_event::<TimelineCheckpoint>()
+            .add_event::<ArenaReset>()
+            .add_event::<MoveRequest>()
+            .add_event::<AbilityCast>()
+            .add_event::<Interact>()
             .add_systems(
                 Update,
                 (
                     // Control virtual time pause state BEFORE updating clocks
                     control_virtual_time_pause,
                     update_timeline_clocks,
+                    emit_arena_resets,
+                    flag_off_tier_recordings,
+                    replay_ghost_timelines.before(resolve_ability_casts),
                     debug_timeline_clocks,
                 )
                     .chain(),
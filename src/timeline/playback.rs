@@ -0,0 +1,184 @@
+use crate::ability::AbilityCast;
+use crate::arena::{Arena, ArenaEntities, ArenaName, MoveRequest, step_tile};
+use crate::character::{Ghost, Interact};
+use crate::combat::Health;
+use crate::difficulty::Difficulty;
+use crate::recording::Playback;
+use crate::timeline::{
+    EventType, GridPositionComponent, TimeStamp, TimelineClock, TimelineEvent, TimelineManager,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Where a ghost's recorded run begins - it returns here each time its arena loops
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[require(ReplayCursor, QueuedSteps)]
+pub struct ReplayOrigin {
+    pub arena: ArenaName,
+    pub tile: IVec2,
+}
+
+/// Timestamp up to which a ghost's timeline has been replayed this loop
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct ReplayCursor(pub TimeStamp);
+
+/// Recorded steps a ghost has yet to take - the resolver moves a unit once per tick,
+/// so steps recorded closer together than a frame are taken on following ticks
+#[derive(Component, Debug, Clone, Default)]
+pub struct QueuedSteps(pub VecDeque<IVec2>);
+
+/// Ghost whose recording was made on a different tier than its arena is set to -
+/// it sits out playback until the tiers match again
+#[derive(Component, Debug, Clone, Copy)]
+pub struct OffTierRecording;
+
+/// Flags ghosts whose recording does not match their arena's current difficulty
+pub fn flag_off_tier_recordings(
+    mut commands: Commands,
+    arena_entities: Res<ArenaEntities>,
+    arena_q: Query<&Difficulty>,
+    ghost_q: Query<
+        (
+            Entity,
+            &ReplayOrigin,
+            &TimelineManager,
+            Option<&OffTierRecording>,
+        ),
+        With<Ghost>,
+    >,
+) {
+    for (ghost, origin, manager, flagged) in ghost_q.iter() {
+        let Ok(difficulty) = arena_q.get(arena_entities.get(origin.arena)) else {
+            continue;
+        };
+        let Some(timeline) = manager.get_timeline(origin.arena) else {
+            continue;
+        };
+        match (timeline.difficulty == *difficulty, flagged) {
+            (false, None) => {
+                info!(
+                    "Ghost recorded on {} sits out {} on {difficulty}",
+                    timeline.difficulty, origin.arena
+                );
+                commands.entity(ghost).insert(OffTierRecording);
+            }
+            (true, Some(_)) => {
+                commands.entity(ghost).remove::<OffTierRecording>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Event writers a replayed timeline drives
+#[derive(SystemParam)]
+pub struct GhostActions<'w> {
+    moves: EventWriter<'w, MoveRequest>,
+    casts: EventWriter<'w, AbilityCast>,
+    interactions: EventWriter<'w, Interact>,
+}
+
+/// Ghosts replaying a timeline on their recording's tier, with the state replay moves
+type ReplayingGhostQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static ReplayOrigin,
+        &'static mut ReplayCursor,
+        &'static mut QueuedSteps,
+        &'static TimelineManager,
+        &'static mut GridPositionComponent,
+        &'static mut Health,
+        &'static ChildOf,
+    ),
+    (With<Ghost>, Without<OffTierRecording>),
+>;
+
+/// Replays recorded timelines for ghosts whose arena is in playback
+///
+/// Movement becomes occupancy move requests and abilities become casts, so a ghost
+/// obeys the same collision and combat rules as the live hero who recorded it.
+/// Steps are queued and taken one per tick, each from where the last one landed.
+/// When the arena clock wraps, the rest of the loop is flushed and the ghost returns
+/// to its origin at full health.
+pub fn replay_ghost_timelines(
+    arena_entities: Res<ArenaEntities>,
+    clock_q: Query<&TimelineClock, With<Playback>>,
+    arena_q: Query<&Arena>,
+    mut ghost_q: ReplayingGhostQuery,
+    mut commands: Commands,
+    mut ghost_actions: GhostActions,
+) {
+    for (ghost, origin, mut cursor, mut steps, manager, mut position, mut health, child_of) in
+        ghost_q.iter_mut()
+    {
+        let origin_entity = arena_entities.get(origin.arena);
+        let Ok(clock) = clock_q.get(origin_entity) else {
+            continue;
+        };
+        let Some(timeline) = manager.get_timeline(origin.arena) else {
+            continue;
+        };
+        let Ok(arena) = arena_q.get(child_of.parent()) else {
+            continue;
+        };
+
+        let mut replay = |event: &TimelineEvent| {
+            if health.is_dead() {
+                return;
+            }
+            match &event.event_type {
+                EventType::Movement(step) => {
+                    steps.0.push_back(step.truncate().as_ivec2());
+                }
+                EventType::Ability(ability, _) => {
+                    ghost_actions.casts.write(AbilityCast {
+                        caster: ghost,
+                        ability: *ability,
+                    });
+                }
+                EventType::Interact => {
+                    ghost_actions.interactions.write(Interact { actor: ghost });
+                }
+                // Replayed deaths come from combat, not from the recording
+                EventType::Death => {}
+            }
+        };
+
+        let now = clock.current();
+        if clock.timer.just_finished() {
+            // Tail moves are moot - the ghost is back at its origin this frame
+            timeline
+                .events
+                .iter()
+                .skip_while(|event| event.timestamp < cursor.0)
+                .filter(|event| !matches!(event.event_type, EventType::Movement(_)))
+                .for_each(&mut replay);
+
+            steps.0.clear();
+            position.0 = origin.tile;
+            if child_of.parent() != origin_entity {
+                commands.entity(ghost).insert(ChildOf(origin_entity));
+            }
+            *health = Health::new(health.max);
+            cursor.0 = TimeStamp::ZERO;
+        } else if let Ok(events) = timeline.events_in_range(cursor.0, now) {
+            events.for_each(replay);
+            cursor.0 = now;
+
+            if health.is_dead() {
+                steps.0.clear();
+            } else if let Some(step) = steps.0.pop_front()
+                && let Some((to_arena, to_tile)) = step_tile(arena.0, position.0, step)
+            {
+                ghost_actions.moves.write(MoveRequest {
+                    entity: ghost,
+                    to_arena,
+                    to_tile,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::ability::AbilityType;
+use crate::arena::ArenaName;
+use crate::character::{CHARACTER_MAX_HEALTH, Character, Ghost};
+use crate::class_type::ClassType;
+use crate::combat::Health;
+use crate::difficulty::Difficulty;
+use crate::timeline::{
+    DraftTimeline, EventType, GridPositionComponent, PublishTimeline, ReplayOrigin, TargetData,
+    TimeStamp, TimelineEvent, TimelineManager,
+};
+use bevy::prelude::{Bundle, IVec2, Name};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Error types for reading saved timeline files
+#[derive(Error, Debug)]
+pub enum TimelineFileError {
+    #[error("Could not read timeline file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Missing `{field}` header")]
+    MissingHeader { field: &'static str },
+    #[error("Line {line}: {message}")]
+    Malformed { line: usize, message: String },
+    #[error("Name `{name}` must not be blank or contain `#` or line breaks")]
+    InvalidName { name: String },
+}
+
+/// One hero's recorded run in an arena, as written to disk (value type)
+#[derive(Clone)]
+pub struct SavedTimeline {
+    pub name: String,
+    pub class: ClassType,
+    pub arena: ArenaName,
+    /// Local tile the recording started from
+    pub start: IVec2,
+    pub timeline: PublishTimeline,
+}
+
+impl SavedTimeline {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TimelineFileError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Components for a ghost replaying this run in `arena`, which need not be
+    /// the arena it was recorded in
+    pub fn ghost_bundle(&self, arena: ArenaName) -> impl Bundle {
+        let mut manager = TimelineManager::new();
+        manager.set_timeline(arena, self.timeline.clone());
+        (
+            Character,
+            Ghost,
+            self.class,
+            Health::new(CHARACTER_MAX_HEALTH),
+            GridPositionComponent(self.start),
+            ReplayOrigin {
+                arena,
+                tile: self.start,
+            },
+            manager,
+            Name::new(self.name.clone()),
+        )
+    }
+}
+
+/// File format: `name`, `class`, `arena`, `difficulty` and `start` headers
+/// (files without `difficulty` were recorded on Normal), then one
+/// `<seconds> move <x> <y>`, `<seconds> ability <Class:Ability> [at <x> <y>]`,
+/// `<seconds> death` or `<seconds> interact` line per event, `#` comments
+impl Display for SavedTimeline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "name {}", self.name)?;
+        writeln!(f, "class {}", self.class.name())?;
+        writeln!(f, "arena {:?}", self.arena)?;
+        writeln!(f, "difficulty {}", self.timeline.difficulty)?;
+        writeln!(f, "start {} {}", self.start.x, self.start.y)?;
+        for event in self.timeline.events.iter() {
+            let seconds = event.timestamp.as_secs();
+            match &event.event_type {
+                EventType::Movement(step) => {
+                    let step = step.truncate().as_ivec2();
+                    writeln!(f, "{seconds} move {} {}", step.x, step.y)?;
+                }
+                EventType::Ability(ability, Some(TargetData::Position(tile))) => {
+                    writeln!(f, "{seconds} ability {ability} at {} {}", tile.x, tile.y)?;
+                }
+                // Entity targets are only meaningful within one session
+                EventType::Ability(ability, _) => writeln!(f, "{seconds} ability {ability}")?,
+                EventType::Death => writeln!(f, "{seconds} death")?,
+                EventType::Interact => writeln!(f, "{seconds} interact")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SavedTimeline {
+    type Err = TimelineFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut name = None;
+        let mut class = None;
+        let mut arena = None;
+        let mut start = None;
+        let mut difficulty = Difficulty::default();
+        let mut draft = DraftTimeline::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = |message: String| TimelineFileError::Malformed {
+                line: index + 1,
+                message,
+            };
+            let (head, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+            match head {
+                "name" => name = Some(rest.to_string()),
+                "class" => {
+                    class = Some(
+                        rest.parse()
+                            .map_err(|error| malformed(format!("{error}")))?,
+                    );
+                }
+                "arena" => {
+                    arena = Some(
+                        rest.parse()
+                            .map_err(|error| malformed(format!("{error}")))?,
+                    );
+                }
+                "difficulty" => {
+                    difficulty = rest
+                        .parse()
+                        .map_err(|error| malformed(format!("{error}")))?;
+                }
+                "start" => {
+                    start = Some(parse_tile(rest).ok_or_else(|| {
+                        malformed(format!("expected `start <x> <y>`, got `{rest}`"))
+                    })?);
+                }
+                seconds => {
+                    let seconds: f32 = seconds
+                        .parse()
+                        .map_err(|_| malformed(format!("unknown line `{line}`")))?;
+                    let event_type = parse_event(rest).map_err(malformed)?;
+                    draft
+                        .add_event(TimelineEvent {
+                            timestamp: TimeStamp::new(seconds),
+                            event_type,
+                        })
+                        .map_err(|error| malformed(error.to_string()))?;
+                }
+            }
+        }
+
+        Ok(Self {
+            name: name.ok_or(TimelineFileError::MissingHeader { field: "name" })?,
+            class: class.ok_or(TimelineFileError::MissingHeader { field: "class" })?,
+            arena: arena.ok_or(TimelineFileError::MissingHeader { field: "arena" })?,
+            start: start.ok_or(TimelineFileError::MissingHeader { field: "start" })?,
+            timeline: PublishTimeline::from_draft(draft).with_difficulty(difficulty),
+        })
+    }
+}
+
+fn parse_tile(s: &str) -> Option<IVec2> {
+    let (x, y) = s.trim().split_once(' ')?;
+    Some(IVec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+fn parse_event(s: &str) -> Result<EventType, String> {
+    let (kind, rest) = s.split_once(' ').unwrap_or((s, ""));
+    match kind {
+        "move" => parse_tile(rest)
+            .map(|step| EventType::Movement(step.as_vec2().extend(0.0)))
+            .ok_or_else(|| format!("expected `move <x> <y>`, got `{s}`")),
+        "ability" => {
+            let (ability, target) = match rest.split_once(" at ") {
+                Some((ability, tile)) => {
+                    let tile = parse_tile(tile)
+                        .ok_or_else(|| format!("expected `at <x> <y>`, got `{tile}`"))?;
+                    (ability, Some(TargetData::Position(tile)))
+                }
+                None => (rest, None),
+            };
+            let ability: AbilityType =
+                ability.trim().parse().map_err(|error| format!("{error}"))?;
+            Ok(EventType::Ability(ability, target))
+        }
+        "death" => Ok(EventType::Death),
+        "interact" => Ok(EventType::Interact),
+        _ => Err(format!("unknown event `{s}`")),
+    }
+}
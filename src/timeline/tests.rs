@@ -1,6 +1,8 @@
 use super::*;
 use crate::ability::{AbilityType, HunterAbility};
-use crate::arena::{Arena, ArenaName};
+use crate::arena::{Arena, ArenaName, CurrentArena, OccupancyPlugin, create_test_arenas};
+use crate::character::Ghost;
+use crate::combat::Health;
 use std::time::Duration;
 
 #[test]
@@ -263,3 +265,101 @@ fn test_timeline_clock_only_runs_with_playback_component() {
         "First clock should continue advancing"
     );
 }
+
+#[test]
+fn test_saved_timeline_round_trips_through_the_file_format() {
+    let text = "\
+name Rook
+class Hunter
+arena GuildHouse
+difficulty Heroic
+start 12 7
+1.5 move 1 0
+2.25 ability Hunter:Trap at 20 9
+3 ability Hunter:AutoShot
+4 death
+";
+    let saved: SavedTimeline = text.parse().unwrap();
+    assert_eq!(saved.arena, ArenaName::GuildHouse);
+    assert_eq!(saved.start, IVec2::new(12, 7));
+    assert_eq!(saved.timeline.difficulty, Difficulty::Heroic);
+    assert!(matches!(
+        saved.timeline.events[1].event_type,
+        EventType::Ability(
+            AbilityType::Hunter(HunterAbility::Trap),
+            Some(TargetData::Position(IVec2 { x: 20, y: 9 }))
+        )
+    ));
+    assert_eq!(saved.to_string(), text);
+
+    // Files from before tiers existed were recorded on Normal
+    let legacy: SavedTimeline = text.replace("difficulty Heroic\n", "").parse().unwrap();
+    assert_eq!(legacy.timeline.difficulty, Difficulty::Normal);
+}
+
+#[test]
+fn test_saved_timeline_errors_name_the_line() {
+    assert!(matches!(
+        "name Rook\nclass Hunter\narena Moon".parse::<SavedTimeline>(),
+        Err(TimelineFileError::Malformed { line: 3, .. })
+    ));
+    assert!(matches!(
+        "name Rook\nclass Hunter\narena Casino\nstart 1 1\n5 ability Hunter:Fireball"
+            .parse::<SavedTimeline>(),
+        Err(TimelineFileError::Malformed { line: 5, .. })
+    ));
+    assert!(matches!(
+        "name Rook\nclass Hunter\narena Casino".parse::<SavedTimeline>(),
+        Err(TimelineFileError::MissingHeader { field: "start" })
+    ));
+}
+
+#[test]
+fn test_two_moves_recorded_in_one_tick_are_both_replayed() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(OccupancyPlugin)
+        .add_event::<AbilityCast>()
+        .add_event::<Interact>()
+        .insert_resource(CurrentArena(ArenaName::GuildHouse))
+        .add_systems(Update, replay_ghost_timelines);
+    let arenas = create_test_arenas(app.world_mut(), |_| (TimelineClock::new(), Playback));
+    let labyrinth = arenas.get(ArenaName::Labyrinth);
+
+    let mut draft = DraftTimeline::new();
+    for seconds in [1.0, 1.01] {
+        draft
+            .add_event(TimelineEvent::movement(TimeStamp::new(seconds), IVec2::X))
+            .unwrap();
+    }
+    let mut manager = TimelineManager::new();
+    manager.set_timeline(ArenaName::Labyrinth, PublishTimeline::from_draft(draft));
+    let origin = IVec2::new(10, 10);
+    let ghost = app
+        .world_mut()
+        .spawn((
+            Ghost,
+            ReplayOrigin {
+                arena: ArenaName::Labyrinth,
+                tile: origin,
+            },
+            manager,
+            GridPositionComponent(origin),
+            Health::new(100.0),
+            ChildOf(labyrinth),
+        ))
+        .id();
+
+    // One frame covers both steps
+    let tick = |app: &mut App, seconds: f32| {
+        app.world_mut()
+            .get_mut::<TimelineClock>(labyrinth)
+            .unwrap()
+            .tick(Duration::from_secs_f32(seconds));
+        app.update();
+        app.world().get::<GridPositionComponent>(ghost).unwrap().0
+    };
+    assert_eq!(tick(&mut app, 1.5), origin + IVec2::X);
+    assert_eq!(tick(&mut app, 0.05), origin + IVec2::new(2, 0));
+    assert_eq!(tick(&mut app, 0.05), origin + IVec2::new(2, 0));
+}
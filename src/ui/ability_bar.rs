@@ -0,0 +1,178 @@
+// Standard library and external crates
+use bevy::prelude::*;
+
+// Local crate modules
+use crate::ability::{AbilityCooldowns, AbilityType};
+use crate::character::Character;
+use crate::class_type::ClassType;
+use crate::controls::{Action, InputBindings};
+use crate::selectors::Active;
+use crate::ui::UiFonts;
+
+const SLOT_SIZE: f32 = 64.0;
+const SLOT_COLOR: Color = Color::srgba(0.05, 0.05, 0.08, 0.85);
+const SWEEP_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+
+/// Bottom-centre ability bar; remembers which hero its slots were built for
+#[derive(Component, Default)]
+pub struct AbilityBar {
+    pub hero: Option<Entity>,
+}
+
+/// Darkened overlay shrinking as the slot's ability comes off cooldown
+#[derive(Component)]
+pub struct CooldownSweep(pub AbilityType);
+
+/// Seconds-remaining readout on a slot
+#[derive(Component)]
+pub struct CooldownText(pub AbilityType);
+
+/// Short display name for an ability slot
+#[must_use]
+pub fn ability_label(ability: AbilityType) -> String {
+    match ability {
+        AbilityType::Hunter(ability) => format!("{ability:?}"),
+        AbilityType::Cardinal(ability) => format!("{ability:?}"),
+        AbilityType::Alchemist(ability) => format!("{ability:?}"),
+        AbilityType::Bard(ability) => format!("{ability:?}"),
+        AbilityType::Forager(ability) => format!("{ability:?}"),
+        AbilityType::Merchant(ability) => format!("{ability:?}"),
+        AbilityType::Thief(ability) => format!("{ability:?}"),
+        AbilityType::Warrior(ability) => format!("{ability:?}"),
+        AbilityType::GuildMaster(ability) => format!("{ability:?}"),
+    }
+}
+
+pub fn setup_ability_bar(mut commands: Commands) {
+    commands.spawn((
+        AbilityBar::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(16.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        },
+    ));
+}
+
+/// The hero the player is controlling, with its class
+type ActiveClassQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static ClassType), (With<Character>, With<Active>)>;
+
+/// Rebuilds the ability slots whenever a different hero becomes active
+pub fn rebuild_ability_bar(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    bindings: Res<InputBindings>,
+    mut bar_q: Query<(Entity, &mut AbilityBar)>,
+    active_q: ActiveClassQuery,
+) {
+    let Ok((bar_entity, mut bar)) = bar_q.single_mut() else {
+        return;
+    };
+    let active = active_q.single().ok();
+    if bar.hero == active.map(|(entity, _)| entity) {
+        return;
+    }
+    bar.hero = active.map(|(entity, _)| entity);
+
+    commands.entity(bar_entity).despawn_related::<Children>();
+    let Some((_, class)) = active else {
+        return;
+    };
+
+    let icon = asset_server.load(class.icon_path(false));
+    commands.entity(bar_entity).with_children(|parent| {
+        // Slots share the class icon, so each is captioned with the key that casts it
+        for (&ability, action) in class.abilities().iter().zip(Action::ABILITY_SLOTS) {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(SLOT_SIZE),
+                        height: Val::Px(SLOT_SIZE),
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::SpaceBetween,
+                        padding: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(SLOT_COLOR),
+                    ImageNode::new(icon.clone()),
+                ))
+                .with_children(|slot| {
+                    slot.spawn((
+                        Text::new(bindings.label(action)),
+                        TextFont {
+                            font: fonts.bold.clone(),
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    slot.spawn((
+                        Text::new(ability_label(ability)),
+                        TextFont {
+                            font: fonts.regular.clone(),
+                            font_size: 9.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    slot.spawn((
+                        CooldownSweep(ability),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(0.0),
+                            bottom: Val::Px(0.0),
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(0.0),
+                            ..default()
+                        },
+                        BackgroundColor(SWEEP_COLOR),
+                    ));
+                    slot.spawn((
+                        CooldownText(ability),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(22.0),
+                            width: Val::Percent(100.0),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        Text::new(""),
+                        TextFont {
+                            font: fonts.display.clone(),
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        }
+    });
+}
+
+/// Sweeps each slot's overlay down as its cooldown runs out
+pub fn update_cooldown_sweeps(
+    active_q: Query<&AbilityCooldowns, (With<Character>, With<Active>)>,
+    mut sweep_q: Query<(&CooldownSweep, &mut Node)>,
+    mut text_q: Query<(&CooldownText, &mut Text)>,
+) {
+    let Ok(cooldowns) = active_q.single() else {
+        return;
+    };
+
+    for (sweep, mut node) in sweep_q.iter_mut() {
+        node.height = Val::Percent(cooldowns.fraction_remaining(sweep.0) * 100.0);
+    }
+    for (cooldown_text, mut text) in text_q.iter_mut() {
+        let remaining = cooldowns.remaining(cooldown_text.0);
+        text.0 = if remaining.is_zero() {
+            String::new()
+        } else {
+            format!("{:.0}", remaining.as_secs_f32().ceil())
+        };
+    }
+}
@@ -0,0 +1,149 @@
+// Standard library and external crates
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use std::ops::Range;
+
+// Local crate modules
+use crate::arena::{ArenaName, CurrentArena};
+use crate::combat_log::CombatLog;
+use crate::controls::{Action, ActionState};
+use crate::ui::UiFonts;
+
+const PANEL_COLOR: Color = Color::srgba(0.05, 0.05, 0.08, 0.85);
+const LOG_TEXT_COLOR: Color = Color::srgb(0.85, 0.85, 0.9);
+/// Pixels scrolled per mouse-wheel line
+const SCROLL_LINE_HEIGHT: f32 = 16.0;
+/// Log entries the window shows at once
+const VISIBLE_LINES: usize = 30;
+
+/// Scrollable window onto the current arena's combat log, hidden until toggled
+#[derive(Component)]
+pub struct CombatLogPanel;
+
+/// How many entries the window is scrolled back from the newest - 0 follows the log
+#[derive(Component, Debug, Default)]
+pub struct CombatLogScroll(pub usize);
+
+#[derive(Component)]
+pub struct CombatLogText;
+
+/// Entries in view when the window is scrolled `back` entries from the newest of `len`
+#[must_use]
+pub fn combat_log_window(len: usize, back: usize) -> Range<usize> {
+    let end = len.saturating_sub(back).max(len.min(VISIBLE_LINES));
+    end.saturating_sub(VISIBLE_LINES)..end
+}
+
+/// Text shown in the combat log window for an arena's entries in view, oldest first
+#[must_use]
+pub fn combat_log_lines(log: &CombatLog, arena: ArenaName, window: Range<usize>) -> String {
+    let entries = log.entries(arena);
+    if entries.is_empty() {
+        return format!("No combat in {arena} yet");
+    }
+    entries[window]
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn setup_combat_log_panel(mut commands: Commands, fonts: Res<UiFonts>) {
+    commands
+        .spawn((
+            CombatLogPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(16.0),
+                top: Val::Px(64.0),
+                width: Val::Px(420.0),
+                height: Val::Percent(45.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                flex_direction: FlexDirection::Column,
+                overflow: Overflow::clip_y(),
+                ..default()
+            },
+            CombatLogScroll::default(),
+            BackgroundColor(PANEL_COLOR),
+            BorderRadius::all(Val::Px(4.0)),
+            Visibility::Hidden,
+        ))
+        .with_child((
+            CombatLogText,
+            Text::new(""),
+            TextFont {
+                font: fonts.regular.clone(),
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(LOG_TEXT_COLOR),
+        ));
+}
+
+pub fn toggle_combat_log_panel(
+    actions: Res<ActionState>,
+    mut panel_q: Query<&mut Visibility, With<CombatLogPanel>>,
+) {
+    if !actions.just_pressed(Action::ToggleCombatLog) {
+        return;
+    }
+    for mut visibility in panel_q.iter_mut() {
+        *visibility = if *visibility == Visibility::Hidden {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Rewrites the log text while the window is open, only when the entries in view
+/// change - the log is append-only, so the same arena and range show the same lines
+pub fn update_combat_log_text(
+    log: Res<CombatLog>,
+    current: Res<CurrentArena>,
+    panel_q: Query<(&Visibility, &CombatLogScroll), With<CombatLogPanel>>,
+    mut text_q: Query<&mut Text, With<CombatLogText>>,
+    mut shown: Local<Option<(ArenaName, Range<usize>)>>,
+) {
+    let Ok((visibility, scroll)) = panel_q.single() else {
+        return;
+    };
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+    let window = combat_log_window(log.entries(current.0).len(), scroll.0);
+    let view = Some((current.0, window.clone()));
+    if *shown == view {
+        return;
+    }
+    let lines = combat_log_lines(&log, current.0, window);
+    for mut text in text_q.iter_mut() {
+        text.0.clone_from(&lines);
+    }
+    *shown = view;
+}
+
+/// Scrolls the open log window back through older entries with the mouse wheel
+pub fn scroll_combat_log(
+    mut wheel_events: EventReader<MouseWheel>,
+    log: Res<CombatLog>,
+    current: Res<CurrentArena>,
+    mut panel_q: Query<(&mut CombatLogScroll, &Visibility), With<CombatLogPanel>>,
+) {
+    for event in wheel_events.read() {
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / SCROLL_LINE_HEIGHT,
+        }
+        .round() as isize;
+        if lines == 0 {
+            continue;
+        }
+        let oldest = log.entries(current.0).len().saturating_sub(VISIBLE_LINES);
+        for (mut scroll, visibility) in panel_q.iter_mut() {
+            if *visibility != Visibility::Hidden {
+                scroll.0 = scroll.0.saturating_add_signed(lines).min(oldest);
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Standard library and external crates
+use bevy::prelude::*;
+
+// Local crate modules
+use crate::arena::{Arena, ArenaName, CurrentArena, CurrentArenaEntity};
+use crate::character::{Character, Ghost};
+use crate::controls::{Action, InputBindings};
+use crate::recording::{
+    AcceptRecordingPrompt, CountdownDisplayed, GlobalPauseReason, GlobalRecordingMode,
+};
+use crate::timeline::{EventType, TimeStamp, TimelineClock, TimelineManager};
+use crate::ui::UiFonts;
+
+const PANEL_COLOR: Color = Color::srgba(0.05, 0.05, 0.08, 0.85);
+const BAR_TRACK_COLOR: Color = Color::srgb(0.2, 0.2, 0.25);
+const BAR_FILL_COLOR: Color = Color::srgb(1.0, 0.557, 0.0);
+const MARKER_COLOR: Color = Color::srgb(1.0, 0.824, 0.0);
+const REC_COLOR: Color = Color::srgb(0.9, 0.1, 0.1);
+const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.3);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.35, 0.42);
+
+/// Height of one ghost's marker lane under the timeline bar
+const MARKER_LANE_HEIGHT: f32 = 6.0;
+
+/// Marker for the filled portion of the timeline bar
+#[derive(Component)]
+pub struct TimelineBarFill;
+
+/// Marker for the arena name and clock readout above the timeline bar
+#[derive(Component)]
+pub struct TimelineLabel;
+
+/// Container holding one lane of ability markers per ghost
+#[derive(Component)]
+pub struct GhostMarkerLanes;
+
+/// Large centred 3-2-1 countdown text
+#[derive(Component)]
+pub struct CountdownText;
+
+/// Red REC badge shown while recording
+#[derive(Component)]
+pub struct RecIndicator;
+
+/// Commit / ghost prompt panel
+#[derive(Component)]
+pub struct RecordingPrompt;
+
+#[derive(Component)]
+pub struct RecordingPromptText;
+
+/// Button accepting the open recording prompt
+#[derive(Component)]
+pub struct AcceptPromptButton;
+
+/// Position of a timestamp along the 0-120s bar, in percent
+#[must_use]
+pub fn timeline_percent(timestamp: TimeStamp) -> f32 {
+    (timestamp.as_secs() / TimeStamp::MAX.as_secs() * 100.0).clamp(0.0, 100.0)
+}
+
+/// Bar positions (percent) of every ability event in a ghost's timeline for an arena
+#[must_use]
+pub fn ghost_ability_markers(manager: &TimelineManager, arena: ArenaName) -> Vec<f32> {
+    manager
+        .get_timeline(arena)
+        .map(|timeline| {
+            timeline
+                .events
+                .iter()
+                .filter(|event| matches!(event.event_type, EventType::Ability(..)))
+                .map(|event| timeline_percent(event.timestamp))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn text_font(font: &Handle<Font>, font_size: f32) -> TextFont {
+    TextFont {
+        font: font.clone(),
+        font_size,
+        ..default()
+    }
+}
+
+/// Spawns the HUD tree: timeline bar, REC badge, countdown and prompt
+pub fn setup_hud(mut commands: Commands, fonts: Res<UiFonts>, bindings: Res<InputBindings>) {
+    // Timeline bar along the top of the screen
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(16.0),
+            left: Val::Percent(20.0),
+            width: Val::Percent(60.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TimelineLabel,
+                Text::new(""),
+                text_font(&fonts.bold, 14.0),
+                TextColor(Color::WHITE),
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(10.0),
+                        ..default()
+                    },
+                    BackgroundColor(BAR_TRACK_COLOR),
+                    BorderRadius::all(Val::Px(5.0)),
+                ))
+                .with_child((
+                    TimelineBarFill,
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(BAR_FILL_COLOR),
+                    BorderRadius::all(Val::Px(5.0)),
+                ));
+            parent.spawn((
+                GhostMarkerLanes,
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    ..default()
+                },
+            ));
+        });
+
+    // REC badge in the top-left corner
+    commands
+        .spawn((
+            RecIndicator,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(16.0),
+                left: Val::Px(16.0),
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                column_gap: Val::Px(6.0),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(PANEL_COLOR),
+            BorderRadius::all(Val::Px(4.0)),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(10.0),
+                    height: Val::Px(10.0),
+                    ..default()
+                },
+                BackgroundColor(REC_COLOR),
+                BorderRadius::MAX,
+            ));
+            parent.spawn((
+                Text::new("REC"),
+                text_font(&fonts.bold, 16.0),
+                TextColor(REC_COLOR),
+            ));
+        });
+
+    // Countdown numerals in the middle of the screen
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_child((
+            CountdownText,
+            Text::new(""),
+            text_font(&fonts.display, 160.0),
+            TextColor(Color::WHITE),
+            Visibility::Hidden,
+        ));
+
+    // Commit / ghost prompt near the bottom of the screen
+    commands
+        .spawn((
+            RecordingPrompt,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(120.0),
+                left: Val::Percent(35.0),
+                width: Val::Percent(30.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(PANEL_COLOR),
+            BorderRadius::all(Val::Px(8.0)),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                RecordingPromptText,
+                Text::new(""),
+                text_font(&fonts.regular, 18.0),
+                TextColor(Color::WHITE),
+            ));
+            parent
+                .spawn((
+                    AcceptPromptButton,
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(20.0), Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(BUTTON_COLOR),
+                    BorderRadius::all(Val::Px(4.0)),
+                ))
+                .with_child((
+                    Text::new(format!("Accept ({})", bindings.label(Action::Accept))),
+                    text_font(&fonts.bold, 16.0),
+                    TextColor(Color::WHITE),
+                ));
+        });
+}
+
+/// Fills the timeline bar to the current arena's clock position
+pub fn update_timeline_bar(
+    current: CurrentArenaEntity,
+    arena_q: Query<(&Arena, &TimelineClock)>,
+    mut fill_q: Query<&mut Node, With<TimelineBarFill>>,
+    mut label_q: Query<&mut Text, With<TimelineLabel>>,
+) {
+    let Ok((arena, clock)) = arena_q.get(current.get()) else {
+        return;
+    };
+    let timestamp = clock.current();
+
+    for mut node in fill_q.iter_mut() {
+        node.width = Val::Percent(timeline_percent(timestamp));
+    }
+    for mut text in label_q.iter_mut() {
+        text.0 = format!(
+            "{}  {:.1}s / {:.0}s",
+            arena.0,
+            timestamp.as_secs(),
+            TimeStamp::MAX.as_secs()
+        );
+    }
+}
+
+/// Rebuilds one lane of ability markers per ghost when the arena or ghosts change
+pub fn rebuild_ghost_markers(
+    mut commands: Commands,
+    current_arena: Res<CurrentArena>,
+    lanes_q: Query<Entity, With<GhostMarkerLanes>>,
+    ghost_q: Query<&TimelineManager, (With<Character>, With<Ghost>)>,
+    changed_q: Query<(), (With<Ghost>, Or<(Added<Ghost>, Changed<TimelineManager>)>)>,
+    mut removed_ghosts: RemovedComponents<Ghost>,
+) {
+    let ghosts_removed = removed_ghosts.read().count() > 0;
+    if !current_arena.is_changed() && changed_q.is_empty() && !ghosts_removed {
+        return;
+    }
+    let Ok(lanes) = lanes_q.single() else {
+        return;
+    };
+
+    commands.entity(lanes).despawn_related::<Children>();
+    for manager in ghost_q.iter() {
+        let markers = ghost_ability_markers(manager, current_arena.0);
+        if markers.is_empty() {
+            continue;
+        }
+        commands.entity(lanes).with_children(|parent| {
+            parent
+                .spawn(Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(MARKER_LANE_HEIGHT),
+                    ..default()
+                })
+                .with_children(|lane| {
+                    for percent in markers {
+                        lane.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Val::Percent(percent),
+                                width: Val::Px(2.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(MARKER_COLOR),
+                        ));
+                    }
+                });
+        });
+    }
+}
+
+/// Shows each countdown number as it comes up and hides the overlay afterwards
+pub fn update_countdown_overlay(
+    recording_mode: Res<GlobalRecordingMode>,
+    mut countdown_events: EventReader<CountdownDisplayed>,
+    mut text_q: Query<(&mut Text, &mut Visibility), With<CountdownText>>,
+) {
+    let Ok((mut text, mut visibility)) = text_q.single_mut() else {
+        return;
+    };
+
+    if let Some(event) = countdown_events.read().last() {
+        text.0 = event.seconds.to_string();
+    }
+    let counting = matches!(*recording_mode, GlobalRecordingMode::Countdown(_));
+    visibility.set_if_neq(if counting {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+}
+
+/// Shows the REC badge while recording
+pub fn update_rec_indicator(
+    recording_mode: Res<GlobalRecordingMode>,
+    mut indicator_q: Query<&mut Visibility, With<RecIndicator>>,
+) {
+    if !recording_mode.is_changed() {
+        return;
+    }
+    let recording = matches!(*recording_mode, GlobalRecordingMode::Recording);
+    for mut visibility in indicator_q.iter_mut() {
+        *visibility = if recording {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Shows the commit or ghost prompt while recording is paused for one
+pub fn update_recording_prompt(
+    recording_mode: Res<GlobalRecordingMode>,
+    mut prompt_q: Query<&mut Visibility, With<RecordingPrompt>>,
+    mut text_q: Query<&mut Text, With<RecordingPromptText>>,
+) {
+    if !recording_mode.is_changed() {
+        return;
+    }
+    let message = match &*recording_mode {
+        GlobalRecordingMode::Paused(GlobalPauseReason::CommitRequested) => {
+            Some("Commit this recording?")
+        }
+        GlobalRecordingMode::Paused(GlobalPauseReason::GhostType) => {
+            Some("This hero is a ghost. Record over its timeline?")
+        }
+        _ => None,
+    };
+
+    for mut visibility in prompt_q.iter_mut() {
+        *visibility = if message.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if let Some(message) = message {
+        for mut text in text_q.iter_mut() {
+            text.0 = message.to_string();
+        }
+    }
+}
+
+/// Accepts the open prompt when its button is clicked
+pub fn handle_prompt_button(
+    mut button_q: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<AcceptPromptButton>),
+    >,
+    mut accept_events: EventWriter<AcceptRecordingPrompt>,
+) {
+    for (interaction, mut background) in button_q.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                accept_events.write(AcceptRecordingPrompt);
+            }
+            Interaction::Hovered => background.0 = BUTTON_HOVER_COLOR,
+            Interaction::None => background.0 = BUTTON_COLOR,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+// Standard library and external crates
+use bevy::prelude::*;
+
+// Local crate modules
+use crate::arena::CurrentArenaEntity;
+use crate::character::{Character, Ghost};
+use crate::controls::{Action, ActionState};
+use crate::guild::HeroId;
+use crate::meters::{ArenaMeters, MeterColumn, MeterOwner, Meters, per_second};
+use crate::timeline::{TimeStamp, TimelineClock};
+use crate::ui::UiFonts;
+
+const PANEL_COLOR: Color = Color::srgba(0.05, 0.05, 0.08, 0.85);
+const HEADER_COLOR: Color = Color::srgb(0.7, 0.7, 0.75);
+const SORTED_HEADER_COLOR: Color = Color::srgb(1.0, 0.557, 0.0);
+
+/// Column the meters panel is sorted by
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MeterSort(pub MeterColumn);
+
+/// Meters window for the current arena, hidden until toggled
+#[derive(Component)]
+pub struct MetersPanel;
+
+/// Clickable column heading that sorts the meters by its column
+#[derive(Component)]
+pub struct MeterSortButton(pub MeterColumn);
+
+/// Column of values in the meters panel, one line per hero
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeterField {
+    Hero,
+    Total(MeterColumn),
+    /// Damage per second the hero managed in the loop before
+    PreviousDps,
+}
+
+impl MeterField {
+    pub const ALL: [Self; 6] = [
+        Self::Hero,
+        Self::Total(MeterColumn::Damage),
+        Self::Total(MeterColumn::Healing),
+        Self::Total(MeterColumn::DamageTaken),
+        Self::Total(MeterColumn::Threat),
+        Self::PreviousDps,
+    ];
+
+    /// RULE 3 COMPLIANCE: Static data lookup
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Hero => "Hero",
+            Self::Total(column) => column.label(),
+            Self::PreviousDps => "Last DPS",
+        }
+    }
+}
+
+/// One hero's line on the meters panel (value type)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeterRow {
+    pub hero: MeterOwner,
+    pub dps: f32,
+    pub hps: f32,
+    pub dtps: f32,
+    pub threat: f32,
+    /// None when the hero did nothing the loop before
+    pub previous_dps: Option<f32>,
+}
+
+impl MeterRow {
+    /// Cell text for a numeric column
+    #[must_use]
+    pub fn cell(&self, field: MeterField) -> String {
+        match field {
+            MeterField::Hero => match self.hero {
+                MeterOwner::Hero(hero) => format!("hero {}", hero.0),
+                MeterOwner::Ghost(ghost) => format!("ghost {ghost}"),
+            },
+            MeterField::Total(MeterColumn::Damage) => format!("{:.1}", self.dps),
+            MeterField::Total(MeterColumn::Healing) => format!("{:.1}", self.hps),
+            MeterField::Total(MeterColumn::DamageTaken) => format!("{:.1}", self.dtps),
+            MeterField::Total(MeterColumn::Threat) => format!("{:.0}", self.threat),
+            MeterField::PreviousDps => self
+                .previous_dps
+                .map_or_else(|| "-".to_string(), |dps| format!("{dps:.1}")),
+        }
+    }
+}
+
+/// Rows for the running loop, `elapsed` seconds in, sorted by `sort`; rates for the
+/// previous loop cover the whole loop
+#[must_use]
+pub fn meter_rows(meters: &ArenaMeters, sort: MeterColumn, elapsed: TimeStamp) -> Vec<MeterRow> {
+    meters
+        .current
+        .sorted_by(sort)
+        .into_iter()
+        .map(|(hero, meter)| MeterRow {
+            hero,
+            dps: per_second(meter.damage, elapsed),
+            hps: per_second(meter.healing, elapsed),
+            dtps: per_second(meter.damage_taken, elapsed),
+            threat: meter.threat,
+            previous_dps: meters
+                .previous()
+                .and_then(|previous| previous.hero(hero))
+                .map(|previous| per_second(previous.damage, TimeStamp::MAX)),
+        })
+        .collect()
+}
+
+pub fn setup_meters_panel(mut commands: Commands, fonts: Res<UiFonts>) {
+    commands
+        .spawn((
+            MetersPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(16.0),
+                bottom: Val::Px(96.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                column_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(PANEL_COLOR),
+            BorderRadius::all(Val::Px(4.0)),
+            Visibility::Hidden,
+        ))
+        .with_children(|panel| {
+            for field in MeterField::ALL {
+                panel
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    })
+                    .with_children(|column| {
+                        let heading = (
+                            Text::new(field.label()),
+                            TextFont {
+                                font: fonts.bold.clone(),
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(HEADER_COLOR),
+                        );
+                        if let MeterField::Total(sort) = field {
+                            column.spawn((MeterSortButton(sort), Button, heading));
+                        } else {
+                            column.spawn(heading);
+                        }
+                        column.spawn((
+                            field,
+                            Text::new(""),
+                            TextFont {
+                                font: fonts.regular.clone(),
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+        });
+}
+
+pub fn toggle_meters_panel(
+    actions: Res<ActionState>,
+    mut panel_q: Query<&mut Visibility, With<MetersPanel>>,
+) {
+    if !actions.just_pressed(Action::ToggleMeters) {
+        return;
+    }
+    for mut visibility in panel_q.iter_mut() {
+        *visibility = if *visibility == Visibility::Hidden {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Clicking a column heading sorts the meters by it and highlights it
+pub fn select_meter_sort(
+    mut sort: ResMut<MeterSort>,
+    mut button_q: Query<(&MeterSortButton, &Interaction, &mut TextColor)>,
+) {
+    if let Some((button, ..)) = button_q
+        .iter()
+        .find(|(_, interaction, _)| **interaction == Interaction::Pressed)
+    {
+        sort.set_if_neq(MeterSort(button.0));
+    }
+    for (button, _, mut color) in button_q.iter_mut() {
+        color.set_if_neq(TextColor(if button.0 == sort.0 {
+            SORTED_HEADER_COLOR
+        } else {
+            HEADER_COLOR
+        }));
+    }
+}
+
+/// Named heroes and ghosts, with what identifies their meter line
+type MeterNameQuery<'w, 's> =
+    Query<'w, 's, (Entity, Option<&'static HeroId>, Has<Ghost>, &'static Name), With<Character>>;
+
+/// Refreshes the open panel with the current arena's running loop whenever the
+/// meters, the sort or the arena change, or the panel is opened
+pub fn update_meters_panel(
+    meters: Res<Meters>,
+    sort: Res<MeterSort>,
+    current: CurrentArenaEntity,
+    clock_q: Query<&TimelineClock>,
+    name_q: MeterNameQuery,
+    panel_q: Query<Ref<Visibility>, With<MetersPanel>>,
+    mut text_q: Query<(&MeterField, &mut Text)>,
+) {
+    let Ok(visibility) = panel_q.single() else {
+        return;
+    };
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+    if !meters.is_changed()
+        && !sort.is_changed()
+        && !current.is_changed()
+        && !visibility.is_changed()
+    {
+        return;
+    }
+    let elapsed = clock_q
+        .get(current.get())
+        .map_or(TimeStamp::ZERO, TimelineClock::current);
+    let rows = meter_rows(meters.arena(current.name()), sort.0, elapsed);
+    for (field, mut text) in text_q.iter_mut() {
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| match field {
+                MeterField::Hero => name_q
+                    .iter()
+                    .find(|(entity, hero, is_ghost, _)| {
+                        MeterOwner::of(*entity, *hero, *is_ghost) == Some(row.hero)
+                    })
+                    .map_or_else(|| row.cell(*field), |(.., name)| name.to_string()),
+                _ => row.cell(*field),
+            })
+            .collect();
+        text.0 = lines.join("\n");
+    }
+}
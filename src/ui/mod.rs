@@ -1 +1,108 @@
+mod ability_bar;
+mod combat_log;
+mod hud;
+mod meters;
+mod overview;
+mod party_frames;
 
+pub use ability_bar::*;
+use bevy::prelude::*;
+pub use combat_log::*;
+pub use hud::*;
+pub use meters::*;
+pub use overview::*;
+pub use party_frames::*;
+
+/// Bundled UI fonts
+#[derive(Resource)]
+pub struct UiFonts {
+    pub regular: Handle<Font>,
+    pub bold: Handle<Font>,
+    /// Migra display face for large numerals and headings
+    pub display: Handle<Font>,
+}
+
+impl UiFonts {
+    pub fn new(asset_server: &Res<AssetServer>) -> Self {
+        Self {
+            regular: asset_server.load("fonts/BeVietnamPro-Regular.ttf"),
+            bold: asset_server.load("fonts/BeVietnamPro-Bold.ttf"),
+            display: asset_server.load("fonts/Migra-Extrabold.ttf"),
+        }
+    }
+}
+
+fn load_ui_fonts(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(UiFonts::new(&asset_server));
+}
+
+/// Plugin for the in-game HUD
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (
+                load_ui_fonts,
+                setup_hud,
+                setup_ability_bar,
+                setup_party_frames,
+                setup_overview,
+                setup_combat_log_panel,
+                setup_meters_panel,
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            (
+                update_timeline_bar,
+                rebuild_ghost_markers,
+                update_countdown_overlay,
+                update_rec_indicator,
+                update_recording_prompt,
+                handle_prompt_button,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                rebuild_ability_bar,
+                update_cooldown_sweeps,
+                rebuild_party_frames,
+                update_party_frames,
+                select_party_frame,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                update_overview_visibility,
+                position_overview_panels,
+                count_overview_layers,
+                update_overview_panels
+                    .after(count_overview_layers)
+                    .run_if(overview_shown),
+                select_overview_panel,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                toggle_combat_log_panel,
+                update_combat_log_text,
+                scroll_combat_log,
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            (toggle_meters_panel, select_meter_sort, update_meters_panel).chain(),
+        )
+        .init_resource::<MeterSort>();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,296 @@
+// Standard library and external crates
+use bevy::prelude::*;
+
+// Local crate modules
+use crate::arena::{
+    ARENA_HEIGHT, ARENA_WIDTH, Arena, ArenaName, CameraUpdate, CurrentArena, TILE_SIZE,
+};
+use crate::arena_camera::{CameraRig, ZOOM, ZoomOut, arena_shot};
+use crate::boss::Decoy;
+use crate::character::{Boss, Character};
+use crate::combat::Health;
+use crate::timeline::{TimeStamp, TimelineClock, TimelineManager};
+use crate::ui::UiFonts;
+
+const STATUS_COLOR: Color = Color::srgba(0.05, 0.05, 0.08, 0.8);
+const HOVER_BORDER_COLOR: Color = Color::srgb(0.7, 0.7, 0.75);
+const CURRENT_BORDER_COLOR: Color = Color::srgb(1.0, 0.557, 0.0);
+
+/// Full-screen layer holding the overview panels over the zoomed-out camera
+#[derive(Component)]
+pub struct OverviewGrid;
+
+/// Clickable panel laid over one arena's on-screen footprint in the overview
+#[derive(Component)]
+pub struct OverviewPanel(pub ArenaName);
+
+/// Recorded layers per arena, indexed by arena - recounted only when a hero's
+/// timelines or arena change
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OverviewLayers(pub [usize; 9]);
+
+impl OverviewLayers {
+    #[must_use]
+    pub fn get(&self, arena: ArenaName) -> usize {
+        self.0[arena.as_u8() as usize]
+    }
+}
+
+#[derive(Component)]
+pub struct OverviewPanelText(pub ArenaName);
+
+/// Snapshot of one arena shown on its overview panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArenaStatus {
+    pub arena: ArenaName,
+    pub timestamp: TimeStamp,
+    /// Heroes in the arena carrying a recorded timeline for it
+    pub layers: usize,
+    /// Boss health fraction, None once no boss remains
+    pub boss_health: Option<f32>,
+    pub heroes_alive: usize,
+}
+
+impl ArenaStatus {
+    /// Panel readout, one fact per line
+    #[must_use]
+    pub fn lines(&self) -> String {
+        let boss = self.boss_health.map_or_else(
+            || "Boss defeated".to_string(),
+            |fraction| format!("Boss {:.0}%", fraction * 100.0),
+        );
+        format!(
+            "{}\n{:.1}s / {:.0}s\n{} {}\n{}\n{} {} alive",
+            self.arena,
+            self.timestamp.as_secs(),
+            TimeStamp::MAX.as_secs(),
+            self.layers,
+            if self.layers == 1 { "layer" } else { "layers" },
+            boss,
+            self.heroes_alive,
+            if self.heroes_alive == 1 {
+                "hero"
+            } else {
+                "heroes"
+            },
+        )
+    }
+}
+
+/// Spawns the hidden overview layer with one panel per arena, in arena index order
+///
+/// Panels are placed over their arenas by `position_overview_panels`.
+pub fn setup_overview(mut commands: Commands, fonts: Res<UiFonts>) {
+    commands.init_resource::<OverviewLayers>();
+    commands
+        .spawn((
+            OverviewGrid,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            Visibility::Hidden,
+        ))
+        .with_children(|grid| {
+            for arena in ArenaName::ALL_ARENAS {
+                grid.spawn((
+                    OverviewPanel(arena),
+                    Button,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        padding: UiRect::all(Val::Px(8.0)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        align_items: AlignItems::FlexStart,
+                        ..default()
+                    },
+                    BorderColor(Color::NONE),
+                ))
+                .with_children(|panel| {
+                    panel
+                        .spawn((
+                            Node {
+                                padding: UiRect::all(Val::Px(6.0)),
+                                ..default()
+                            },
+                            BackgroundColor(STATUS_COLOR),
+                            BorderRadius::all(Val::Px(4.0)),
+                        ))
+                        .with_child((
+                            OverviewPanelText(arena),
+                            Text::new(arena.to_string()),
+                            TextFont {
+                                font: fonts.bold.clone(),
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                });
+            }
+        });
+}
+
+/// Shows the overview grid only while the camera is zoomed out
+pub fn update_overview_visibility(
+    camera_q: Query<Has<ZoomOut>, With<Camera3d>>,
+    mut grid_q: Query<&mut Visibility, With<OverviewGrid>>,
+) {
+    let zoomed_out = camera_q.iter().any(|zoom_out| zoom_out);
+    for mut visibility in grid_q.iter_mut() {
+        visibility.set_if_neq(if zoomed_out {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+/// The camera while it is zoomed out over the whole battleground
+type OverviewCameraQuery<'w, 's> =
+    Query<'w, 's, (&'static Camera, &'static GlobalTransform), (With<Camera3d>, With<ZoomOut>)>;
+
+/// Places each panel over its arena's footprint as the zoomed-out camera sees it
+pub fn position_overview_panels(
+    camera_q: OverviewCameraQuery,
+    arena_q: Query<(&Arena, &GlobalTransform)>,
+    mut panel_q: Query<(&OverviewPanel, &mut Node)>,
+) {
+    let Ok((camera, camera_transform)) = camera_q.single() else {
+        return;
+    };
+    // Tiles are centred on their grid coordinates, so the arena's edges sit half a
+    // tile outside the first and last tile centres
+    let top_left = Vec3::new(-TILE_SIZE / 2.0, ARENA_HEIGHT - TILE_SIZE / 2.0, 0.0);
+    let bottom_right = Vec3::new(ARENA_WIDTH - TILE_SIZE / 2.0, -TILE_SIZE / 2.0, 0.0);
+
+    for (arena, arena_transform) in arena_q.iter() {
+        let (Ok(min), Ok(max)) = (
+            camera.world_to_viewport(camera_transform, arena_transform.transform_point(top_left)),
+            camera.world_to_viewport(
+                camera_transform,
+                arena_transform.transform_point(bottom_right),
+            ),
+        ) else {
+            continue;
+        };
+        for (_, mut node) in panel_q.iter_mut().filter(|(panel, _)| panel.0 == arena.0) {
+            node.left = Val::Px(min.x);
+            node.top = Val::Px(min.y);
+            node.width = Val::Px(max.x - min.x);
+            node.height = Val::Px(max.y - min.y);
+        }
+    }
+}
+
+/// Heroes whose timelines or arena changed since the last count
+type ChangedLayerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (),
+    (
+        With<Character>,
+        Or<(Changed<TimelineManager>, Changed<ChildOf>)>,
+    ),
+>;
+
+/// Recounts each arena's recorded layers when a hero's timelines or arena change
+pub fn count_overview_layers(
+    changed_q: ChangedLayerQuery,
+    mut removed: RemovedComponents<TimelineManager>,
+    arena_q: Query<&Arena>,
+    hero_q: Query<(&TimelineManager, &ChildOf), With<Character>>,
+    mut layers: ResMut<OverviewLayers>,
+) {
+    if changed_q.is_empty() && removed.read().count() == 0 {
+        return;
+    }
+
+    let mut counts = [0; 9];
+    for (manager, child_of) in hero_q.iter() {
+        if let Ok(arena) = arena_q.get(child_of.parent())
+            && manager.has_recording_for(arena.0)
+        {
+            counts[arena.0.as_u8() as usize] += 1;
+        }
+    }
+    layers.set_if_neq(OverviewLayers(counts));
+}
+
+/// Run condition that checks if the overview grid is showing
+pub fn overview_shown(grid_q: Query<&Visibility, With<OverviewGrid>>) -> bool {
+    grid_q
+        .iter()
+        .any(|visibility| *visibility != Visibility::Hidden)
+}
+
+/// Refreshes every panel's readout and highlights the current arena
+pub fn update_overview_panels(
+    current_arena: Res<CurrentArena>,
+    layers: Res<OverviewLayers>,
+    arena_q: Query<(&Arena, &TimelineClock, &Children)>,
+    boss_q: Query<&Health, (With<Boss>, Without<Decoy>)>,
+    hero_q: Query<&Health, With<Character>>,
+    mut panel_q: Query<(&OverviewPanel, &Interaction, &mut BorderColor)>,
+    mut text_q: Query<(&OverviewPanelText, &mut Text)>,
+) {
+    let mut statuses = Vec::with_capacity(ArenaName::ALL_ARENAS.len());
+    for (arena, clock, children) in arena_q.iter() {
+        let boss_health = boss_q
+            .iter_many(children)
+            .find(|health| !health.is_dead())
+            .map(Health::fraction);
+        let heroes_alive = hero_q
+            .iter_many(children)
+            .filter(|health| !health.is_dead())
+            .count();
+        statuses.push(ArenaStatus {
+            arena: arena.0,
+            timestamp: clock.current(),
+            layers: layers.get(arena.0),
+            boss_health,
+            heroes_alive,
+        });
+    }
+
+    for (text_marker, mut text) in text_q.iter_mut() {
+        if let Some(status) = statuses.iter().find(|status| status.arena == text_marker.0) {
+            text.0 = status.lines();
+        }
+    }
+    for (panel, interaction, mut border) in panel_q.iter_mut() {
+        border.0 = if panel.0 == current_arena.0 {
+            CURRENT_BORDER_COLOR
+        } else if *interaction == Interaction::Hovered {
+            HOVER_BORDER_COLOR
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+/// Clicking a panel makes that arena current and glides the camera into it
+pub fn select_overview_panel(
+    mut commands: Commands,
+    mut current_arena: ResMut<CurrentArena>,
+    panel_q: Query<(&Interaction, &OverviewPanel), Changed<Interaction>>,
+    mut camera_q: Query<(Entity, &Transform, &mut CameraRig), (With<Camera3d>, With<ZoomOut>)>,
+    mut arena_refresh_event: EventWriter<CameraUpdate>,
+) {
+    let Ok((camera_entity, camera_transform, mut rig)) = camera_q.single_mut() else {
+        return;
+    };
+    let Some(panel) = panel_q
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, panel)| panel)
+    else {
+        return;
+    };
+
+    current_arena.0 = panel.0;
+    rig.move_to(camera_transform, arena_shot(panel.0.as_u8(), ZOOM.0));
+    commands.entity(camera_entity).remove::<ZoomOut>();
+    arena_refresh_event.write(CameraUpdate);
+}
@@ -0,0 +1,247 @@
+// Standard library and external crates
+use bevy::prelude::*;
+
+// Local crate modules
+use crate::arena::CurrentArenaEntity;
+use crate::character::{Character, Ghost, set_active_character};
+use crate::class_type::ClassType;
+use crate::combat::Health;
+use crate::materials::Materials;
+use crate::selectors::Active;
+use crate::ui::UiFonts;
+
+const FRAME_COLOR: Color = Color::srgba(0.05, 0.05, 0.08, 0.85);
+const ACTIVE_FRAME_COLOR: Color = Color::srgba(0.15, 0.25, 0.5, 0.9);
+const HEALTH_TRACK_COLOR: Color = Color::srgb(0.25, 0.05, 0.05);
+const HEALTH_FILL_COLOR: Color = Color::srgb(0.2, 0.8, 0.3);
+const GHOST_TEXT_COLOR: Color = Color::srgb(0.7, 0.8, 1.0);
+
+/// Left-hand column of party frames; remembers which heroes it was built for
+#[derive(Component, Default)]
+pub struct PartyFrames {
+    pub heroes: Vec<Entity>,
+}
+
+/// Clickable frame for one hero
+#[derive(Component)]
+pub struct PartyFrame(pub Entity);
+
+#[derive(Component)]
+pub struct PartyFrameHealth(pub Entity);
+
+#[derive(Component)]
+pub struct PartyFrameIcon {
+    pub hero: Entity,
+    pub selected: bool,
+}
+
+#[derive(Component)]
+pub struct PartyFrameGhostTag(pub Entity);
+
+pub fn setup_party_frames(mut commands: Commands) {
+    commands.spawn((
+        PartyFrames::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.0),
+            top: Val::Px(64.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+    ));
+}
+
+/// Rebuilds the frames when the set of heroes in the current arena changes
+pub fn rebuild_party_frames(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    current: CurrentArenaEntity,
+    mut frames_q: Query<(Entity, &mut PartyFrames)>,
+    arena_children_q: Query<&Children>,
+    character_q: Query<(&ClassType, Option<&Name>), With<Character>>,
+) {
+    let Ok((frames_entity, mut frames)) = frames_q.single_mut() else {
+        return;
+    };
+    let heroes: Vec<Entity> = arena_children_q
+        .get(current.get())
+        .map(|children| {
+            children
+                .iter()
+                .filter(|child| character_q.contains(*child))
+                .collect()
+        })
+        .unwrap_or_default();
+    if frames.heroes == heroes {
+        return;
+    }
+    frames.heroes = heroes.clone();
+
+    commands.entity(frames_entity).despawn_related::<Children>();
+    commands.entity(frames_entity).with_children(|parent| {
+        for hero in heroes {
+            let Ok((class, name)) = character_q.get(hero) else {
+                continue;
+            };
+            let label = name.map_or_else(|| class.name(), |name| name.to_string());
+            parent
+                .spawn((
+                    PartyFrame(hero),
+                    Button,
+                    Node {
+                        width: Val::Px(200.0),
+                        padding: UiRect::all(Val::Px(6.0)),
+                        column_gap: Val::Px(8.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(FRAME_COLOR),
+                    BorderRadius::all(Val::Px(4.0)),
+                ))
+                .with_children(|frame| {
+                    frame.spawn((
+                        PartyFrameIcon {
+                            hero,
+                            selected: false,
+                        },
+                        Node {
+                            width: Val::Px(32.0),
+                            height: Val::Px(32.0),
+                            ..default()
+                        },
+                        ImageNode::new(asset_server.load(class.icon_path(false))),
+                    ));
+                    frame
+                        .spawn(Node {
+                            flex_grow: 1.0,
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(3.0),
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column
+                                .spawn(Node {
+                                    justify_content: JustifyContent::SpaceBetween,
+                                    ..default()
+                                })
+                                .with_children(|row| {
+                                    row.spawn((
+                                        Text::new(label),
+                                        TextFont {
+                                            font: fonts.bold.clone(),
+                                            font_size: 13.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::WHITE),
+                                    ));
+                                    row.spawn((
+                                        PartyFrameGhostTag(hero),
+                                        Text::new("GHOST"),
+                                        TextFont {
+                                            font: fonts.regular.clone(),
+                                            font_size: 11.0,
+                                            ..default()
+                                        },
+                                        TextColor(GHOST_TEXT_COLOR),
+                                        Visibility::Hidden,
+                                    ));
+                                });
+                            column
+                                .spawn((
+                                    Node {
+                                        width: Val::Percent(100.0),
+                                        height: Val::Px(6.0),
+                                        ..default()
+                                    },
+                                    BackgroundColor(HEALTH_TRACK_COLOR),
+                                ))
+                                .with_child((
+                                    PartyFrameHealth(hero),
+                                    Node {
+                                        width: Val::Percent(100.0),
+                                        height: Val::Percent(100.0),
+                                        ..default()
+                                    },
+                                    BackgroundColor(HEALTH_FILL_COLOR),
+                                ));
+                        });
+                });
+        }
+    });
+}
+
+/// Heroes with what their party frame shows
+type PartyMemberQuery<'w, 's> =
+    Query<'w, 's, (&'static ClassType, &'static Health, Has<Ghost>, Has<Active>), With<Character>>;
+
+/// Keeps health bars, ghost tags and the active highlight current
+pub fn update_party_frames(
+    asset_server: Res<AssetServer>,
+    character_q: PartyMemberQuery,
+    mut frame_q: Query<(&PartyFrame, &mut BackgroundColor)>,
+    mut health_q: Query<(&PartyFrameHealth, &mut Node)>,
+    mut icon_q: Query<(&mut PartyFrameIcon, &mut ImageNode)>,
+    mut ghost_q: Query<(&PartyFrameGhostTag, &mut Visibility)>,
+) {
+    for (frame, mut background) in frame_q.iter_mut() {
+        if let Ok((_, _, _, is_active)) = character_q.get(frame.0) {
+            background.0 = if is_active {
+                ACTIVE_FRAME_COLOR
+            } else {
+                FRAME_COLOR
+            };
+        }
+    }
+    for (health_bar, mut node) in health_q.iter_mut() {
+        if let Ok((_, health, _, _)) = character_q.get(health_bar.0) {
+            node.width = Val::Percent(health.fraction() * 100.0);
+        }
+    }
+    for (mut icon, mut image) in icon_q.iter_mut() {
+        if let Ok((class, _, _, is_active)) = character_q.get(icon.hero)
+            && icon.selected != is_active
+        {
+            icon.selected = is_active;
+            image.image = asset_server.load(class.icon_path(is_active));
+        }
+    }
+    for (tag, mut visibility) in ghost_q.iter_mut() {
+        if let Ok((_, _, is_ghost, _)) = character_q.get(tag.0) {
+            visibility.set_if_neq(if is_ghost {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+        }
+    }
+}
+
+/// The hero the player is controlling, with its arena
+type ActiveHeroQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static ChildOf), (With<Character>, With<Active>)>;
+
+/// Clicking a frame makes that hero the active character
+pub fn select_party_frame(
+    mut commands: Commands,
+    mats: Res<Materials>,
+    current: CurrentArenaEntity,
+    frame_q: Query<(&Interaction, &PartyFrame), Changed<Interaction>>,
+    active_q: ActiveHeroQuery,
+) {
+    let arena_entity = current.get();
+    for (interaction, frame) in frame_q.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let previous = active_q
+            .iter()
+            .find(|(_, child_of)| child_of.parent() == arena_entity)
+            .map(|(entity, _)| entity);
+        if previous == Some(frame.0) {
+            continue;
+        }
+        set_active_character(&mut commands, &mats, arena_entity, previous, frame.0);
+    }
+}
@@ -0,0 +1,345 @@
+use super::*;
+use crate::ability::{
+    AbilityCast, AbilityCooldowns, AbilityType, CardinalAbility, cast_slotted_abilities,
+};
+use crate::arena::{Arena, ArenaEntities, ArenaName, CameraUpdate, CurrentArena};
+use crate::arena_camera::{CameraRig, ZOOM, ZoomOut, arena_shot};
+use crate::character::Character;
+use crate::class_type::ClassType;
+use crate::controls::{Action, ActionState};
+use crate::guild::HeroId;
+use crate::materials::Materials;
+use crate::meters::{MeterColumn, MeterOwner, Meters};
+use crate::recording::{GlobalPauseReason, GlobalRecordingMode, RecordingPlugin};
+use crate::selectors::Active;
+use crate::timeline::{
+    DraftTimeline, EventType, GlobalTimelinePause, PublishTimeline, TimeStamp, TimelineEvent,
+    TimelineManager,
+};
+use bevy::app::{App, Update};
+use bevy::asset::{AssetApp, Assets};
+use bevy::ecs::system::RunSystemOnce;
+use bevy::pbr::StandardMaterial;
+
+#[test]
+fn test_timeline_percent_spans_the_two_minute_loop() {
+    assert_eq!(timeline_percent(TimeStamp::new(0.0)), 0.0);
+    assert_eq!(timeline_percent(TimeStamp::new(60.0)), 50.0);
+    assert_eq!(timeline_percent(TimeStamp::MAX), 100.0);
+}
+
+#[test]
+fn test_ghost_markers_include_only_ability_events_for_the_arena() {
+    let mut draft = DraftTimeline::new();
+    draft
+        .add_event(TimelineEvent::movement(TimeStamp::new(3.0), IVec2::X))
+        .unwrap();
+    draft
+        .add_event(TimelineEvent {
+            timestamp: TimeStamp::new(30.0),
+            event_type: EventType::Ability(AbilityType::Cardinal(CardinalAbility::HolyNova), None),
+        })
+        .unwrap();
+    let mut manager = TimelineManager::new();
+    manager.set_timeline(ArenaName::Sanctum, PublishTimeline::from_draft(draft));
+
+    assert_eq!(
+        ghost_ability_markers(&manager, ArenaName::Sanctum),
+        vec![25.0]
+    );
+    assert!(ghost_ability_markers(&manager, ArenaName::Gala).is_empty());
+}
+
+#[test]
+fn test_clicking_the_prompt_button_accepts_the_ghost_prompt() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.init_resource::<ActionState>();
+    app.add_plugins(RecordingPlugin);
+    app.add_systems(Update, handle_prompt_button);
+    app.insert_resource(GlobalRecordingMode::Paused(GlobalPauseReason::GhostType));
+
+    app.world_mut().spawn((
+        AcceptPromptButton,
+        Interaction::Pressed,
+        BackgroundColor(Color::BLACK),
+    ));
+    app.update();
+    app.update();
+
+    assert!(matches!(
+        *app.world().resource::<GlobalRecordingMode>(),
+        GlobalRecordingMode::Countdown(_)
+    ));
+}
+
+#[test]
+fn test_cooldown_sweep_fraction_runs_down_to_ready() {
+    let nova = AbilityType::Cardinal(CardinalAbility::HolyNova);
+    let mut cooldowns = AbilityCooldowns::default();
+    cooldowns.start(nova);
+    assert!(!cooldowns.is_ready(nova));
+    assert_eq!(cooldowns.fraction_remaining(nova), 1.0);
+
+    cooldowns.tick(nova.cooldown() / 2);
+    assert_eq!(cooldowns.fraction_remaining(nova), 0.5);
+
+    cooldowns.tick(nova.cooldown());
+    assert!(cooldowns.is_ready(nova));
+    assert!(cooldowns.0.is_empty());
+}
+
+#[test]
+fn test_each_ability_key_sweeps_its_own_slot() {
+    let mut app = App::new();
+    app.add_event::<AbilityCast>()
+        .init_resource::<ActionState>()
+        .init_resource::<DraftTimeline>()
+        .init_resource::<GlobalTimelinePause>()
+        .add_systems(
+            Update,
+            (cast_slotted_abilities, update_cooldown_sweeps).chain(),
+        );
+    let world = app.world_mut();
+    let arena = world.spawn(Arena(ArenaName::Labyrinth)).id();
+    world.insert_resource(ArenaEntities::new(
+        ArenaName::ALL_ARENAS.map(|name| (name, arena)),
+    ));
+    world.insert_resource(CurrentArena(ArenaName::Labyrinth));
+    world.spawn((Character, ClassType::Cardinal, Active, ChildOf(arena)));
+    let sweeps = ClassType::Cardinal
+        .abilities()
+        .iter()
+        .map(|&ability| world.spawn((CooldownSweep(ability), Node::default())).id())
+        .collect::<Vec<_>>();
+
+    for action in &Action::ABILITY_SLOTS[1..] {
+        app.world_mut()
+            .resource_mut::<ActionState>()
+            .set(*action, true);
+    }
+    app.update();
+
+    let heights = sweeps
+        .iter()
+        .map(|&sweep| app.world().get::<Node>(sweep).unwrap().height)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        heights,
+        [
+            Val::Percent(0.0),
+            Val::Percent(100.0),
+            Val::Percent(100.0),
+            Val::Percent(100.0)
+        ]
+    );
+}
+
+#[test]
+fn test_every_hero_class_fills_the_four_slot_bar() {
+    for class in [
+        ClassType::Alchemist,
+        ClassType::Bard,
+        ClassType::Cardinal,
+        ClassType::Forager,
+        ClassType::GuildMaster,
+        ClassType::Hunter,
+        ClassType::Merchant,
+        ClassType::Thief,
+        ClassType::Warrior,
+    ] {
+        assert_eq!(class.abilities().len(), 4, "{class:?}");
+    }
+}
+
+#[test]
+fn test_clicking_a_party_frame_selects_that_hero() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.add_plugins(bevy::asset::AssetPlugin::default());
+    app.init_asset::<StandardMaterial>();
+    app.world_mut()
+        .run_system_once(
+            |mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>| {
+                let test_material = materials.add(StandardMaterial::default());
+                commands.insert_resource(Materials {
+                    blue: test_material.clone(),
+                    gray: test_material.clone(),
+                    red: test_material.clone(),
+                    black: test_material.clone(),
+                    yellow: test_material.clone(),
+                });
+            },
+        )
+        .expect("Failed to setup test materials");
+    app.add_systems(Update, select_party_frame);
+
+    let world = app.world_mut();
+    let arena = world.spawn(Arena(ArenaName::Labyrinth)).id();
+    world.insert_resource(ArenaEntities::new(
+        ArenaName::ALL_ARENAS.map(|name| (name, arena)),
+    ));
+    world.insert_resource(CurrentArena(ArenaName::Labyrinth));
+    let warrior = world
+        .spawn((Character, ClassType::Warrior, Active, ChildOf(arena)))
+        .id();
+    let hunter = world
+        .spawn((Character, ClassType::Hunter, ChildOf(arena)))
+        .id();
+    world.spawn((PartyFrame(hunter), Interaction::Pressed));
+    app.update();
+
+    assert!(app.world().get::<Active>(hunter).is_some());
+    assert!(app.world().get::<Active>(warrior).is_none());
+}
+
+#[test]
+fn test_arena_status_lists_clock_layers_boss_and_heroes() {
+    let status = ArenaStatus {
+        arena: ArenaName::Casino,
+        timestamp: TimeStamp::new(42.25),
+        layers: 1,
+        boss_health: Some(0.75),
+        heroes_alive: 3,
+    };
+    assert_eq!(
+        status.lines(),
+        "Casino\n42.2s / 120s\n1 layer\nBoss 75%\n3 heroes alive"
+    );
+
+    let cleared = ArenaStatus {
+        boss_health: None,
+        heroes_alive: 1,
+        layers: 0,
+        ..status
+    };
+    assert!(
+        cleared
+            .lines()
+            .ends_with("0 layers\nBoss defeated\n1 hero alive")
+    );
+}
+
+#[test]
+fn test_overview_layers_are_recounted_when_timelines_change() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.init_resource::<OverviewLayers>();
+    app.add_systems(Update, count_overview_layers);
+    let arenas = crate::arena::create_test_arenas(app.world_mut(), |_| ());
+
+    let recorded = |arena| {
+        let mut manager = TimelineManager::new();
+        manager.set_timeline(arena, PublishTimeline::from_draft(DraftTimeline::new()));
+        manager
+    };
+    let casino = arenas.get(ArenaName::Casino);
+    let world = app.world_mut();
+    world.spawn((Character, recorded(ArenaName::Casino), ChildOf(casino)));
+    world.spawn((Character, recorded(ArenaName::Gala), ChildOf(casino)));
+    let newcomer = world
+        .spawn((Character, TimelineManager::new(), ChildOf(casino)))
+        .id();
+    app.update();
+    assert_eq!(
+        app.world()
+            .resource::<OverviewLayers>()
+            .get(ArenaName::Casino),
+        1,
+        "only recordings for the hero's own arena are layers"
+    );
+
+    app.world_mut()
+        .get_mut::<TimelineManager>(newcomer)
+        .unwrap()
+        .set_timeline(
+            ArenaName::Casino,
+            PublishTimeline::from_draft(DraftTimeline::new()),
+        );
+    app.update();
+    let layers = app.world().resource::<OverviewLayers>();
+    assert_eq!(layers.get(ArenaName::Casino), 2);
+    assert_eq!(layers.get(ArenaName::Gala), 0);
+
+    app.world_mut().despawn(newcomer);
+    app.update();
+    assert_eq!(
+        app.world()
+            .resource::<OverviewLayers>()
+            .get(ArenaName::Casino),
+        1
+    );
+}
+
+#[test]
+fn test_clicking_an_overview_panel_switches_arena_and_zooms_in() {
+    let mut app = App::new();
+    app.add_plugins(bevy::prelude::MinimalPlugins);
+    app.add_event::<CameraUpdate>();
+    app.insert_resource(CurrentArena(ArenaName::GuildHouse));
+    app.add_systems(Update, select_overview_panel);
+
+    let overview = arena_shot(4, ZOOM.1);
+    let camera = app
+        .world_mut()
+        .spawn((Camera3d::default(), ZoomOut, overview, CameraRig::default()))
+        .id();
+    app.world_mut()
+        .spawn((OverviewPanel(ArenaName::Gala), Interaction::Pressed));
+    app.update();
+
+    assert_eq!(app.world().resource::<CurrentArena>().0, ArenaName::Gala);
+    let world = app.world();
+    assert!(world.get::<ZoomOut>(camera).is_none());
+    let transition = world
+        .get::<CameraRig>(camera)
+        .and_then(|rig| rig.transition.as_ref())
+        .expect("camera should be gliding into the arena");
+    assert_eq!(transition.from, overview);
+    assert_eq!(transition.to, arena_shot(ArenaName::Gala.as_u8(), ZOOM.0));
+}
+
+#[test]
+fn test_combat_log_window_follows_the_newest_entries_until_scrolled_back() {
+    assert_eq!(combat_log_window(10, 0), 0..10);
+    assert_eq!(combat_log_window(100, 0), 70..100);
+    assert_eq!(combat_log_window(100, 20), 50..80);
+    assert_eq!(
+        combat_log_window(100, 500),
+        0..30,
+        "stops at the oldest entry"
+    );
+}
+
+#[test]
+fn test_meter_rows_sort_by_the_chosen_column_and_compare_with_the_last_loop() {
+    let (tank, healer) = (MeterOwner::Hero(HeroId(3)), MeterOwner::Hero(HeroId(5)));
+    let mut meters = Meters::default();
+    meters.record_damage(ArenaName::Bastion, tank, 1_200.0);
+    meters.start_loop(ArenaName::Bastion);
+    meters.record_damage(ArenaName::Bastion, tank, 300.0);
+    meters.record_damage_taken(ArenaName::Bastion, tank, 50.0);
+    meters.record_healing(ArenaName::Bastion, healer, 400.0);
+
+    let arena = meters.arena(ArenaName::Bastion);
+    let rows = meter_rows(arena, MeterColumn::Healing, TimeStamp::new(10.0));
+    assert_eq!(
+        rows.iter().map(|row| row.hero).collect::<Vec<_>>(),
+        [healer, tank]
+    );
+    let tank_row = &rows[1];
+    assert_eq!(
+        tank_row.cell(MeterField::Total(MeterColumn::Damage)),
+        "30.0"
+    );
+    assert_eq!(
+        tank_row.cell(MeterField::Total(MeterColumn::DamageTaken)),
+        "5.0"
+    );
+    assert_eq!(tank_row.cell(MeterField::PreviousDps), "10.0");
+    assert_eq!(rows[0].cell(MeterField::Total(MeterColumn::Threat)), "200");
+    assert_eq!(rows[0].cell(MeterField::PreviousDps), "-");
+
+    let by_damage = meter_rows(arena, MeterColumn::Damage, TimeStamp::new(10.0));
+    assert_eq!(by_damage[0].hero, tank);
+}